@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::commands::logbook::fetch_entries;
+use crate::database::connection::establish_connection;
+use crate::models::attendance::{AttendanceSummary, DailyTimeRecord, DtrDay};
+use crate::models::logbook::Logbook;
+use crate::pdf::document::local_date;
+
+// Holidays and approved leave, which is what tells an absence apart from a day off
+pub struct Calendar {
+    dated: HashMap<NaiveDate, String>,
+    yearly: HashMap<(u32, u32), String>,
+    leaves: Vec<(i32, NaiveDate, NaiveDate, String)>,
+}
+
+impl Calendar {
+    pub fn load(conn: &Connection) -> Result<Self, String> {
+        let mut calendar = Calendar { dated: HashMap::new(), yearly: HashMap::new(), leaves: Vec::new() };
+
+        // special working days are ordinary work days
+        let mut stmt = conn
+            .prepare("SELECT name, month, day, year FROM holidays WHERE active = 1 AND type_ <> 'Special Working'")
+            .map_err(|e| e.to_string())?;
+        let holidays = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?, row.get::<_, Option<i32>>(3)?))
+            })
+            .map_err(|e| e.to_string())?;
+        for holiday in holidays {
+            let (name, month, day, year) = holiday.map_err(|e| e.to_string())?;
+            match year {
+                Some(year) => {
+                    if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                        calendar.dated.insert(date, name);
+                    }
+                }
+                None => {
+                    calendar.yearly.insert((month, day), name);
+                }
+            }
+        }
+
+        let mut stmt = conn
+            .prepare("SELECT official_id, start_date, end_date, leave_type FROM leave_requests WHERE status = 'Approved'")
+            .map_err(|e| e.to_string())?;
+        let leaves = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+            })
+            .map_err(|e| e.to_string())?;
+        for leave in leaves {
+            let (official_id, start, end, leave_type) = leave.map_err(|e| e.to_string())?;
+            if let (Some(start), Some(end)) = (local_date(&start), local_date(&end)) {
+                calendar.leaves.push((official_id, start, end, leave_type));
+            }
+        }
+
+        Ok(calendar)
+    }
+
+    // Name of the non-working holiday on a date
+    pub fn holiday(&self, date: NaiveDate) -> Option<&str> {
+        self.dated
+            .get(&date)
+            .or_else(|| self.yearly.get(&(date.month(), date.day())))
+            .map(String::as_str)
+    }
+
+    // Type of approved leave an official is on for a date
+    pub fn leave(&self, official_id: Option<i32>, date: NaiveDate) -> Option<&str> {
+        let official_id = official_id?;
+        self.leaves
+            .iter()
+            .find(|(official, start, end, _)| *official == official_id && (*start..=*end).contains(&date))
+            .map(|(_, _, _, leave_type)| leave_type.as_str())
+    }
+
+    // Days an official is expected to report for work
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && self.holiday(date).is_none()
+    }
+
+    // Adjusts the status worked out from the punches: time put in on a rest day or
+    // holiday is never short, and a day without punches is only an absence on a
+    // working day the official wasn't on leave
+    pub fn settle(&self, entry: &mut Logbook) {
+        let Some(date) = local_date(&entry.date) else {
+            return;
+        };
+        let attended = entry.status.is_some() && entry.status.as_deref() != Some("Absent");
+
+        if !self.is_working_day(date) {
+            entry.undertime_minutes = Some(0);
+            entry.status = Some(
+                if attended { "Present" } else if self.holiday(date).is_some() { "Holiday" } else { "Rest Day" }.to_string(),
+            );
+        } else if !attended && self.leave(entry.official_id, date).is_some() {
+            entry.undertime_minutes = Some(0);
+            entry.status = Some("On Leave".to_string());
+        }
+    }
+}
+
+// First and last day of a "YYYY-MM" month, the current month when none is given
+pub fn month_range(month: Option<&str>) -> Result<(NaiveDate, NaiveDate), String> {
+    let first = match month.map(str::trim).filter(|month| !month.is_empty()) {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| format!("'{}' is not a valid month. Use YYYY-MM, e.g. 2026-03.", month))?,
+        None => Local::now().date_naive().with_day(1).unwrap_or_default(),
+    };
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    };
+    let last = next.and_then(|next| next.pred_opt()).ok_or("The month is out of range.")?;
+    Ok((first, last))
+}
+
+// Start and end of a term; an open end is still in office
+type TermDates = (Option<NaiveDate>, Option<NaiveDate>);
+
+fn terms_of(conn: &Connection, official_id: i32) -> Result<Vec<TermDates>, String> {
+    let mut stmt = conn
+        .prepare("SELECT term_start, ended_on FROM official_terms WHERE official_id = ?1")
+        .map_err(|e| e.to_string())?;
+    let terms = stmt
+        .query_map(params![official_id], |row| {
+            let date = |value: Option<String>| value.as_deref().and_then(local_date);
+            Ok((date(row.get(0)?), date(row.get(1)?)))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(terms)
+}
+
+pub fn daily_time_record(conn: &Connection, official_id: i32, month: Option<&str>) -> Result<DailyTimeRecord, String> {
+    let (first, last) = month_range(month)?;
+    let (name, role): (String, String) = conn
+        .query_row("SELECT name, role FROM officials WHERE id = ?1", params![official_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Official {} does not exist.", official_id))?;
+
+    let entries = fetch_entries(conn, Some(official_id), Some(first), Some(last))?;
+    let calendar = Calendar::load(conn)?;
+    let terms = terms_of(conn, official_id)?;
+    // officials with no recorded terms are taken to be in office throughout
+    let in_office = |date: NaiveDate| {
+        terms.is_empty()
+            || terms
+                .iter()
+                .any(|(start, ended)| start.is_none_or(|start| start <= date) && ended.is_none_or(|ended| ended >= date))
+    };
+    let today = Local::now().date_naive();
+
+    let mut record = DailyTimeRecord {
+        official_id,
+        official_name: name,
+        official_role: role,
+        month: first.format("%Y-%m").to_string(),
+        days: Vec::new(),
+        days_present: 0,
+        absences: 0,
+        days_on_leave: 0,
+        holidays: 0,
+        hours: 0.0,
+        undertime_minutes: 0,
+    };
+
+    for date in first.iter_days().take_while(|date| *date <= last) {
+        // the first entry of the day counts if the day was logged twice before that was refused
+        let entry = entries.iter().find(|entry| local_date(&entry.date) == Some(date));
+        let mut day = DtrDay {
+            day: date.day(),
+            date: date.to_string(),
+            weekday: date.format("%A").to_string(),
+            time_in_am: None,
+            time_out_am: None,
+            time_in_pm: None,
+            time_out_pm: None,
+            status: None,
+            hours: 0.0,
+            undertime_minutes: 0,
+            remarks: None,
+        };
+
+        match entry {
+            Some(entry) => {
+                day.time_in_am = entry.time_in_am.clone();
+                day.time_out_am = entry.time_out_am.clone();
+                day.time_in_pm = entry.time_in_pm.clone();
+                day.time_out_pm = entry.time_out_pm.clone();
+                day.status = entry.status.clone();
+                day.remarks = entry.remarks.clone();
+                // an absence is not undertime
+                if entry.status.as_deref() != Some("Absent") {
+                    day.hours = entry.total_hours.unwrap_or_default();
+                    day.undertime_minutes = entry.undertime_minutes.unwrap_or_default();
+                }
+            }
+            // days before the official took office or after they left are left blank
+            None if !in_office(date) => {}
+            None => {
+                if let Some(holiday) = calendar.holiday(date) {
+                    day.status = Some("Holiday".to_string());
+                    day.remarks = Some(holiday.to_string());
+                } else if !calendar.is_working_day(date) {
+                    day.status = Some("Rest Day".to_string());
+                } else if let Some(leave_type) = calendar.leave(Some(official_id), date) {
+                    day.status = Some("On Leave".to_string());
+                    day.remarks = Some(leave_type.to_string());
+                } else if date < today {
+                    day.status = Some("Absent".to_string());
+                }
+            }
+        }
+
+        match day.status.as_deref() {
+            Some("Absent") => record.absences += 1,
+            Some("On Leave") => record.days_on_leave += 1,
+            Some("Holiday") => record.holidays += 1,
+            Some("Rest Day") | None => {}
+            Some(_) => record.days_present += 1,
+        }
+        record.hours += day.hours;
+        record.undertime_minutes += day.undertime_minutes;
+        record.days.push(day);
+    }
+    record.hours = (record.hours * 100.0).round() / 100.0;
+
+    Ok(record)
+}
+
+// Everyone who served at some point in the month
+pub fn attendance_summary(conn: &Connection, month: Option<&str>) -> Result<Vec<AttendanceSummary>, String> {
+    let (first, last) = month_range(month)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT o.id, o.section FROM officials o
+             WHERE EXISTS (SELECT 1 FROM official_terms t
+                           WHERE t.official_id = o.id
+                             AND (t.term_start IS NULL OR t.term_start <= ?2)
+                             AND (t.ended_on IS NULL OR t.ended_on >= ?1))
+             ORDER BY o.name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let officials = stmt
+        .query_map(params![first.to_string(), last.to_string()], |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut summary = Vec::new();
+    for (official_id, section) in officials {
+        let record = daily_time_record(conn, official_id, month)?;
+        let count = |status: &str| record.days.iter().filter(|day| day.status.as_deref() == Some(status)).count() as i32;
+        summary.push(AttendanceSummary {
+            official_id,
+            official_name: record.official_name.clone(),
+            official_role: record.official_role.clone(),
+            official_section: section,
+            days_present: record.days_present,
+            days_late: count("Late"),
+            days_undertime: count("Undertime"),
+            absences: record.absences,
+            days_on_leave: record.days_on_leave,
+            hours: record.hours,
+            undertime_minutes: record.undertime_minutes,
+        });
+    }
+
+    Ok(summary)
+}
+
+// Month is "YYYY-MM"; the current month when left out
+#[tauri::command]
+pub fn fetch_daily_time_record_command(official_id: i32, month: Option<String>) -> Result<DailyTimeRecord, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    daily_time_record(&conn, official_id, month.as_deref())
+}
+
+// Days present, absences and hours of every official for the month
+#[tauri::command]
+pub fn fetch_attendance_summary_command(month: Option<String>) -> Result<Vec<AttendanceSummary>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    attendance_summary(&conn, month.as_deref())
+}
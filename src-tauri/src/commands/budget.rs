@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::commands::vouchers::final_expense;
+use crate::database::connection::establish_connection;
+use crate::models::budget::{Budget, BudgetUtilization, BudgetUtilizationRow};
+use crate::models::expense::Expense;
+use crate::models::money::Money;
+use crate::pdf::document::{local_date, money};
+
+const BUDGET_KINDS: &[&str] = &["Original", "Supplemental"];
+
+fn budget_from_row(row: &Row) -> rusqlite::Result<Budget> {
+    Ok(Budget {
+        id: row.get(0)?,
+        fiscal_year: row.get(1)?,
+        category: row.get(2)?,
+        amount: row.get(3)?,
+        kind: row.get(4)?,
+        reference: row.get(5)?,
+        approved_date: row.get(6)?,
+        notes: row.get(7)?,
+    })
+}
+
+// Fiscal years follow the calendar year of the expense date
+fn fiscal_year(date: &str) -> Option<i32> {
+    local_date(date).map(|date| date.year())
+}
+
+// Original plus supplemental appropriations for a category; None when the
+// year has no budget entered at all, so nothing can be checked against it
+fn appropriation(conn: &Connection, fiscal_year: i32, category: &str) -> rusqlite::Result<Option<Money>> {
+    let budgeted: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM budgets WHERE fiscal_year = ?1",
+        params![fiscal_year],
+        |row| row.get(0),
+    )?;
+    if budgeted == 0 {
+        return Ok(None);
+    }
+
+    conn.query_row(
+        "SELECT COALESCE(SUM(amount), 0) FROM budgets WHERE fiscal_year = ?1 AND category = ?2 COLLATE NOCASE",
+        params![fiscal_year, category.trim()],
+        |row| row.get(0),
+    )
+    .map(Some)
+}
+
+// Expenses already charged to a category in the year, leaving out the one being edited.
+// Only expenses whose voucher has been approved count as spent.
+fn spent(conn: &Connection, year: i32, category: &str, exclude_id: Option<i32>) -> rusqlite::Result<Money> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT x.id, x.amount, x.date FROM expenses x WHERE x.category = ?1 COLLATE NOCASE AND {}",
+        final_expense("x")
+    ))?;
+    let rows = stmt.query_map(params![category.trim()], |row| {
+        Ok((row.get::<_, i32>(0)?, row.get::<_, Money>(1)?, row.get::<_, String>(2)?))
+    })?;
+
+    let mut total = Money::ZERO;
+    for row in rows {
+        let (id, amount, date) = row?;
+        if Some(id) != exclude_id && fiscal_year(&date) == Some(year) {
+            total += amount;
+        }
+    }
+    Ok(total)
+}
+
+// Compares an expense with what is left of its category's appropriation. Under the
+// "block" policy an overspend is refused; otherwise it comes back as a warning.
+pub fn check_appropriation(conn: &Connection, expense: &Expense) -> Result<Vec<String>, String> {
+    let Some(year) = fiscal_year(&expense.date) else {
+        return Ok(Vec::new());
+    };
+    let Some(appropriation) = appropriation(conn, year, &expense.category).map_err(|e| e.to_string())? else {
+        return Ok(Vec::new());
+    };
+    let remaining = appropriation - spent(conn, year, &expense.category, expense.id).map_err(|e| e.to_string())?;
+    if expense.amount <= remaining {
+        return Ok(Vec::new());
+    }
+
+    let message = if appropriation.is_zero() {
+        format!("{} has no appropriation in the {} budget.", expense.category, year)
+    } else if remaining.is_negative() {
+        format!(
+            "{} is already {} over its {} appropriation.",
+            expense.category,
+            money(-remaining),
+            year
+        )
+    } else {
+        format!(
+            "This expense of {} exceeds the remaining {} appropriation for {} ({} left) by {}.",
+            money(expense.amount),
+            year,
+            expense.category,
+            money(remaining),
+            money(expense.amount - remaining)
+        )
+    };
+
+    let policy: String = conn
+        .query_row("SELECT budget_overspend_policy FROM settings WHERE id = 1", [], |row| row.get(0))
+        .unwrap_or_else(|_| "warn".to_string());
+    if policy == "block" {
+        return Err(format!("{} Record a supplemental budget before charging it.", message));
+    }
+    Ok(vec![message])
+}
+
+fn validate(conn: &Connection, budget: &Budget) -> Result<(), String> {
+    if budget.category.trim().is_empty() {
+        return Err("A budget category is required.".into());
+    }
+    if !(1900..=9999).contains(&budget.fiscal_year) {
+        return Err(format!("{} is not a valid fiscal year.", budget.fiscal_year));
+    }
+    if !BUDGET_KINDS.contains(&budget.kind.as_str()) {
+        return Err(format!("Budget kind must be one of: {}.", BUDGET_KINDS.join(", ")));
+    }
+    if budget.kind == "Original" && budget.amount.is_negative() {
+        return Err("An original appropriation cannot be negative.".into());
+    }
+
+    if budget.kind == "Original" {
+        let existing: Option<i32> = conn
+            .query_row(
+                "SELECT id FROM budgets WHERE fiscal_year = ?1 AND category = ?2 COLLATE NOCASE AND kind = 'Original'",
+                params![budget.fiscal_year, budget.category.trim()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if existing.is_some_and(|id| Some(id) != budget.id) {
+            return Err(format!(
+                "{} already has an original appropriation for {}. Record changes as a supplemental budget.",
+                budget.category.trim(),
+                budget.fiscal_year
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn fetch_budgets_command(fiscal_year: Option<i32>) -> Result<Vec<Budget>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, fiscal_year, category, amount, kind, reference, approved_date, notes FROM budgets
+             WHERE ?1 IS NULL OR fiscal_year = ?1
+             ORDER BY fiscal_year DESC, category, kind DESC, id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let budget_iter = stmt
+        .query_map(params![fiscal_year], budget_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut budgets = Vec::new();
+    for budget in budget_iter {
+        budgets.push(budget.map_err(|e| e.to_string())?);
+    }
+
+    Ok(budgets)
+}
+
+#[tauri::command]
+pub fn save_budget_command(budget: Budget) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    validate(&conn, &budget)?;
+
+    if let Some(id) = budget.id {
+        conn.execute(
+            "UPDATE budgets SET fiscal_year = ?1, category = ?2, amount = ?3, kind = ?4, reference = ?5, approved_date = ?6, notes = ?7
+             WHERE id = ?8",
+            params![
+                budget.fiscal_year,
+                budget.category.trim(),
+                budget.amount,
+                budget.kind,
+                budget.reference,
+                budget.approved_date,
+                budget.notes,
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    } else {
+        conn.execute(
+            "INSERT INTO budgets (fiscal_year, category, amount, kind, reference, approved_date, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                budget.fiscal_year,
+                budget.category.trim(),
+                budget.amount,
+                budget.kind,
+                budget.reference,
+                budget.approved_date,
+                budget.notes
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_budget_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM budgets WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn utilization_row(category: String) -> BudgetUtilizationRow {
+    BudgetUtilizationRow {
+        category,
+        original: Money::ZERO,
+        supplemental: Money::ZERO,
+        appropriation: Money::ZERO,
+        quarters: [Money::ZERO; 4],
+        actual: Money::ZERO,
+        balance: Money::ZERO,
+        utilization: 0.0,
+    }
+}
+
+fn finish_row(row: &mut BudgetUtilizationRow) {
+    row.appropriation = row.original + row.supplemental;
+    row.actual = row.quarters.iter().sum();
+    row.balance = row.appropriation - row.actual;
+    row.utilization = if row.appropriation.is_zero() {
+        0.0
+    } else {
+        (row.actual.pesos() / row.appropriation.pesos() * 10000.0).round() / 100.0
+    };
+}
+
+// Budget versus actual per category for one fiscal year, with spending split by
+// quarter. Categories spent on without an appropriation are listed too.
+#[tauri::command]
+pub fn fetch_budget_utilization_command(fiscal_year: i32) -> Result<BudgetUtilization, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut rows: BTreeMap<String, BudgetUtilizationRow> = BTreeMap::new();
+
+    let mut stmt = conn
+        .prepare("SELECT category, kind, SUM(amount) FROM budgets WHERE fiscal_year = ?1 GROUP BY category, kind")
+        .map_err(|e| e.to_string())?;
+    let budgets = stmt
+        .query_map(params![fiscal_year], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Money>(2)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for budget in budgets {
+        let (category, kind, amount) = budget.map_err(|e| e.to_string())?;
+        let row = rows.entry(category.clone()).or_insert_with(|| utilization_row(category));
+        if kind == "Original" {
+            row.original += amount;
+        } else {
+            row.supplemental += amount;
+        }
+    }
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT x.category, x.amount, x.date FROM expenses x WHERE {}", final_expense("x")))
+        .map_err(|e| e.to_string())?;
+    let expenses = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Money>(1)?, row.get::<_, String>(2)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for expense in expenses {
+        let (category, amount, date) = expense.map_err(|e| e.to_string())?;
+        let Some(date) = local_date(&date).filter(|date| date.year() == fiscal_year) else {
+            continue;
+        };
+        let key = rows
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(category.trim()))
+            .cloned()
+            .unwrap_or_else(|| category.trim().to_string());
+        let row = rows.entry(key.clone()).or_insert_with(|| utilization_row(key));
+        row.quarters[date.month0() as usize / 3] += amount;
+    }
+
+    let mut total = utilization_row("Total".to_string());
+    let mut rows: Vec<BudgetUtilizationRow> = rows.into_values().collect();
+    for row in &mut rows {
+        finish_row(row);
+        total.original += row.original;
+        total.supplemental += row.supplemental;
+        for (quarter, amount) in total.quarters.iter_mut().zip(row.quarters) {
+            *quarter += amount;
+        }
+    }
+    finish_row(&mut total);
+
+    Ok(BudgetUtilization { fiscal_year, rows, total })
+}
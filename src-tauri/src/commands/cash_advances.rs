@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use chrono::{Days, Local, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::commands::expense::fetch_all_expenses_command;
+use crate::commands::ledger::{post_cash_advance, unpost};
+use crate::commands::vouchers::final_expense;
+use crate::database::connection::establish_connection;
+use crate::models::cash_advance::{CashAdvance, CashAdvanceAging};
+use crate::models::expense::Expense;
+use crate::models::money::Money;
+use crate::pdf::document::{local_date, money};
+
+// Advances have to be liquidated within 30 days unless a due date is given
+const LIQUIDATION_DAYS: u64 = 30;
+
+// Liquidations count once their voucher is approved
+fn advance_query() -> String {
+    format!(
+        "SELECT c.id, c.official_id, o.name, c.event_id, e.name, c.amount, c.purpose, c.date_granted, c.due_date,
+            c.refunded, c.refund_date,
+            (SELECT COALESCE(SUM(x.amount), 0) FROM expenses x
+             WHERE x.cash_advance_id = c.id AND {})
+     FROM cash_advances c
+     LEFT JOIN officials o ON o.id = c.official_id
+     LEFT JOIN events e ON e.id = c.event_id",
+        final_expense("x")
+    )
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+// Reads a cash advance and works out its balance and standing as of `as_of`
+fn advance_from_row(row: &Row, as_of: NaiveDate) -> rusqlite::Result<CashAdvance> {
+    let amount: Money = row.get(5)?;
+    let refunded: Money = row.get(9)?;
+    let liquidated: Money = row.get(11)?;
+    let due_date: String = row.get(8)?;
+
+    let balance = amount - liquidated - refunded;
+    let days_overdue = local_date(&due_date)
+        .map(|due| (as_of - due).num_days())
+        .unwrap_or_default();
+    let status = if balance <= Money::ZERO {
+        "Liquidated"
+    } else if days_overdue > 0 {
+        "Overdue"
+    } else {
+        "Open"
+    };
+
+    Ok(CashAdvance {
+        id: row.get(0)?,
+        official_id: row.get(1)?,
+        official_name: row.get(2)?,
+        event_id: row.get(3)?,
+        event_name: row.get(4)?,
+        amount,
+        purpose: row.get(6)?,
+        date_granted: row.get(7)?,
+        due_date: Some(due_date),
+        refunded,
+        refund_date: row.get(10)?,
+        liquidated,
+        balance,
+        status: Some(status.to_string()),
+        days_overdue: if balance > Money::ZERO { days_overdue.max(0) } else { 0 },
+    })
+}
+
+pub fn fetch_advances(conn: &Connection, official_id: Option<i32>, as_of: NaiveDate) -> Result<Vec<CashAdvance>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 IS NULL OR c.official_id = ?1 ORDER BY c.date_granted DESC, c.id DESC",
+            advance_query()
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let advance_iter = stmt
+        .query_map(params![official_id], |row| advance_from_row(row, as_of))
+        .map_err(|e| e.to_string())?;
+
+    let mut advances = Vec::new();
+    for advance in advance_iter {
+        advances.push(advance.map_err(|e| e.to_string())?);
+    }
+
+    Ok(advances)
+}
+
+fn fetch_advance(conn: &Connection, id: i32) -> Result<CashAdvance, String> {
+    conn.query_row(&format!("{} WHERE c.id = ?1", advance_query()), params![id], |row| {
+        advance_from_row(row, today())
+    })
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Cash advance {} does not exist.", id))
+}
+
+// Checks the advance an expense liquidates. Spending past the advance is allowed,
+// since the official is reimbursed, but comes back as a warning.
+pub fn check_liquidation(conn: &Connection, expense: &Expense) -> Result<Vec<String>, String> {
+    let Some(advance_id) = expense.cash_advance_id else {
+        return Ok(Vec::new());
+    };
+    let advance = fetch_advance(conn, advance_id)?;
+
+    // the balance already counts this expense when it is being edited
+    let previous: Money = match expense.id {
+        Some(id) => conn
+            .query_row(
+                "SELECT amount FROM expenses WHERE id = ?1 AND cash_advance_id = ?2",
+                params![id, advance_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default(),
+        None => Money::ZERO,
+    };
+    let remaining = advance.balance + previous;
+    if expense.amount <= remaining {
+        return Ok(Vec::new());
+    }
+
+    Ok(vec![format!(
+        "This liquidation exceeds the unliquidated balance of the cash advance to {} ({}) by {}; the excess is reimbursable.",
+        advance.official_name.unwrap_or_default(),
+        money(remaining),
+        money(expense.amount - remaining)
+    )])
+}
+
+fn validate(conn: &Connection, advance: &CashAdvance) -> Result<(), String> {
+    if advance.purpose.trim().is_empty() {
+        return Err("A purpose is required for the cash advance.".into());
+    }
+    if advance.amount <= Money::ZERO {
+        return Err("The amount advanced must be greater than zero.".into());
+    }
+    if advance.refunded.is_negative() || advance.refunded > advance.amount {
+        return Err("The amount refunded must be between zero and the amount advanced.".into());
+    }
+    let granted = local_date(&advance.date_granted).ok_or("The date granted is not a valid date.")?;
+    if let Some(due) = advance.due_date.as_deref().filter(|due| !due.trim().is_empty()) {
+        if local_date(due).is_none_or(|due| due < granted) {
+            return Err("The due date must be on or after the date granted.".into());
+        }
+    }
+
+    let official: Option<i32> = conn
+        .query_row("SELECT id FROM officials WHERE id = ?1", params![advance.official_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if official.is_none() {
+        return Err(format!("Official {} does not exist.", advance.official_id));
+    }
+    if let Some(event_id) = advance.event_id {
+        let event: Option<i32> = conn
+            .query_row("SELECT id FROM events WHERE id = ?1", params![event_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if event.is_none() {
+            return Err(format!("Event {} does not exist.", event_id));
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn fetch_cash_advances_command(official_id: Option<i32>) -> Result<Vec<CashAdvance>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    fetch_advances(&conn, official_id, today())
+}
+
+// Expenses filed as liquidations of an advance
+#[tauri::command]
+pub fn fetch_cash_advance_liquidations_command(id: i32) -> Result<Vec<Expense>, String> {
+    Ok(fetch_all_expenses_command()?
+        .into_iter()
+        .filter(|expense| expense.cash_advance_id == Some(id))
+        .collect())
+}
+
+#[tauri::command]
+pub fn save_cash_advance_command(advance: CashAdvance) -> Result<i32, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    validate(&conn, &advance)?;
+
+    let due_date = match advance.due_date.as_deref().filter(|due| !due.trim().is_empty()) {
+        Some(due) => due.to_string(),
+        None => local_date(&advance.date_granted)
+            .and_then(|granted| granted.checked_add_days(Days::new(LIQUIDATION_DAYS)))
+            .map(|due| due.to_string())
+            .unwrap_or_default(),
+    };
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = if let Some(id) = advance.id {
+        tx.execute(
+            "UPDATE cash_advances SET official_id = ?1, event_id = ?2, amount = ?3, purpose = ?4, date_granted = ?5, due_date = ?6,
+             refunded = ?7, refund_date = ?8
+             WHERE id = ?9",
+            params![
+                advance.official_id,
+                advance.event_id,
+                advance.amount,
+                advance.purpose.trim(),
+                advance.date_granted,
+                due_date,
+                advance.refunded,
+                advance.refund_date,
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        id
+    } else {
+        tx.execute(
+            "INSERT INTO cash_advances (official_id, event_id, amount, purpose, date_granted, due_date, refunded, refund_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                advance.official_id,
+                advance.event_id,
+                advance.amount,
+                advance.purpose.trim(),
+                advance.date_granted,
+                due_date,
+                advance.refunded,
+                advance.refund_date
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.last_insert_rowid() as i32
+    };
+    post_cash_advance(&tx, id as i64).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn delete_cash_advance_command(id: i32) -> Result<(), String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let liquidations: i64 = conn
+        .query_row("SELECT COUNT(*) FROM expenses WHERE cash_advance_id = ?1", params![id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if liquidations > 0 {
+        return Err(format!(
+            "This cash advance has {} liquidating expense(s). Remove them from the advance first.",
+            liquidations
+        ));
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    unpost(&tx, "cash_advance", id as i64).map_err(|e| e.to_string())?;
+    unpost(&tx, "cash_advance_refund", id as i64).map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM cash_advances WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Unliquidated balances per official as of a date (today by default), grouped by
+// how many days past the due date they are
+#[tauri::command]
+pub fn fetch_cash_advance_aging_command(as_of: Option<String>) -> Result<Vec<CashAdvanceAging>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let as_of = as_of.as_deref().and_then(local_date).unwrap_or_else(today);
+
+    let mut officials: BTreeMap<(String, i32), CashAdvanceAging> = BTreeMap::new();
+    for advance in fetch_advances(&conn, None, as_of)? {
+        let granted_later = local_date(&advance.date_granted).is_some_and(|granted| granted > as_of);
+        if advance.balance <= Money::ZERO || granted_later {
+            continue;
+        }
+        let name = advance.official_name.clone().unwrap_or_default();
+        let aging = officials
+            .entry((name.clone(), advance.official_id))
+            .or_insert_with(|| CashAdvanceAging { official_id: advance.official_id, official_name: name, ..Default::default() });
+
+        let bucket = match advance.days_overdue {
+            0 => &mut aging.current,
+            1..=30 => &mut aging.days_1_30,
+            31..=60 => &mut aging.days_31_60,
+            61..=90 => &mut aging.days_61_90,
+            _ => &mut aging.over_90,
+        };
+        *bucket += advance.balance;
+        aging.total += advance.balance;
+        aging.advances += 1;
+    }
+
+    Ok(officials.into_values().collect())
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::database::connection::establish_connection;
+use crate::models::certificate::Certificate;
+use crate::models::certificate_type::{CertificateType, CertificateTypeVersion};
+
+const TYPE_COLUMNS: &str =
+    "t.id, t.code, t.name, t.prefix, t.active, t.current_version, v.body, v.fee, v.required_fields, v.validity_days";
+
+fn parse_fields(json: String) -> Vec<String> {
+    serde_json::from_str(&json).unwrap_or_default()
+}
+
+fn certificate_type_from_row(row: &Row) -> rusqlite::Result<CertificateType> {
+    Ok(CertificateType {
+        id: row.get(0)?,
+        code: row.get(1)?,
+        name: row.get(2)?,
+        prefix: row.get(3)?,
+        active: row.get(4)?,
+        version: row.get(5)?,
+        body: row.get(6)?,
+        fee: row.get(7)?,
+        required_fields: parse_fields(row.get(8)?),
+        validity_days: row.get(9)?,
+    })
+}
+
+// Looks up the template a certificate is issued under, by its display name
+pub fn find_certificate_type(conn: &Connection, name: &str) -> rusqlite::Result<Option<(CertificateType, i64)>> {
+    conn.query_row(
+        &format!(
+            "SELECT {}, v.id FROM certificate_types t
+             JOIN certificate_type_versions v ON v.certificate_type_id = t.id AND v.version = t.current_version
+             WHERE t.name = ?1 COLLATE NOCASE",
+            TYPE_COLUMNS
+        ),
+        params![name],
+        |row| Ok((certificate_type_from_row(row)?, row.get(10)?)),
+    )
+    .optional()
+}
+
+// Names of required fields the certificate leaves blank
+pub fn missing_fields(certificate_type: &CertificateType, cert: &Certificate) -> Vec<String> {
+    let blank = |value: &Option<String>| value.as_deref().map(str::trim).unwrap_or("").is_empty();
+
+    certificate_type
+        .required_fields
+        .iter()
+        .filter(|field| match field.as_str() {
+            "age" => cert.age.is_none(),
+            "civil_status" => blank(&cert.civil_status),
+            "ownership_text" => blank(&cert.ownership_text),
+            "purpose" => blank(&cert.purpose),
+            "amount" => blank(&cert.amount),
+            "payer" => blank(&cert.payer),
+            "or_number" => cert.or_number.is_none(),
+            "resident_id" => cert.resident_id.is_none(),
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+// Wording a certificate was issued under: its recorded template version, or the
+// current version of its type for certificates saved before the registry existed
+pub fn template_body(conn: &Connection, template_version_id: Option<i32>, type_: &str) -> rusqlite::Result<Option<String>> {
+    if let Some(version_id) = template_version_id {
+        let body = conn
+            .query_row(
+                "SELECT body FROM certificate_type_versions WHERE id = ?1",
+                params![version_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if body.is_some() {
+            return Ok(body);
+        }
+    }
+
+    Ok(find_certificate_type(conn, type_)?.map(|(certificate_type, _)| certificate_type.body))
+}
+
+// Replaces {{placeholders}}; blanks and unknown names print as a fill-in line
+pub fn render_body(body: &str, values: &HashMap<&str, String>) -> String {
+    let mut rendered = String::with_capacity(body.len());
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let key = rest[start + 2..start + end].trim();
+        match values.get(key).map(|value| value.trim()).filter(|value| !value.is_empty()) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str("________________"),
+        }
+        rest = &rest[start + end + 2..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+fn validate(certificate_type: &CertificateType) -> Result<(), String> {
+    if certificate_type.code.trim().is_empty()
+        || certificate_type.name.trim().is_empty()
+        || certificate_type.prefix.trim().is_empty()
+    {
+        return Err("Certificate type code, name and prefix are required.".into());
+    }
+    if certificate_type.body.trim().is_empty() {
+        return Err("Certificate template body cannot be empty.".into());
+    }
+    if certificate_type.fee.is_negative() {
+        return Err("Certificate fee cannot be negative.".into());
+    }
+    Ok(())
+}
+
+// A prefix already printed on another type's control numbers can't be handed to this one
+fn check_prefix_free(conn: &Connection, prefix: &str, names: &[&str]) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT type_ FROM certificate_series WHERE prefix = ?1")
+        .map_err(|e| e.to_string())?;
+    let types = stmt
+        .query_map(params![prefix], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    match types.iter().find(|type_| !names.iter().any(|name| name.eq_ignore_ascii_case(type_))) {
+        Some(type_) => Err(format!("Prefix {} is already used on {} control numbers.", prefix, type_)),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn fetch_all_certificate_types_command() -> Result<Vec<CertificateType>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM certificate_types t
+             JOIN certificate_type_versions v ON v.certificate_type_id = t.id AND v.version = t.current_version
+             ORDER BY t.name",
+            TYPE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let type_iter = stmt
+        .query_map([], certificate_type_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut types = Vec::new();
+    for certificate_type in type_iter {
+        types.push(certificate_type.map_err(|e| e.to_string())?);
+    }
+
+    Ok(types)
+}
+
+#[tauri::command]
+pub fn fetch_certificate_type_versions_command(id: i32) -> Result<Vec<CertificateTypeVersion>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, certificate_type_id, version, body, fee, required_fields, validity_days, created_at
+             FROM certificate_type_versions WHERE certificate_type_id = ?1 ORDER BY version DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let version_iter = stmt
+        .query_map(params![id], |row| {
+            Ok(CertificateTypeVersion {
+                id: row.get(0)?,
+                certificate_type_id: row.get(1)?,
+                version: row.get(2)?,
+                body: row.get(3)?,
+                fee: row.get(4)?,
+                required_fields: parse_fields(row.get(5)?),
+                validity_days: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut versions = Vec::new();
+    for version in version_iter {
+        versions.push(version.map_err(|e| e.to_string())?);
+    }
+
+    Ok(versions)
+}
+
+#[tauri::command]
+pub fn insert_certificate_type_command(certificate_type: CertificateType) -> Result<(), String> {
+    validate(&certificate_type)?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    check_prefix_free(&tx, &certificate_type.prefix.trim().to_uppercase(), &[certificate_type.name.trim()])?;
+
+    tx.execute(
+        "INSERT INTO certificate_types (code, name, prefix, active, current_version) VALUES (?1, ?2, ?3, ?4, 1)",
+        params![
+            certificate_type.code.trim(),
+            certificate_type.name.trim(),
+            certificate_type.prefix.trim().to_uppercase(),
+            certificate_type.active
+        ],
+    ).map_err(|e| e.to_string())?;
+
+    tx.execute(
+        "INSERT INTO certificate_type_versions (certificate_type_id, version, body, fee, required_fields, validity_days)
+         VALUES (?1, 1, ?2, ?3, ?4, ?5)",
+        params![
+            tx.last_insert_rowid(),
+            certificate_type.body,
+            certificate_type.fee,
+            serde_json::to_string(&certificate_type.required_fields).map_err(|e| e.to_string())?,
+            certificate_type.validity_days
+        ],
+    ).map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Name, prefix and active flag are edited in place. Changes to the body, fee,
+// required fields or validity create a new version so issued certificates
+// keep pointing at the wording they were printed with.
+#[tauri::command]
+pub fn update_certificate_type_command(certificate_type: CertificateType) -> Result<(), String> {
+    validate(&certificate_type)?;
+    let id = certificate_type.id.ok_or("Certificate type id is required.")?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let current = tx
+        .query_row(
+            &format!(
+                "SELECT {} FROM certificate_types t
+                 JOIN certificate_type_versions v ON v.certificate_type_id = t.id AND v.version = t.current_version
+                 WHERE t.id = ?1",
+                TYPE_COLUMNS
+            ),
+            params![id],
+            certificate_type_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+    check_prefix_free(
+        &tx,
+        &certificate_type.prefix.trim().to_uppercase(),
+        &[certificate_type.name.trim(), current.name.as_str()],
+    )?;
+
+    tx.execute(
+        "UPDATE certificate_types SET code = ?1, name = ?2, prefix = ?3, active = ?4 WHERE id = ?5",
+        params![
+            certificate_type.code.trim(),
+            certificate_type.name.trim(),
+            certificate_type.prefix.trim().to_uppercase(),
+            certificate_type.active,
+            id
+        ],
+    ).map_err(|e| e.to_string())?;
+
+    // Control number series are kept per type name, so they follow a rename
+    // instead of starting the type over at 000001
+    let name = certificate_type.name.trim();
+    if name != current.name {
+        let numbered_elsewhere: bool = tx
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM certificate_series
+                     WHERE type_ = ?1 COLLATE NOCASE AND type_ <> ?2 COLLATE NOCASE)",
+                params![name, current.name],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if numbered_elsewhere {
+            return Err(format!("Certificates have already been numbered under the name '{}'. Choose another name.", name));
+        }
+        tx.execute(
+            "UPDATE certificate_series SET type_ = ?1 WHERE type_ = ?2 COLLATE NOCASE",
+            params![name, current.name],
+        ).map_err(|e| e.to_string())?;
+    }
+
+    let content_changed = current.body != certificate_type.body
+        || current.fee != certificate_type.fee
+        || current.required_fields != certificate_type.required_fields
+        || current.validity_days != certificate_type.validity_days;
+
+    if content_changed {
+        let next_version = current.version.unwrap_or(0) + 1;
+        tx.execute(
+            "INSERT INTO certificate_type_versions (certificate_type_id, version, body, fee, required_fields, validity_days)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                id,
+                next_version,
+                certificate_type.body,
+                certificate_type.fee,
+                serde_json::to_string(&certificate_type.required_fields).map_err(|e| e.to_string())?,
+                certificate_type.validity_days
+            ],
+        ).map_err(|e| e.to_string())?;
+        tx.execute(
+            "UPDATE certificate_types SET current_version = ?1 WHERE id = ?2",
+            params![next_version, id],
+        ).map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn save_certificate_type_command(certificate_type: CertificateType) -> Result<(), String> {
+    if certificate_type.id.is_some() {
+        update_certificate_type_command(certificate_type)
+    } else {
+        insert_certificate_type_command(certificate_type)
+    }
+}
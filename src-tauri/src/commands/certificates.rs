@@ -1,28 +1,323 @@
-use rusqlite::params;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+use sha2::{Digest, Sha256};
+use crate::commands::certificate_types::{find_certificate_type, missing_fields};
+use crate::commands::ledger::post_income;
+use crate::commands::officials::holder_on;
+use crate::commands::receipts::assign_or_number;
+use crate::commands::settings::fetch_settings_command;
 use crate::database::connection::establish_connection;
-use crate::models::certificate::Certificate; 
+use crate::models::certificate::{Certificate, CertificateBatch, CertificateIssuance, PendingBlotter};
+use crate::models::money::Money;
+use crate::pdf::document::local_date;
+use crate::pdf::write_certificate_batch;
 
-#[tauri::command]
-pub fn fetch_all_certificates_command() -> Result<Vec<Certificate>, String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+// "Barangay Clearance" -> "BC", "4Ps Certificate" -> "4C"
+fn control_prefix(type_: &str) -> String {
+    let prefix: String = type_
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .flat_map(char::to_uppercase)
+        .collect();
+
+    if prefix.is_empty() { "CERT".to_string() } else { prefix }
+}
+
+// Initials for a type missing from the registry, numbered on while another type
+// already prints them: "Certification of BARC" gets "COB2" after "Certification of Blood"
+fn fallback_prefix(conn: &Connection, type_: &str) -> rusqlite::Result<String> {
+    let initials = control_prefix(type_);
+    let mut prefix = initials.clone();
+    for n in 2.. {
+        let taken: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM certificate_types WHERE prefix = ?1)
+                 OR EXISTS (SELECT 1 FROM certificate_series WHERE prefix = ?1 AND type_ <> ?2 COLLATE NOCASE)",
+            params![prefix, type_],
+            |row| row.get(0),
+        )?;
+        if !taken {
+            break;
+        }
+        prefix = format!("{}{}", initials, n);
+    }
+    Ok(prefix)
+}
+
+// The webview sends a UTC timestamp; the certificate is dated by the local calendar day
+fn issue_date(issued_date: &Option<String>) -> NaiveDate {
+    issued_date
+        .as_deref()
+        .and_then(local_date)
+        .unwrap_or_else(|| Local::now().date_naive())
+}
+
+// Bumps the series for (type_, year) and formats the result, e.g. BC-2026-000123.
+// Must run inside the transaction that inserts the certificate so numbers are never skipped.
+pub fn allocate_control_number(conn: &Connection, type_: &str, year: i32) -> rusqlite::Result<String> {
+    let registered_prefix: Option<String> = conn
+        .query_row(
+            "SELECT prefix FROM certificate_types WHERE name = ?1 COLLATE NOCASE",
+            params![type_],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let prefix = match registered_prefix {
+        Some(prefix) => prefix,
+        None => fallback_prefix(conn, type_)?,
+    };
+
+    // The prefix is taken afresh each time so an edit in the registry applies to the next number
+    let (prefix, number): (String, i64) = conn.query_row(
+        "INSERT INTO certificate_series (type_, year, prefix, last_number) VALUES (?1, ?2, ?3, 1)
+         ON CONFLICT (type_, year) DO UPDATE SET last_number = last_number + 1, prefix = excluded.prefix
+         RETURNING prefix, last_number",
+        params![type_, year, prefix],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(format!("{}-{}-{:06}", prefix, year, number))
+}
+
+// SHA-256 over everything printed on the certificate and the template it was
+// printed from, so a copy can be checked later
+pub fn certificate_content_hash(conn: &Connection, cert: &Certificate, control_number: &str) -> rusqlite::Result<String> {
+    let (version, body): (Option<i64>, Option<String>) = match cert.template_version_id {
+        Some(id) => conn
+            .query_row(
+                "SELECT version, body FROM certificate_type_versions WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .unwrap_or((None, None)),
+        None => (None, None),
+    };
+
+    let content = [
+        format!("control_number={}", control_number),
+        format!("resident_name={}", cert.resident_name),
+        format!("type={}", cert.type_),
+        format!("age={}", cert.age.map(|a| a.to_string()).unwrap_or_default()),
+        format!("civil_status={}", cert.civil_status.clone().unwrap_or_default()),
+        format!("ownership_text={}", cert.ownership_text.clone().unwrap_or_default()),
+        format!("amount={}", cert.amount.clone().unwrap_or_default()),
+        format!("issued_date={}", cert.issued_date.clone().unwrap_or_default()),
+        format!("purpose={}", cert.purpose.clone().unwrap_or_default()),
+        format!("valid_until={}", cert.valid_until.clone().unwrap_or_default()),
+        format!("or_number={}", cert.or_number.map(|n| n.to_string()).unwrap_or_default()),
+        format!("payer={}", cert.payer.clone().unwrap_or_default()),
+        format!("template_version={}", version.map(|v| v.to_string()).unwrap_or_default()),
+        format!("template_body={}", body.unwrap_or_default()),
+    ]
+    .join("\n");
+
+    Ok(format!("{:x}", Sha256::digest(content.as_bytes())))
+}
+
+fn is_clearance(type_: &str) -> bool {
+    type_.to_lowercase().contains("clearance")
+}
+
+// Blotters still "Active" or "On Going" that name the resident as the respondent
+// (the "involved" side of "reported_by vs involved"). Names are compared word by
+// word within one party, so "Ann" does not match "Joanne" and a first name from
+// one party cannot pair with a last name from another.
+pub fn find_pending_blotters(
+    conn: &Connection,
+    resident_id: Option<i32>,
+    resident_name: &str,
+) -> rusqlite::Result<Vec<PendingBlotter>> {
+    let name = match resident_id {
+        Some(id) => conn
+            .query_row(
+                "SELECT first_name || ' ' || last_name FROM residents WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, String>(0),
+            )
+            .unwrap_or_else(|_| resident_name.to_string()),
+        None => resident_name.to_string(),
+    };
+    let terms = name_words(&name);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
 
     let mut stmt = conn.prepare(
-        "SELECT id, resident_name, type_, age, civil_status, ownership_text, amount, issued_date, purpose FROM certificates"
-    ).map_err(|e| e.to_string())?;
+        "SELECT id, type_, reported_by, involved, incident_date, status FROM blotters
+         WHERE status IN ('Active', 'On Going')
+         ORDER BY incident_date",
+    )?;
 
-    let certs_iter = stmt.query_map([], |row| {
-        Ok(Certificate {
+    let rows = stmt.query_map([], |row| {
+        Ok(PendingBlotter {
             id: row.get(0)?,
-            resident_name: row.get(1)?,
-            type_: row.get(2)?,
-            age: row.get(3).ok(),
-            civil_status: row.get(4)?,
-            ownership_text: row.get(5)?,
-            amount: row.get(6)?,
-            issued_date: row.get(7)?,
-            purpose: row.get(8)?,
+            type_: row.get(1)?,
+            reported_by: row.get(2)?,
+            involved: row.get(3)?,
+            incident_date: row.get(4)?,
+            status: row.get(5)?,
         })
-    }).map_err(|e| e.to_string())?;
+    })?;
+
+    let mut pending = Vec::new();
+    for blotter in rows {
+        let blotter = blotter?;
+        let named = respondents(&blotter.involved)
+            .iter()
+            .any(|party| terms.iter().all(|t| party.contains(t)));
+        if named {
+            pending.push(blotter);
+        }
+    }
+
+    Ok(pending)
+}
+
+fn name_words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// Several respondents may share the field: "Juan Cruz; Pedro Santos & Ana Reyes"
+fn respondents(involved: &str) -> Vec<Vec<String>> {
+    involved
+        .split([';', '/', '&', '\n'])
+        .flat_map(|part| part.split(" and "))
+        .map(name_words)
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+fn case_references(pending: &[PendingBlotter]) -> String {
+    pending
+        .iter()
+        .map(|b| format!("#{} {} ({}, {})", b.id, b.type_, b.status, b.incident_date))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[tauri::command]
+pub fn check_clearance_eligibility_command(
+    resident_id: Option<i32>,
+    resident_name: String,
+) -> Result<Vec<PendingBlotter>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    find_pending_blotters(&conn, resident_id, &resident_name).map_err(|e| e.to_string())
+}
+
+pub const CERTIFICATE_FEE_CATEGORY: &str = "Certificate fees";
+
+// Certificate.amount is free text: "10.00", "PHP 1,250.50". Blank means no fee.
+fn parse_fee(amount: &Option<String>) -> Result<Money, String> {
+    let raw = amount.as_deref().unwrap_or("").trim();
+    if raw.is_empty() {
+        return Ok(Money::ZERO);
+    }
+    Money::parse(raw)
+        .filter(|fee| !fee.is_negative())
+        .ok_or_else(|| format!("Certificate fee \"{}\" is not a valid amount.", raw))
+}
+
+// The treasurer in office when the certificate was issued
+fn treasurer_on(conn: &Connection, issued_on: NaiveDate) -> String {
+    holder_on(conn, "Treasurer", issued_on)
+        .ok()
+        .flatten()
+        .map(|(_, name)| name)
+        .unwrap_or_else(|| "Barangay Treasurer".to_string())
+}
+
+// Records the fee in incomes and links the two rows both ways
+fn post_certificate_fee(
+    conn: &Connection,
+    cert: &Certificate,
+    certificate_id: i64,
+    fee: Money,
+    booklet_id: Option<i32>,
+) -> rusqlite::Result<i64> {
+    let payer = cert
+        .payer
+        .clone()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| cert.resident_name.clone());
+    let date = cert
+        .issued_date
+        .clone()
+        .unwrap_or_else(|| Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true));
+
+    conn.execute(
+        "INSERT INTO incomes (type_, amount, or_number, received_from, received_by, category, date, certificate_id, or_booklet_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            format!("{} fee", cert.type_),
+            fee,
+            cert.or_number.unwrap_or(0),
+            payer,
+            treasurer_on(conn, issue_date(&cert.issued_date)),
+            CERTIFICATE_FEE_CATEGORY,
+            date,
+            certificate_id,
+            booklet_id
+        ],
+    )?;
+    let income_id = conn.last_insert_rowid();
+    post_income(conn, income_id)?;
+
+    conn.execute(
+        "UPDATE certificates SET income_id = ?1 WHERE id = ?2",
+        params![income_id, certificate_id],
+    )?;
+
+    Ok(income_id)
+}
+
+pub const CERTIFICATE_COLUMNS: &str =
+    "id, resident_name, type_, age, civil_status, ownership_text, amount, issued_date, purpose, resident_id,
+     control_number, content_hash, voided, void_reason, voided_at, or_number, payer, income_id,
+     template_version_id, valid_until";
+
+// Maps a row selected with CERTIFICATE_COLUMNS
+pub fn certificate_from_row(row: &Row) -> rusqlite::Result<Certificate> {
+    Ok(Certificate {
+        id: row.get(0)?,
+        resident_name: row.get(1)?,
+        type_: row.get(2)?,
+        age: row.get(3)?,
+        civil_status: row.get(4)?,
+        ownership_text: row.get(5)?,
+        amount: row.get(6)?,
+        issued_date: row.get(7)?,
+        purpose: row.get(8)?,
+        resident_id: row.get(9)?,
+        control_number: row.get(10)?,
+        content_hash: row.get(11)?,
+        voided: row.get(12)?,
+        void_reason: row.get(13)?,
+        voided_at: row.get(14)?,
+        or_number: row.get(15)?,
+        payer: row.get(16)?,
+        income_id: row.get(17)?,
+        template_version_id: row.get(18)?,
+        valid_until: row.get(19)?,
+    })
+}
+
+#[tauri::command]
+pub fn fetch_all_certificates_command() -> Result<Vec<Certificate>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM certificates", CERTIFICATE_COLUMNS))
+        .map_err(|e| e.to_string())?;
+
+    let certs_iter = stmt
+        .query_map([], certificate_from_row)
+        .map_err(|e| e.to_string())?;
 
     let mut certs = Vec::new();
     for cert in certs_iter {
@@ -32,14 +327,113 @@ pub fn fetch_all_certificates_command() -> Result<Vec<Certificate>, String> {
     Ok(certs)
 }
 
-#[tauri::command]
-pub fn insert_certificate_command(cert: Certificate) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+// Outcome of the checks run before a certificate is numbered; nothing is written yet
+struct IssuancePlan {
+    issued_on: NaiveDate,
+    template_version_id: Option<i64>,
+    valid_until: Option<String>,
+    fee: Money,
+    pending: Vec<PendingBlotter>,
+    override_reason: Option<String>,
+    warnings: Vec<String>,
+}
 
-    conn.execute(
+// Applies the template (fee, validity, required fields) and the blotter policy
+fn plan_issuance(conn: &Connection, cert: &mut Certificate, override_reason: Option<String>) -> Result<IssuancePlan, String> {
+    let issued_on = issue_date(&cert.issued_date);
+    let mut template_version_id = None;
+    let mut valid_until = None;
+    if let Some((certificate_type, version_id)) =
+        find_certificate_type(conn, &cert.type_).map_err(|e| e.to_string())?
+    {
+        if !certificate_type.active {
+            return Err(format!("{} is no longer being issued.", certificate_type.name));
+        }
+
+        let missing = missing_fields(&certificate_type, cert);
+        if !missing.is_empty() {
+            return Err(format!("{} requires: {}", certificate_type.name, missing.join(", ")));
+        }
+
+        if cert.amount.as_deref().unwrap_or("").trim().is_empty() && !certificate_type.fee.is_zero() {
+            cert.amount = Some(certificate_type.fee.to_string());
+        }
+        template_version_id = Some(version_id);
+        valid_until = certificate_type
+            .validity_days
+            .map(|days| (issued_on + Duration::days(days as i64)).format("%Y-%m-%d").to_string());
+    }
+
+    let pending = if is_clearance(&cert.type_) {
+        find_pending_blotters(conn, cert.resident_id, &cert.resident_name)
+            .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+    let override_reason = override_reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    let fee = parse_fee(&cert.amount)?;
+
+    let mut warnings = Vec::new();
+    if !pending.is_empty() {
+        let policy: String = conn
+            .query_row("SELECT clearance_blotter_policy FROM settings WHERE id = 1", [], |row| row.get(0))
+            .unwrap_or_else(|_| "warn".to_string());
+        let message = format!(
+            "{} is involved in unresolved blotter case(s): {}",
+            cert.resident_name,
+            case_references(&pending)
+        );
+
+        if policy == "block" && override_reason.is_none() {
+            return Err(format!("{}. An override reason is required to issue this clearance.", message));
+        }
+        warnings.push(message);
+    }
+
+    Ok(IssuancePlan {
+        issued_on,
+        template_version_id,
+        valid_until,
+        fee,
+        pending,
+        override_reason,
+        warnings,
+    })
+}
+
+// Numbers and stores a planned certificate. Runs inside the caller's transaction.
+fn record_issuance(tx: &Connection, cert: &mut Certificate, plan: IssuancePlan) -> Result<CertificateIssuance, String> {
+    let IssuancePlan {
+        issued_on,
+        template_version_id,
+        valid_until,
+        fee,
+        pending,
+        override_reason,
+        mut warnings,
+    } = plan;
+
+    // fees are receipted by the treasurer, from their booklet unless a number was typed
+    let mut booklet_id = None;
+    if !fee.is_zero() {
+        let (or_number, booklet) = assign_or_number(tx, cert.or_number.unwrap_or(0), &treasurer_on(tx, issued_on), None)?;
+        cert.or_number = Some(or_number).filter(|n| *n > 0);
+        booklet_id = booklet;
+    }
+
+    let control_number = allocate_control_number(tx, &cert.type_, issued_on.year())
+        .map_err(|e| e.to_string())?;
+    cert.template_version_id = template_version_id.map(|id| id as i32);
+    cert.valid_until = valid_until;
+    let content_hash = certificate_content_hash(tx, cert, &control_number).map_err(|e| e.to_string())?;
+
+    tx.execute(
         "INSERT INTO certificates (
-            resident_name, type_, age, civil_status, ownership_text, amount, issued_date, purpose
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            resident_name, type_, age, civil_status, ownership_text, amount, issued_date, purpose, resident_id,
+            control_number, content_hash, or_number, payer, template_version_id, valid_until
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         params![
             cert.resident_name,
             cert.type_,
@@ -48,16 +442,190 @@ pub fn insert_certificate_command(cert: Certificate) -> Result<(), String> {
             cert.ownership_text,
             cert.amount,
             cert.issued_date,
-            cert.purpose
+            cert.purpose,
+            cert.resident_id,
+            control_number,
+            content_hash,
+            cert.or_number,
+            cert.payer,
+            cert.template_version_id,
+            cert.valid_until
         ],
     ).map_err(|e| e.to_string())?;
-    Ok(())
+    let id = tx.last_insert_rowid();
+
+    let mut income_id = None;
+    if !fee.is_zero() {
+        income_id = Some(post_certificate_fee(tx, cert, id, fee, booklet_id).map_err(|e| e.to_string())?);
+        if cert.or_number.is_none() {
+            warnings.push("No official receipt number was given and the treasurer has no open OR booklet; the fee was recorded without one.".to_string());
+        }
+    }
+
+    if let Some(reason) = override_reason.as_ref().filter(|_| !pending.is_empty()) {
+        let blotter_ids = pending
+            .iter()
+            .map(|b| b.id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        tx.execute(
+            "INSERT INTO certificate_overrides (certificate_id, blotter_ids, reason) VALUES (?1, ?2, ?3)",
+            params![id, blotter_ids, reason],
+        ).map_err(|e| e.to_string())?;
+    }
+
+    Ok(CertificateIssuance {
+        id,
+        control_number,
+        income_id,
+        or_number: cert.or_number,
+        warnings,
+        pending_blotters: pending,
+    })
+}
+
+#[tauri::command]
+pub fn insert_certificate_command(
+    mut cert: Certificate,
+    override_reason: Option<String>,
+) -> Result<CertificateIssuance, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let plan = plan_issuance(&conn, &mut cert, override_reason)?;
+
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+    let issuance = record_issuance(&tx, &mut cert, plan)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(issuance)
+}
+
+// Builds the certificate for one resident of a batch from their registry record
+fn batch_certificate(conn: &Connection, resident_id: i32, type_: &str, purpose: &Option<String>, issued_date: &str) -> Result<Certificate, String> {
+    let (first_name, last_name, civil_status, date_of_birth): (String, String, String, String) = conn
+        .query_row(
+            "SELECT first_name, last_name, civil_status, date_of_birth FROM residents WHERE id = ?1",
+            params![resident_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Resident {} does not exist.", resident_id))?;
+
+    let issued_on = issue_date(&Some(issued_date.to_string()));
+    let age = local_date(&date_of_birth).and_then(|born| issued_on.years_since(born)).map(|years| years as i32);
+
+    Ok(Certificate {
+        id: 0,
+        resident_name: format!("{} {}", first_name.trim(), last_name.trim()),
+        type_: type_.to_string(),
+        age,
+        civil_status: Some(civil_status).filter(|status| !status.trim().is_empty()),
+        ownership_text: None,
+        amount: None,
+        issued_date: Some(issued_date.to_string()),
+        purpose: purpose.clone().filter(|purpose| !purpose.trim().is_empty()),
+        resident_id: Some(resident_id),
+        control_number: None,
+        content_hash: None,
+        voided: false,
+        void_reason: None,
+        voided_at: None,
+        or_number: None,
+        payer: None,
+        income_id: None,
+        template_version_id: None,
+        valid_until: None,
+    })
+}
+
+// Issues the same certificate to many residents at once, e.g. during 4Ps or
+// scholarship season. Every certificate is numbered in one transaction, and
+// nothing is recorded unless all of them pass and the printout is written.
+// `format` is "pdf" for one merged file (default) or "zip" for one PDF each.
+#[tauri::command]
+pub fn issue_certificate_batch_command(
+    resident_ids: Vec<i32>,
+    type_: String,
+    purpose: Option<String>,
+    format: Option<String>,
+    output_path: String,
+) -> Result<CertificateBatch, String> {
+    let format = format.unwrap_or_else(|| "pdf".to_string()).to_lowercase();
+    if format != "pdf" && format != "zip" {
+        return Err(format!("Unknown batch format '{}'. Use \"pdf\" or \"zip\".", format));
+    }
+    let output = PathBuf::from(output_path.trim());
+    if output.as_os_str().is_empty() {
+        return Err("Choose where to save the certificates.".into());
+    }
+
+    let mut seen = HashSet::new();
+    let resident_ids: Vec<i32> = resident_ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    if resident_ids.is_empty() {
+        return Err("Select at least one resident.".into());
+    }
+
+    let settings = fetch_settings_command()?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let issued_date = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    let mut planned = Vec::new();
+    let mut problems = Vec::new();
+    for resident_id in resident_ids {
+        let planned_cert = batch_certificate(&conn, resident_id, &type_, &purpose, &issued_date)
+            .and_then(|mut cert| plan_issuance(&conn, &mut cert, None).map(|plan| (cert, plan)));
+        match planned_cert {
+            Ok(entry) => planned.push(entry),
+            Err(e) => problems.push(format!("Resident {}: {}", resident_id, e)),
+        }
+    }
+    if !problems.is_empty() {
+        return Err(format!("No certificates were issued.\n{}", problems.join("\n")));
+    }
+
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+
+    let mut issuances = Vec::new();
+    let mut certs = Vec::new();
+    for (mut cert, plan) in planned {
+        let issuance = record_issuance(&tx, &mut cert, plan)?;
+        certs.push(
+            tx.query_row(
+                &format!("SELECT {} FROM certificates WHERE id = ?1", CERTIFICATE_COLUMNS),
+                params![issuance.id],
+                certificate_from_row,
+            )
+            .map_err(|e| e.to_string())?,
+        );
+        issuances.push(issuance);
+    }
+
+    write_certificate_batch(&tx, &settings, &certs, &format, &output)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(CertificateBatch {
+        certificates: issuances,
+        output_path: output.to_string_lossy().into_owned(),
+    })
 }
 
 #[tauri::command]
 pub fn update_certificate_command(cert: Certificate) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
+    // Numbered certificates are hashed at issuance; editing them would break verification
+    if let Some(control_number) = issued_control_number(&conn, cert.id)? {
+        return Err(format!(
+            "Certificate {} has already been issued. Void it and issue a new one instead.",
+            control_number
+        ));
+    }
+
     conn.execute(
         "UPDATE certificates SET
             resident_name = ?1,
@@ -66,9 +634,10 @@ pub fn update_certificate_command(cert: Certificate) -> Result<(), String> {
             civil_status = ?4,
             ownership_text = ?5,
             amount = ?6,
-            issued_date = ?7
-            purpose = ?8
-        WHERE id = ?8",
+            issued_date = ?7,
+            purpose = ?8,
+            resident_id = ?9
+        WHERE id = ?10",
         params![
             cert.resident_name,
             cert.type_,
@@ -78,6 +647,7 @@ pub fn update_certificate_command(cert: Certificate) -> Result<(), String> {
             cert.amount,
             cert.issued_date,
             cert.purpose,
+            cert.resident_id,
             cert.id
         ],
     ).map_err(|e| e.to_string())?;
@@ -86,18 +656,99 @@ pub fn update_certificate_command(cert: Certificate) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn save_certificate_command(cert: Certificate) -> Result<(), String> {
+pub fn save_certificate_command(
+    cert: Certificate,
+    override_reason: Option<String>,
+) -> Result<Option<CertificateIssuance>, String> {
     if cert.id != 0 {
-        update_certificate_command(cert)
+        update_certificate_command(cert).map(|_| None)
     } else {
-        insert_certificate_command(cert)
+        insert_certificate_command(cert, override_reason).map(Some)
     }
 }
 
 #[tauri::command]
 pub fn delete_certificate_command(id: i32) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    if let Some(control_number) = issued_control_number(&conn, id)? {
+        return Err(format!(
+            "Certificate {} has a control number and cannot be deleted. Void it instead.",
+            control_number
+        ));
+    }
     conn.execute("DELETE FROM certificates WHERE id = ?", params![id])
         .map_err(|e| e.to_string())?;
     Ok(())
-}
\ No newline at end of file
+}
+
+// Voiding keeps the number. A linked fee is either reversed with a negative
+// income entry or flagged for the treasurer to settle by hand.
+#[tauri::command]
+pub fn void_certificate_command(id: i32, reason: String, reverse_fee: Option<bool>) -> Result<(), String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err("A reason is required to void a certificate.".into());
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let updated = tx.execute(
+        "UPDATE certificates SET voided = 1, void_reason = ?1, voided_at = CURRENT_TIMESTAMP
+         WHERE id = ?2 AND voided = 0",
+        params![reason, id],
+    ).map_err(|e| e.to_string())?;
+
+    if updated == 0 {
+        return Err(format!("Certificate {} does not exist or is already void.", id));
+    }
+
+    let (control_number, income_id): (Option<String>, Option<i64>) = tx
+        .query_row(
+            "SELECT control_number, income_id FROM certificates WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let label = control_number.unwrap_or_else(|| format!("#{}", id));
+
+    if let Some(income_id) = income_id {
+        if reverse_fee.unwrap_or(false) {
+            // the reversal issues no receipt of its own; it points back at the one it cancels
+            tx.execute(
+                "INSERT INTO incomes (type_, amount, or_number, received_from, received_by, category, date, certificate_id, flag)
+                 SELECT 'Reversal: ' || type_, -amount, 0, received_from, received_by, category, ?1, certificate_id,
+                     'Reverses OR ' || or_number || ' (income #' || id || ')'
+                 FROM incomes WHERE id = ?2",
+                params![Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true), income_id],
+            ).map_err(|e| e.to_string())?;
+            post_income(&tx, tx.last_insert_rowid()).map_err(|e| e.to_string())?;
+            tx.execute(
+                "UPDATE incomes SET flag = ?1 WHERE id = ?2",
+                params![format!("Reversed: certificate {} voided ({})", label, reason), income_id],
+            ).map_err(|e| e.to_string())?;
+        } else {
+            tx.execute(
+                "UPDATE incomes SET flag = ?1 WHERE id = ?2",
+                params![format!("Certificate {} voided ({}); fee not reversed", label, reason), income_id],
+            ).map_err(|e| e.to_string())?;
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn issued_control_number(conn: &Connection, id: i32) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT control_number FROM certificates WHERE id = ?1",
+        params![id],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .optional()
+    .map(Option::flatten)
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::commands::blotters::fetch_all_blotters_command;
+use crate::commands::events::fetch_all_events_command;
+use crate::commands::expense::fetch_all_expenses_command;
+use crate::commands::officials::{date_or_today, open_term, IN_OFFICE};
+use crate::database::connection::establish_connection;
+use crate::models::committee::{Committee, CommitteeMember, CommitteeRecords};
+
+const POSITIONS: &[&str] = &["Chair", "Member"];
+
+// Record types that can be tagged to a committee, with their tables
+const TAGGABLE: &[(&str, &str, &str)] = &[
+    ("blotter", "blotters", "Blotter"),
+    ("event", "events", "Event"),
+    ("expense", "expenses", "Expense"),
+];
+
+fn check_committee(conn: &Connection, id: i32) -> Result<(), String> {
+    let active: Option<bool> = conn
+        .query_row("SELECT active FROM committees WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match active {
+        Some(true) => Ok(()),
+        Some(false) => Err(format!("Committee {} has been dissolved.", id)),
+        None => Err(format!("Committee {} does not exist.", id)),
+    }
+}
+
+// Active committees with the members seated on `date`, chair first
+fn fetch_committees(conn: &Connection, date: &str, id: Option<i32>) -> Result<Vec<Committee>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT a.committee_id, a.id, o.id, o.name, t.id, a.position
+             FROM committee_assignments a
+             JOIN official_terms t ON t.id = a.term_id
+             JOIN officials o ON o.id = t.official_id
+             WHERE {}
+             ORDER BY a.position = 'Chair' DESC, o.name COLLATE NOCASE",
+            IN_OFFICE
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![date], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                CommitteeMember {
+                    assignment_id: row.get(1)?,
+                    official_id: row.get(2)?,
+                    official_name: row.get(3)?,
+                    term_id: row.get(4)?,
+                    position: row.get(5)?,
+                },
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut members: HashMap<i32, Vec<CommitteeMember>> = HashMap::new();
+    for row in rows {
+        let (committee_id, member) = row.map_err(|e| e.to_string())?;
+        members.entry(committee_id).or_default().push(member);
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, description FROM committees
+             WHERE active = 1 AND (?1 IS NULL OR id = ?1)
+             ORDER BY name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![id], |row| {
+            Ok(Committee {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                chair: None,
+                members: Vec::new(),
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut committees = Vec::new();
+    for committee in rows {
+        let mut committee = committee.map_err(|e| e.to_string())?;
+        committee.members = committee.id.and_then(|id| members.remove(&id)).unwrap_or_default();
+        committee.chair = committee.members.iter().find(|member| member.position == "Chair").cloned();
+        committees.push(committee);
+    }
+    Ok(committees)
+}
+
+// Committees with their chair and members as of a date (today by default)
+#[tauri::command]
+pub fn fetch_committees_command(date: Option<String>) -> Result<Vec<Committee>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let date = date_or_today(date.as_deref())?;
+    fetch_committees(&conn, &date.to_string(), None)
+}
+
+#[tauri::command]
+pub fn save_committee_command(committee: Committee) -> Result<i32, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let name = committee.name.trim();
+    if name.is_empty() {
+        return Err("A name is required for the committee.".into());
+    }
+    let taken: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM committees WHERE name = ?1 AND id IS NOT ?2",
+            params![name, committee.id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if taken.is_some() {
+        return Err(format!("A committee named '{}' already exists.", name));
+    }
+    let description = committee.description.as_deref().map(str::trim).filter(|description| !description.is_empty());
+
+    if let Some(id) = committee.id {
+        conn.execute(
+            "UPDATE committees SET name = ?1, description = ?2 WHERE id = ?3",
+            params![name, description, id],
+        )
+        .map_err(|e| e.to_string())?;
+        return Ok(id);
+    }
+
+    conn.execute(
+        "INSERT INTO committees (name, description) VALUES (?1, ?2)",
+        params![name, description],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+// Committees with members or records on file are dissolved rather than deleted,
+// so past assignments and tags keep their name
+#[tauri::command]
+pub fn delete_committee_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let used: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM committee_assignments WHERE committee_id = ?1)
+                 OR EXISTS (SELECT 1 FROM blotters WHERE committee_id = ?1)
+                 OR EXISTS (SELECT 1 FROM events WHERE committee_id = ?1)
+                 OR EXISTS (SELECT 1 FROM expenses WHERE committee_id = ?1)",
+            params![id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if used {
+        conn.execute("UPDATE committees SET active = 0 WHERE id = ?1", params![id])
+    } else {
+        conn.execute("DELETE FROM committees WHERE id = ?1", params![id])
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Seats an official on a committee for their current term. Assigning someone
+// already on it changes their position.
+#[tauri::command]
+pub fn assign_committee_member_command(committee_id: i32, official_id: i32, position: String) -> Result<Committee, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    if !POSITIONS.contains(&position.as_str()) {
+        return Err(format!("The position must be one of: {}.", POSITIONS.join(", ")));
+    }
+    check_committee(&conn, committee_id)?;
+    let (term_id, _) = open_term(&conn, official_id)?
+        .ok_or_else(|| format!("Official {} is not serving a term.", official_id))?;
+
+    if position == "Chair" {
+        let chair: Option<String> = conn
+            .query_row(
+                "SELECT o.name FROM committee_assignments a
+                 JOIN official_terms t ON t.id = a.term_id
+                 JOIN officials o ON o.id = t.official_id
+                 WHERE a.committee_id = ?1 AND a.position = 'Chair' AND t.ended_on IS NULL AND a.term_id <> ?2",
+                params![committee_id, term_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if let Some(chair) = chair {
+            return Err(format!("{} already chairs this committee. Make them a member first.", chair));
+        }
+    }
+
+    conn.execute(
+        "INSERT INTO committee_assignments (committee_id, term_id, position) VALUES (?1, ?2, ?3)
+         ON CONFLICT (committee_id, term_id) DO UPDATE SET position = excluded.position",
+        params![committee_id, term_id, position],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let today = date_or_today(None)?;
+    fetch_committees(&conn, &today.to_string(), Some(committee_id))?
+        .pop()
+        .ok_or_else(|| format!("Committee {} does not exist.", committee_id))
+}
+
+#[tauri::command]
+pub fn remove_committee_member_command(assignment_id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM committee_assignments WHERE id = ?1", params![assignment_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Tags a blotter, event or expense to the committee responsible; None clears it
+#[tauri::command]
+pub fn tag_committee_command(record_type: String, record_id: i32, committee_id: Option<i32>) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let (_, table, label) = TAGGABLE
+        .iter()
+        .find(|(type_, _, _)| *type_ == record_type)
+        .ok_or_else(|| format!("Only blotters, events and expenses can be tagged to a committee, not '{}'.", record_type))?;
+    if let Some(committee_id) = committee_id {
+        check_committee(&conn, committee_id)?;
+    }
+
+    let updated = conn
+        .execute(
+            &format!("UPDATE {} SET committee_id = ?1 WHERE id = ?2", table),
+            params![committee_id, record_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("{} {} does not exist.", label, record_id));
+    }
+
+    Ok(())
+}
+
+// Blotters, events and expenses tagged to a committee
+#[tauri::command]
+pub fn fetch_committee_records_command(committee_id: i32) -> Result<CommitteeRecords, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let tagged = |table: &str| -> Result<HashSet<i32>, String> {
+        let mut stmt = conn
+            .prepare(&format!("SELECT id FROM {} WHERE committee_id = ?1", table))
+            .map_err(|e| e.to_string())?;
+        let ids = stmt
+            .query_map(params![committee_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<HashSet<i32>, _>>()
+            .map_err(|e| e.to_string());
+        ids
+    };
+    let blotters = tagged("blotters")?;
+    let events = tagged("events")?;
+    let expenses = tagged("expenses")?;
+
+    Ok(CommitteeRecords {
+        committee_id,
+        blotters: fetch_all_blotters_command()?
+            .into_iter()
+            .filter(|blotter| blotter.id.is_some_and(|id| blotters.contains(&id)))
+            .collect(),
+        events: fetch_all_events_command()?
+            .into_iter()
+            .filter(|event| event.id.is_some_and(|id| events.contains(&id)))
+            .collect(),
+        expenses: fetch_all_expenses_command()?
+            .into_iter()
+            .filter(|expense| expense.id.is_some_and(|id| expenses.contains(&id)))
+            .collect(),
+    })
+}
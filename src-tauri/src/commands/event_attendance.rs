@@ -0,0 +1,233 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+
+use crate::commands::events::{fetch_event, Schedule, CANCELLED};
+use crate::database::connection::establish_connection;
+use crate::models::event::{Event, EventAttendance};
+use crate::pdf::document::{local_date, local_datetime, long_date};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+// Residents are listed surname first, as on the printed sheet
+const ATTENDANCE_QUERY: &str = "SELECT a.id, a.event_id, a.resident_id,
+            r.last_name || ', ' || r.first_name || COALESCE(' ' || NULLIF(TRIM(r.suffix), ''), ''),
+            r.zone, r.household_number, a.sitting_date, a.checked_in_at, a.items_received, a.recorded_by
+     FROM event_attendance a
+     LEFT JOIN residents r ON r.id = a.resident_id";
+
+fn attendance_from_row(row: &Row) -> rusqlite::Result<EventAttendance> {
+    Ok(EventAttendance {
+        id: row.get(0)?,
+        event_id: row.get(1)?,
+        resident_id: row.get(2)?,
+        resident_name: row.get(3)?,
+        zone: row.get(4)?,
+        household_number: row.get(5)?,
+        sitting_date: row.get(6)?,
+        checked_in_at: row.get(7)?,
+        items_received: row.get(8)?,
+        recorded_by: row.get(9)?,
+    })
+}
+
+// Attendance of an event, of one sitting when a date is given, in check-in order
+pub fn fetch_attendance(conn: &Connection, event_id: i32, sitting_date: Option<&str>) -> Result<Vec<EventAttendance>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE a.event_id = ?1 AND (?2 IS NULL OR a.sitting_date = ?2)
+             ORDER BY a.sitting_date, a.checked_in_at, a.id",
+            ATTENDANCE_QUERY
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![event_id, sitting_date], attendance_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut attendance = Vec::new();
+    for attendee in rows {
+        attendance.push(attendee.map_err(|e| e.to_string())?);
+    }
+    Ok(attendance)
+}
+
+fn fetch_attendee(conn: &Connection, id: i32) -> Result<EventAttendance, String> {
+    conn.query_row(&format!("{} WHERE a.id = ?1", ATTENDANCE_QUERY), params![id], attendance_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Attendance record {} does not exist.", id))
+}
+
+// The sitting a check-in belongs to: the one named, or else the one running on
+// the day of the check-in
+fn sitting_for(event: &Event, sitting_date: Option<&str>, checked_in_at: NaiveDateTime) -> Result<NaiveDate, String> {
+    let schedule = Schedule::of(event)?;
+    match sitting_date.map(str::trim).filter(|date| !date.is_empty()) {
+        Some(value) => {
+            let date = local_date(value).ok_or_else(|| format!("'{}' is not a valid date.", value))?;
+            if !schedule.occurrences(date).iter().any(|(start, _)| start.date() == date) {
+                return Err(format!("'{}' has no sitting on {}.", event.name, long_date(value)));
+            }
+            Ok(date)
+        }
+        None => {
+            let day = checked_in_at.date();
+            schedule
+                .occurrences(day)
+                .into_iter()
+                .rev()
+                .find(|(start, end)| start.date() <= day && day <= end.date())
+                .map(|(start, _)| start.date())
+                .ok_or_else(|| format!("'{}' has no sitting on {}; choose the sitting to record.", event.name, long_date(&day.to_string())))
+        }
+    }
+}
+
+// Goods go to each resident once per event, and to each household once when the
+// event says so
+fn check_claim(conn: &Connection, event: &Event, resident_id: i32, except: Option<i32>) -> Result<(), String> {
+    let claim: Option<(i32, String, String, String, Option<String>)> = conn
+        .query_row(
+            "SELECT a.resident_id, r.first_name || ' ' || r.last_name, a.items_received, a.sitting_date, r.household_number
+             FROM event_attendance a
+             JOIN residents r ON r.id = a.resident_id
+             WHERE a.event_id = ?1 AND a.items_received IS NOT NULL AND a.id IS NOT ?2
+               AND (a.resident_id = ?3
+                    OR (?4 AND TRIM(r.household_number) <> ''
+                        AND r.household_number = (SELECT household_number FROM residents WHERE id = ?3)))
+             ORDER BY a.resident_id = ?3 DESC LIMIT 1",
+            params![event.id, except, resident_id, event.claim_per_household],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match claim {
+        None => Ok(()),
+        Some((claimant, name, items, date, _)) if claimant == resident_id => {
+            Err(format!("{} already received {} on {}.", name, items, long_date(&date)))
+        }
+        Some((_, name, items, date, household)) => Err(format!(
+            "{} of household {} already received {} on {}; each household claims once.",
+            name,
+            household.unwrap_or_default(),
+            items,
+            long_date(&date)
+        )),
+    }
+}
+
+fn items(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|items| !items.is_empty()).map(str::to_string)
+}
+
+#[tauri::command]
+pub fn fetch_event_attendance_command(event_id: i32, sitting_date: Option<String>) -> Result<Vec<EventAttendance>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let sitting_date = match sitting_date.as_deref().map(str::trim).filter(|date| !date.is_empty()) {
+        Some(value) => Some(local_date(value).ok_or_else(|| format!("'{}' is not a valid date.", value))?.to_string()),
+        None => None,
+    };
+    fetch_attendance(&conn, event_id, sitting_date.as_deref())
+}
+
+// Checks a resident in to a sitting, recording any goods handed over at the same time
+#[tauri::command]
+pub fn register_event_attendee_command(attendance: EventAttendance) -> Result<EventAttendance, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    // the claim check and the write share one lock, as certificate numbering does,
+    // so two stations can't both hand goods to the same household
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+    let event = fetch_event(&tx, attendance.event_id)?;
+    if event.status == CANCELLED {
+        return Err(format!("'{}' was cancelled; attendance can't be recorded.", event.name));
+    }
+    let name: String = tx
+        .query_row(
+            "SELECT first_name || ' ' || last_name FROM residents WHERE id = ?1",
+            params![attendance.resident_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Resident {} does not exist.", attendance.resident_id))?;
+
+    let checked_in_at = match attendance.checked_in_at.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => local_datetime(value).ok_or_else(|| format!("The check-in time '{}' is not a valid date and time.", value))?,
+        None => Local::now().naive_local(),
+    };
+    let sitting = sitting_for(&event, attendance.sitting_date.as_deref(), checked_in_at)?.to_string();
+
+    let earlier: Option<String> = tx
+        .query_row(
+            "SELECT checked_in_at FROM event_attendance WHERE event_id = ?1 AND resident_id = ?2 AND sitting_date = ?3",
+            params![attendance.event_id, attendance.resident_id, sitting],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some(earlier) = earlier {
+        let time = local_datetime(&earlier).map(|time| time.format("%-I:%M %p").to_string()).unwrap_or(earlier);
+        return Err(format!("{} already checked in to this sitting at {}.", name, time));
+    }
+
+    let items_received = items(&attendance.items_received);
+    if items_received.is_some() {
+        check_claim(&tx, &event, attendance.resident_id, None)?;
+    }
+
+    tx.execute(
+        "INSERT INTO event_attendance (event_id, resident_id, sitting_date, checked_in_at, items_received, recorded_by)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            attendance.event_id,
+            attendance.resident_id,
+            sitting,
+            checked_in_at.format(DATETIME_FORMAT).to_string(),
+            items_received,
+            items(&attendance.recorded_by),
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let attendee = fetch_attendee(&tx, tx.last_insert_rowid() as i32)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(attendee)
+}
+
+// Records goods handed to someone already checked in
+#[tauri::command]
+pub fn record_items_received_command(attendance_id: i32, items_received: String, recorded_by: Option<String>) -> Result<EventAttendance, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    // locked for the claim check, as at check-in
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+    let attendee = fetch_attendee(&tx, attendance_id)?;
+    let items_received = items(&Some(items_received)).ok_or("Enter the items received.")?;
+    let event = fetch_event(&tx, attendee.event_id)?;
+    check_claim(&tx, &event, attendee.resident_id, Some(attendance_id))?;
+
+    tx.execute(
+        "UPDATE event_attendance SET items_received = ?1, recorded_by = COALESCE(?2, recorded_by) WHERE id = ?3",
+        params![items_received, items(&recorded_by), attendance_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let attendee = fetch_attendee(&tx, attendance_id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(attendee)
+}
+
+#[tauri::command]
+pub fn remove_event_attendee_command(attendance_id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM event_attendance WHERE id = ?1", params![attendance_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
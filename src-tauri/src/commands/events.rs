@@ -1,28 +1,353 @@
-use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{Days, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use crate::commands::cash_advances::fetch_advances;
 use crate::database::connection::establish_connection;
-use crate::models::event::Event;
+use crate::models::event::{Event, EventAttention, EventOccurrence};
+use crate::models::money::Money;
+use crate::models::recurrence::Recurrence;
+use crate::pdf::document::{local_date, local_datetime, money};
+
+pub const UPCOMING: &str = "Upcoming";
+pub const ONGOING: &str = "Ongoing";
+pub const FINISHED: &str = "Finished";
+pub const CANCELLED: &str = "Cancelled";
+
+// How often statuses are brought up to date while the app is open
+const STATUS_REFRESH: Duration = Duration::from_secs(60);
+
+// Upcoming events this close without a listed venue are flagged
+const VENUE_NOTICE_DAYS: u64 = 7;
+
+// Rules with a COUNT are expanded at most this far to find their last sitting
+const LAST_SITTING_YEARS: u32 = 100;
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+// Events with only a date run from 00:00 to 23:59
+const ALL_DAY_MINUTES: i64 = 23 * 60 + 59;
+
+// Open-ended recurrences saved before an end was required are expanded this far
+// ahead of their first occurrence
+const HORIZON_MONTHS: u32 = 24;
+
+// Conflicts listed in the error before the rest are summarised
+const CONFLICTS_SHOWN: usize = 3;
+
+const EVENT_COLUMNS: &str = "id, name, type_, status, date, venue, attendee, notes, starts_at, ends_at, venue_id, recurrence, claim_per_household";
+
+fn event_from_row(row: &Row) -> rusqlite::Result<Event> {
+    Ok(Event {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        type_: row.get(2)?,
+        status: row.get(3)?,
+        date: row.get(4)?,
+        venue: row.get(5)?,
+        attendee: row.get(6)?,
+        notes: row.get(7)?,
+        starts_at: row.get(8)?,
+        ends_at: row.get(9)?,
+        venue_id: row.get(10)?,
+        recurrence: row.get(11)?,
+        claim_per_household: row.get(12)?,
+    })
+}
+
+// When an event runs and how it repeats
+pub struct Schedule {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub recurrence: Option<Recurrence>,
+}
+
+impl Schedule {
+    // Reads the stored or submitted times. An event with only a date runs all day.
+    pub fn of(event: &Event) -> Result<Schedule, String> {
+        let (start, end) = match event.starts_at.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(value) => {
+                let start = local_datetime(value).ok_or_else(|| format!("The start '{}' is not a valid date and time.", value))?;
+                let end = event
+                    .ends_at
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .ok_or("An end time is required for the event.")?;
+                let end = local_datetime(end).ok_or_else(|| format!("The end '{}' is not a valid date and time.", end))?;
+                (start, end)
+            }
+            None => {
+                let date = local_date(&event.date).ok_or("A date or start time is required for the event.")?;
+                let start = date.and_time(NaiveTime::MIN);
+                (start, start + TimeDelta::minutes(ALL_DAY_MINUTES))
+            }
+        };
+        if end <= start {
+            return Err("The event must end after it starts.".into());
+        }
+        let recurrence = match event.recurrence.as_deref().map(str::trim).filter(|rule| !rule.is_empty()) {
+            Some(rule) => Some(Recurrence::parse(rule)?),
+            None => None,
+        };
+        Ok(Schedule { start, end, recurrence })
+    }
+
+    // Events that only have a date run all day
+    pub fn all_day(&self) -> bool {
+        self.start.time() == NaiveTime::MIN && self.end - self.start == TimeDelta::minutes(ALL_DAY_MINUTES)
+    }
+
+    // When a sitting holds the venue: an all-day event takes the whole date
+    fn booked(&self, (start, end): (NaiveDateTime, NaiveDateTime)) -> (NaiveDateTime, NaiveDateTime) {
+        if self.all_day() {
+            (start, start + TimeDelta::days(1))
+        } else {
+            (start, end)
+        }
+    }
+
+    // Last date to expand to so every sitting is covered
+    fn horizon(&self) -> NaiveDate {
+        match &self.recurrence {
+            Some(recurrence) => match (recurrence.until, recurrence.count) {
+                (Some(until), _) => until,
+                (None, Some(_)) => self.start.date() + Months::new(LAST_SITTING_YEARS * 12),
+                (None, None) => self.start.date() + Months::new(HORIZON_MONTHS),
+            },
+            None => self.end.date(),
+        }
+    }
+
+    // End of the last sitting, or None while the event keeps repeating
+    fn last_end(&self) -> Option<NaiveDateTime> {
+        match &self.recurrence {
+            Some(recurrence) if recurrence.count.is_none() && recurrence.until.is_none() => None,
+            Some(_) => self.occurrences(self.start.date() + Months::new(LAST_SITTING_YEARS * 12)).last().map(|(_, end)| *end),
+            None => Some(self.end),
+        }
+    }
+
+    // Upcoming, Ongoing or Finished, going by the clock
+    pub fn status_at(&self, now: NaiveDateTime) -> &'static str {
+        if self.occurrences(now.date()).iter().any(|(start, end)| *start <= now && now < *end) {
+            ONGOING
+        } else if self.last_end().is_some_and(|end| end <= now) {
+            FINISHED
+        } else {
+            UPCOMING
+        }
+    }
+
+    // (start, end) of every sitting that starts on or before `through`
+    pub fn occurrences(&self, through: NaiveDate) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let length = self.end - self.start;
+        match &self.recurrence {
+            Some(recurrence) => recurrence
+                .occurrences(self.start, through)
+                .into_iter()
+                .map(|start| (start, start + length))
+                .collect(),
+            None if self.start.date() <= through => vec![(self.start, self.end)],
+            None => Vec::new(),
+        }
+    }
+}
+
+// "October 5, 2026, 9:00 AM-12:00 PM", or both dates for events that run overnight
+fn when(start: NaiveDateTime, end: NaiveDateTime) -> String {
+    let date = |time: NaiveDateTime| time.format("%B %-d, %Y").to_string();
+    let clock = |time: NaiveDateTime| time.format("%-I:%M %p").to_string();
+    let all_day = start.time() == NaiveTime::MIN && end - start == TimeDelta::minutes(ALL_DAY_MINUTES);
+    if all_day {
+        date(start)
+    } else if start.date() == end.date() {
+        format!("{}, {}-{}", date(start), clock(start), clock(end))
+    } else {
+        format!("{}, {} - {}, {}", date(start), clock(start), date(end), clock(end))
+    }
+}
+
+pub fn fetch_event(conn: &Connection, id: i32) -> Result<Event, String> {
+    conn.query_row(&format!("SELECT {} FROM events WHERE id = ?1", EVENT_COLUMNS), params![id], event_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Event {} does not exist.", id))
+}
+
+// When the event runs, as printed in reports; recurring events are marked as such
+pub fn schedule_label(event: &Event) -> String {
+    match Schedule::of(event) {
+        Ok(schedule) if schedule.recurrence.is_some() => format!("{} (recurring)", when(schedule.start, schedule.end)),
+        Ok(schedule) => when(schedule.start, schedule.end),
+        Err(_) => event.date.clone(),
+    }
+}
+
+// Other bookings of the same venue that overlap any sitting of the event. An
+// all-day event conflicts with anything else at the venue that date.
+fn check_conflicts(conn: &Connection, event: &Event, schedule: &Schedule) -> Result<(), String> {
+    let Some(venue_id) = event.venue_id else {
+        return Ok(());
+    };
+    let sittings: Vec<_> = schedule
+        .occurrences(schedule.horizon())
+        .into_iter()
+        .map(|sitting| schedule.booked(sitting))
+        .collect();
+    let (Some(first), Some(last)) = (sittings.first(), sittings.last()) else {
+        return Ok(());
+    };
+    let (window_start, window_end) = (first.0, last.1);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM events WHERE venue_id = ?1 AND id IS NOT ?2 AND status <> ?3 AND starts_at <= ?4",
+            EVENT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let others = stmt
+        .query_map(
+            params![venue_id, event.id, CANCELLED, window_end.format(DATETIME_FORMAT).to_string()],
+            event_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut conflicts = Vec::new();
+    for other in others {
+        let other = other.map_err(|e| e.to_string())?;
+        // a booking saved with a bad schedule can't be checked
+        let Ok(other_schedule) = Schedule::of(&other) else {
+            continue;
+        };
+        for (start, end) in other_schedule.occurrences(window_end.date()) {
+            let (booked_from, booked_until) = other_schedule.booked((start, end));
+            if booked_until <= window_start {
+                continue;
+            }
+            if sittings.iter().any(|(mine_start, mine_end)| booked_from < *mine_end && *mine_start < booked_until) {
+                conflicts.push((start, format!("'{}' on {}", other.name, when(start, end))));
+            }
+        }
+    }
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    conflicts.sort();
+    let mut listed: Vec<String> = conflicts.iter().take(CONFLICTS_SHOWN).map(|(_, conflict)| conflict.clone()).collect();
+    if conflicts.len() > CONFLICTS_SHOWN {
+        listed.push(format!("and {} more", conflicts.len() - CONFLICTS_SHOWN));
+    }
+    Err(format!("{} is already booked at that time: {}.", event.venue, listed.join("; ")))
+}
+
+// Normalises the schedule and venue of an event about to be saved, then checks
+// that the venue is free
+fn prepare_event(conn: &Connection, event: &mut Event) -> Result<(), String> {
+    if event.name.trim().is_empty() {
+        return Err("A name is required for the event.".into());
+    }
+    let schedule = Schedule::of(event)?;
+    // every sitting is checked for conflicts, so the repetition has to stop somewhere
+    if schedule.recurrence.as_ref().is_some_and(|recurrence| recurrence.count.is_none() && recurrence.until.is_none()) {
+        return Err("A repeating event needs an end: add COUNT (e.g. COUNT=12) or UNTIL (e.g. UNTIL=20271231).".into());
+    }
+    event.starts_at = Some(schedule.start.format(DATETIME_FORMAT).to_string());
+    event.ends_at = Some(schedule.end.format(DATETIME_FORMAT).to_string());
+    event.date = schedule.start.date().to_string();
+    event.recurrence = schedule.recurrence.as_ref().map(|recurrence| recurrence.to_string());
+    // the times decide the status; only cancelling is done by hand
+    if event.status != CANCELLED {
+        event.status = schedule.status_at(Local::now().naive_local()).to_string();
+    }
+
+    // a venue picked from the list, or typed text matched against it; anything
+    // else is kept as written and not checked for conflicts
+    match event.venue_id {
+        Some(venue_id) => {
+            let (name, active): (String, bool) = conn
+                .query_row("SELECT name, active FROM venues WHERE id = ?1", params![venue_id], |row| Ok((row.get(0)?, row.get(1)?)))
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Venue {} does not exist.", venue_id))?;
+            if !active {
+                return Err(format!("{} is no longer available for bookings.", name));
+            }
+            event.venue = name;
+        }
+        None => {
+            event.venue = event.venue.trim().to_string();
+            event.venue_id = conn
+                .query_row(
+                    "SELECT id FROM venues WHERE name = ?1 AND active = 1",
+                    params![event.venue],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    if event.status != CANCELLED {
+        check_conflicts(conn, event, &schedule)?;
+    }
+    Ok(())
+}
+
+// Moves events along from Upcoming to Ongoing to Finished as their times pass.
+// Cancelled events and events whose times can't be read are left alone.
+// Returns how many were changed.
+pub fn refresh_event_statuses(conn: &Connection) -> Result<usize, String> {
+    let now = Local::now().naive_local();
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM events WHERE status <> ?1", EVENT_COLUMNS))
+        .map_err(|e| e.to_string())?;
+    let events = stmt
+        .query_map(params![CANCELLED], event_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut changed = 0;
+    for event in events {
+        let Ok(schedule) = Schedule::of(&event) else {
+            continue;
+        };
+        let status = schedule.status_at(now);
+        if event.status != status {
+            conn.execute("UPDATE events SET status = ?1 WHERE id = ?2", params![status, event.id])
+                .map_err(|e| e.to_string())?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+// Keeps statuses current in the background for as long as the app runs
+pub fn watch_event_statuses() {
+    std::thread::spawn(|| loop {
+        std::thread::sleep(STATUS_REFRESH);
+        let result = establish_connection()
+            .map_err(|e| e.to_string())
+            .and_then(|conn| refresh_event_statuses(&conn));
+        if let Err(e) = result {
+            eprintln!("❌ Event status update failed: {}", e);
+        }
+    });
+}
 
 #[tauri::command]
 pub fn fetch_all_events_command() -> Result<Vec<Event>, String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, name, type_, status, date, venue, attendee, notes FROM events"
+        &format!("SELECT {} FROM events", EVENT_COLUMNS)
     ).map_err(|e| e.to_string())?;
 
     let events_iter = stmt
-        .query_map([], |row| {
-            Ok(Event {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                type_: row.get(2)?,
-                status: row.get(3)?,
-                date: row.get(4)?,
-                venue: row.get(5)?,
-                attendee: row.get(6)?,
-                notes: row.get(7)?,
-            })
-        })
+        .query_map([], event_from_row)
         .map_err(|e| e.to_string())?;
 
     let mut events = Vec::new();
@@ -33,9 +358,112 @@ pub fn fetch_all_events_command() -> Result<Vec<Event>, String> {
     Ok(events)
 }
 
+// Every sitting between two dates, recurring events expanded, in start order
 #[tauri::command]
-pub fn insert_event_command(event: Event) -> Result<(), String> {
+pub fn fetch_event_occurrences_command(from: String, to: String) -> Result<Vec<EventOccurrence>, String> {
+    let from = local_date(&from).ok_or_else(|| format!("'{}' is not a valid date.", from))?;
+    let to = local_date(&to).ok_or_else(|| format!("'{}' is not a valid date.", to))?;
+
+    let mut occurrences = Vec::new();
+    for event in fetch_all_events_command()? {
+        let (Some(event_id), Ok(schedule)) = (event.id, Schedule::of(&event)) else {
+            continue;
+        };
+        for (start, end) in schedule.occurrences(to) {
+            if end.date() < from {
+                continue;
+            }
+            occurrences.push(EventOccurrence {
+                event_id,
+                name: event.name.clone(),
+                status: event.status.clone(),
+                venue: event.venue.clone(),
+                venue_id: event.venue_id,
+                starts_at: start.format(DATETIME_FORMAT).to_string(),
+                ends_at: end.format(DATETIME_FORMAT).to_string(),
+                recurring: schedule.recurrence.is_some(),
+            });
+        }
+    }
+    occurrences.sort_by(|a, b| a.starts_at.cmp(&b.starts_at));
+
+    Ok(occurrences)
+}
+
+// Events with loose ends: times that can't be read, no attendance or cash advances
+// still open after the event, or no listed venue shortly before it starts
+#[tauri::command]
+pub fn fetch_events_needing_attention_command() -> Result<Vec<EventAttention>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    refresh_event_statuses(&conn)?;
+    let now = Local::now().naive_local();
+
+    let mut unliquidated: HashMap<i32, Money> = HashMap::new();
+    for advance in fetch_advances(&conn, None, now.date())? {
+        if let (Some(event_id), true) = (advance.event_id, advance.balance > Money::ZERO) {
+            *unliquidated.entry(event_id).or_default() += advance.balance;
+        }
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT event_id FROM event_attendance")
+        .map_err(|e| e.to_string())?;
+    let attended = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<HashSet<i32>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut flagged = Vec::new();
+    for event in fetch_all_events_command()? {
+        let Some(event_id) = event.id else {
+            continue;
+        };
+        if event.status == CANCELLED {
+            continue;
+        }
+        let mut reasons = Vec::new();
+        match Schedule::of(&event) {
+            Err(e) => reasons.push(format!("Its schedule can't be read: {}", e)),
+            Ok(schedule) => {
+                let soon = now.date() + Days::new(VENUE_NOTICE_DAYS);
+                let next = schedule.occurrences(soon).into_iter().find(|(_, end)| *end > now);
+                if event.venue_id.is_none() && next.is_some_and(|(start, _)| start.date() <= soon) {
+                    reasons.push("It starts within a week and is not booked into a listed venue.".to_string());
+                }
+            }
+        }
+        // attendance is either checked in on the register or, as the event form
+        // still does, typed into the attendee field
+        if event.status == FINISHED && !attended.contains(&event_id) && event.attendee.trim().is_empty() {
+            reasons.push("It has ended without any attendance recorded.".to_string());
+        }
+        if event.status == FINISHED {
+            if let Some(balance) = unliquidated.get(&event_id) {
+                reasons.push(format!("It has ended with {} in cash advances not yet liquidated.", money(*balance)));
+            }
+        }
+
+        if !reasons.is_empty() {
+            flagged.push(EventAttention {
+                event_id,
+                name: event.name,
+                status: event.status,
+                starts_at: event.starts_at,
+                ends_at: event.ends_at,
+                reasons,
+            });
+        }
+    }
+    flagged.sort_by(|a, b| a.starts_at.cmp(&b.starts_at));
+
+    Ok(flagged)
+}
+
+#[tauri::command]
+pub fn insert_event_command(mut event: Event) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
+    prepare_event(&conn, &mut event)?;
 
     conn.execute(
         "INSERT INTO events (
@@ -45,8 +473,13 @@ pub fn insert_event_command(event: Event) -> Result<(), String> {
             date,
             venue,
             attendee,
-            notes
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            notes,
+            starts_at,
+            ends_at,
+            venue_id,
+            recurrence,
+            claim_per_household
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             event.name,
             event.type_,
@@ -54,7 +487,12 @@ pub fn insert_event_command(event: Event) -> Result<(), String> {
             event.date,
             event.venue,
             event.attendee,
-            event.notes
+            event.notes,
+            event.starts_at,
+            event.ends_at,
+            event.venue_id,
+            event.recurrence,
+            event.claim_per_household
         ],
     ).map_err(|e| e.to_string())?;
 
@@ -62,8 +500,9 @@ pub fn insert_event_command(event: Event) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn update_event_command(event: Event) -> Result<(), String> {
+pub fn update_event_command(mut event: Event) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
+    prepare_event(&conn, &mut event)?;
 
     conn.execute(
         "UPDATE events SET
@@ -73,8 +512,13 @@ pub fn update_event_command(event: Event) -> Result<(), String> {
             date = ?4,
             venue = ?5,
             attendee = ?6,
-            notes = ?7
-         WHERE id = ?8",
+            notes = ?7,
+            starts_at = ?8,
+            ends_at = ?9,
+            venue_id = ?10,
+            recurrence = ?11,
+            claim_per_household = ?12
+         WHERE id = ?13",
         params![
             event.name,
             event.type_,
@@ -83,6 +527,11 @@ pub fn update_event_command(event: Event) -> Result<(), String> {
             event.venue,
             event.attendee,
             event.notes,
+            event.starts_at,
+            event.ends_at,
+            event.venue_id,
+            event.recurrence,
+            event.claim_per_household,
             event.id
         ],
     ).map_err(|e| e.to_string())?;
@@ -99,12 +548,20 @@ pub fn save_event_command(event: Event) -> Result<(), String> {
     }
 }
 
+// Events with attendance on file are kept as the record of who came
 #[tauri::command]
 pub fn delete_event_command(id: i32) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
+    let attended: bool = conn
+        .query_row("SELECT EXISTS (SELECT 1 FROM event_attendance WHERE event_id = ?1)", params![id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if attended {
+        return Err("Attendance has been recorded for this event, so it can't be deleted. Cancel it instead.".into());
+    }
+
     conn.execute("DELETE FROM events WHERE id = ?", params![id])
         .map_err(|e| e.to_string())?;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,15 +1,39 @@
+use crate::commands::budget::check_appropriation;
+use crate::commands::cash_advances::check_liquidation;
+use crate::commands::ledger::{post_expense, unpost};
+use crate::commands::vouchers::voucher_status;
 use crate::database::connection::establish_connection;
 use crate::models::expense::Expense;
-use rusqlite::params;
+use rusqlite::{params, Connection};
+
+// Once its voucher is approved an expense is final and can't be changed
+fn ensure_not_final(conn: &Connection, id: i32) -> Result<(), String> {
+    match voucher_status(conn, id)? {
+        Some((dv_number, status)) if status == "Approved" || status == "Paid" => Err(format!(
+            "This expense is covered by {} voucher {} and can no longer be changed.",
+            status.to_lowercase(),
+            dv_number
+        )),
+        _ => Ok(()),
+    }
+}
 
 #[tauri::command]
 pub fn delete_expense_command(id: i32) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    ensure_not_final(&tx, id)?;
 
-    conn.execute(
+    unpost(&tx, "expense", id as i64).map_err(|e| e.to_string())?;
+    tx.execute(
+        "DELETE FROM disbursement_vouchers WHERE expense_id = ?1",
+        params![id],
+    ).map_err(|e| e.to_string())?;
+    tx.execute(
         "DELETE FROM expenses WHERE id = ?1",
         params![id],
     ).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -19,7 +43,12 @@ pub fn fetch_all_expenses_command() -> Result<Vec<Expense>, String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare("SELECT id, type_, amount, or_number, paid_to, paid_by, category, date FROM expenses")
+        .prepare(
+            "SELECT e.id, e.type_, e.amount, e.or_number, e.paid_to, e.paid_by, e.category, e.date, v.status, e.cash_advance_id,
+                e.requires_voucher, v.payment_mode
+             FROM expenses e
+             LEFT JOIN disbursement_vouchers v ON v.expense_id = e.id",
+        )
         .map_err(|e| e.to_string())?;
 
     let expense_iter = stmt
@@ -33,6 +62,10 @@ pub fn fetch_all_expenses_command() -> Result<Vec<Expense>, String> {
                 paid_by: row.get(5)?,
                 category: row.get(6)?,
                 date: row.get(7)?,
+                voucher_status: row.get(8)?,
+                payment_mode: row.get(11)?,
+                cash_advance_id: row.get(9)?,
+                requires_voucher: row.get(10)?,
             })
         })
         .map_err(|e| e.to_string())?;
@@ -45,13 +78,18 @@ pub fn fetch_all_expenses_command() -> Result<Vec<Expense>, String> {
     Ok(expenses)
 }
 
+// Returns warnings, e.g. when the expense runs past its category's appropriation.
+// New expenses stay off the books until their disbursement voucher is approved.
 #[tauri::command]
-pub fn insert_expense_command(expense: Expense) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+pub fn insert_expense_command(expense: Expense) -> Result<Vec<String>, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut warnings = check_appropriation(&tx, &expense)?;
+    warnings.extend(check_liquidation(&tx, &expense)?);
 
-    conn.execute(
-        "INSERT INTO expenses (type_, amount, or_number, paid_to, paid_by, category, date)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    tx.execute(
+        "INSERT INTO expenses (type_, amount, or_number, paid_to, paid_by, category, date, cash_advance_id, requires_voucher)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1)",
         params![
             expense.type_,
             expense.amount,
@@ -60,18 +98,27 @@ pub fn insert_expense_command(expense: Expense) -> Result<(), String> {
             expense.paid_by,
             expense.category,
             expense.date,
+            expense.cash_advance_id,
         ],
     ).map_err(|e| e.to_string())?;
+    post_expense(&tx, tx.last_insert_rowid()).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(warnings)
 }
 
 #[tauri::command]
-pub fn update_expense_command(expense: Expense) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+pub fn update_expense_command(expense: Expense) -> Result<Vec<String>, String> {
+    let id = expense.id.ok_or("Expense id is required.")?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    ensure_not_final(&tx, id)?;
+    let mut warnings = check_appropriation(&tx, &expense)?;
+    warnings.extend(check_liquidation(&tx, &expense)?);
 
-    conn.execute(
-        "UPDATE expenses SET type_ = ?1, amount = ?2, or_number = ?3, paid_to = ?4, paid_by = ?5, date = ?6, category = ?7 WHERE id = ?8",
+    tx.execute(
+        "UPDATE expenses SET type_ = ?1, amount = ?2, or_number = ?3, paid_to = ?4, paid_by = ?5, date = ?6, category = ?7, cash_advance_id = ?8
+         WHERE id = ?9",
         params![
             expense.type_,
             expense.amount,
@@ -80,15 +127,24 @@ pub fn update_expense_command(expense: Expense) -> Result<(), String> {
             expense.paid_by,
             expense.date,
             expense.category,
-            expense.id
+            expense.cash_advance_id,
+            id
         ],
     ).map_err(|e| e.to_string())?;
+    // a certified voucher has to be certified again for the changed amounts
+    tx.execute(
+        "UPDATE disbursement_vouchers SET status = 'Prepared', certified_by = NULL, certified_at = NULL
+         WHERE expense_id = ?1 AND status = 'Certified'",
+        params![id],
+    ).map_err(|e| e.to_string())?;
+    post_expense(&tx, id as i64).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(warnings)
 }
 
 #[tauri::command]
-pub fn save_expense_command(expense: Expense) -> Result<(), String> {
+pub fn save_expense_command(expense: Expense) -> Result<Vec<String>, String> {
     if let Some(_) = expense.id {
         update_expense_command(expense)
     } else {
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, NaiveDate};
+use rusqlite::{params, Connection};
+
+use crate::commands::ledger::GENERAL_FUND;
+use crate::commands::vouchers::final_expense;
+use crate::database::connection::establish_connection;
+use crate::models::finance::{
+    CategoryTotal, FinanceSummary, FinanceTotals, FundTotals, MonthComparison, MonthTotals, YearComparison,
+};
+use crate::models::money::Money;
+use crate::pdf::document::local_date;
+
+// Amounts summed per stored date, category and fund. Grouping happens in SQL so
+// only one row per day and category crosses over, however many entries there are.
+struct Bucket {
+    date: NaiveDate,
+    category: String,
+    fund: String,
+    amount: Money,
+}
+
+// Dates are stored either as YYYY-MM-DD or as UTC timestamps, so the SQL range is
+// widened by a day on each side and the exact local-date check is done afterwards
+fn buckets(conn: &Connection, source: &str, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Vec<Bucket>, String> {
+    let (table, settled) = if source == "income" {
+        ("incomes", "1".to_string())
+    } else {
+        // only expenses whose voucher has been approved
+        ("expenses", final_expense("t"))
+    };
+    let lower = from.and_then(|date| date.checked_sub_days(Days::new(1))).map(|date| date.to_string());
+    let upper = to.and_then(|date| date.checked_add_days(Days::new(2))).map(|date| date.to_string());
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT t.date, t.category, COALESCE(j.fund, ?1), SUM(t.amount)
+             FROM {} t
+             LEFT JOIN journal_entries j ON j.source = ?2 AND j.source_id = t.id
+             WHERE (?3 IS NULL OR t.date >= ?3) AND (?4 IS NULL OR t.date < ?4) AND {}
+             GROUP BY t.date, t.category, j.fund",
+            table, settled
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![GENERAL_FUND, source, lower, upper], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Money>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut buckets = Vec::new();
+    for row in rows {
+        let (date, category, fund, amount) = row.map_err(|e| e.to_string())?;
+        let Some(date) = local_date(&date) else {
+            continue;
+        };
+        if from.is_some_and(|from| date < from) || to.is_some_and(|to| date > to) {
+            continue;
+        }
+        buckets.push(Bucket { date, category: category.trim().to_string(), fund, amount });
+    }
+    Ok(buckets)
+}
+
+fn add(totals: &mut FinanceTotals, income: Money, expense: Money) {
+    totals.income += income;
+    totals.expense += expense;
+    totals.net = totals.income - totals.expense;
+}
+
+fn month_key(date: NaiveDate) -> (i32, u32) {
+    (date.year(), date.month())
+}
+
+fn next_month((year, month): (i32, u32)) -> (i32, u32) {
+    if month == 12 { (year + 1, 1) } else { (year, month + 1) }
+}
+
+// Percent change from `previous` to `current`, rounded to two places
+fn change(current: Money, previous: Money) -> Option<f64> {
+    if previous.is_zero() {
+        return None;
+    }
+    Some(((current - previous).pesos() / previous.abs().pesos() * 10000.0).round() / 100.0)
+}
+
+fn summarize(conn: &Connection, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<FinanceSummary, String> {
+    let incomes = buckets(conn, "income", from, to)?;
+    let expenses = buckets(conn, "expense", from, to)?;
+
+    let mut totals = FinanceTotals::default();
+    let mut months: BTreeMap<(i32, u32), FinanceTotals> = BTreeMap::new();
+    let mut categories: BTreeMap<(&str, String), (String, Money)> = BTreeMap::new();
+    let mut funds: BTreeMap<String, FinanceTotals> = BTreeMap::new();
+
+    for (type_, buckets) in [("Income", &incomes), ("Expense", &expenses)] {
+        for bucket in buckets {
+            let (income, expense) = if type_ == "Income" { (bucket.amount, Money::ZERO) } else { (Money::ZERO, bucket.amount) };
+            add(&mut totals, income, expense);
+            add(months.entry(month_key(bucket.date)).or_default(), income, expense);
+            add(funds.entry(bucket.fund.clone()).or_default(), income, expense);
+            // categories typed with different casing count as one
+            let category = categories
+                .entry((type_, bucket.category.to_lowercase()))
+                .or_insert_with(|| (bucket.category.clone(), Money::ZERO));
+            category.1 += bucket.amount;
+        }
+    }
+
+    // Fill the gaps so charts get a point for every month of the range
+    let first = from.map(month_key).or_else(|| months.keys().next().copied());
+    let last = to.map(month_key).or_else(|| months.keys().next_back().copied());
+    let mut by_month = Vec::new();
+    if let (Some(mut month), Some(last)) = (first, last) {
+        while month <= last {
+            by_month.push(MonthTotals {
+                month: format!("{}-{:02}", month.0, month.1),
+                totals: months.get(&month).copied().unwrap_or_default(),
+            });
+            month = next_month(month);
+        }
+    }
+
+    let by_category = categories
+        .into_iter()
+        .map(|((type_, _), (category, amount))| CategoryTotal { type_: type_.to_string(), category, amount })
+        .collect();
+    let by_fund = funds.into_iter().map(|(fund, totals)| FundTotals { fund, totals }).collect();
+
+    Ok(FinanceSummary {
+        from: from.map(|date| date.to_string()),
+        to: to.map(|date| date.to_string()),
+        totals,
+        by_month,
+        by_category,
+        by_fund,
+    })
+}
+
+// Totals by month, category and fund for an inclusive date range; either end may be left open
+#[tauri::command]
+pub fn fetch_finance_summary_command(from: Option<String>, to: Option<String>) -> Result<FinanceSummary, String> {
+    // a blank end is open; anything else must be a date
+    let parse = |date: Option<String>| match date.filter(|date| !date.trim().is_empty()) {
+        Some(date) => local_date(&date).map(Some).ok_or_else(|| format!("'{}' is not a valid date.", date)),
+        None => Ok(None),
+    };
+    let from = parse(from)?;
+    let to = parse(to)?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("The start date must not be after the end date.".into());
+        }
+    }
+
+    summarize(&conn, from, to)
+}
+
+// Month-by-month income and expenses of a year next to the year before it
+#[tauri::command]
+pub fn fetch_finance_year_comparison_command(year: i32) -> Result<YearComparison, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let year_range = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .zip(NaiveDate::from_ymd_opt(year, 12, 31))
+            .ok_or_else(|| format!("{} is not a valid year.", year))
+    };
+    let (start, end) = year_range(year)?;
+    let (previous_start, previous_end) = year_range(year - 1)?;
+
+    let current = summarize(&conn, Some(start), Some(end))?;
+    let previous = summarize(&conn, Some(previous_start), Some(previous_end))?;
+
+    let months = current
+        .by_month
+        .iter()
+        .zip(&previous.by_month)
+        .enumerate()
+        .map(|(index, (current, previous))| MonthComparison {
+            month: index as u32 + 1,
+            current: current.totals,
+            previous: previous.totals,
+        })
+        .collect();
+
+    Ok(YearComparison {
+        year,
+        previous_year: year - 1,
+        months,
+        current: current.totals,
+        previous: previous.totals,
+        income_change: change(current.totals.income, previous.totals.income),
+        expense_change: change(current.totals.expense, previous.totals.expense),
+    })
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use rusqlite::{params, Row};
+
+use crate::database::connection::establish_connection;
+use crate::models::holiday::Holiday;
+
+const HOLIDAY_TYPES: &[&str] = &["Regular", "Special Non-Working", "Special Working"];
+
+fn holiday_from_row(row: &Row) -> rusqlite::Result<Holiday> {
+    Ok(Holiday {
+        id: row.get(0)?,
+        code: row.get(1)?,
+        name: row.get(2)?,
+        type_: row.get(3)?,
+        month: row.get(4)?,
+        day: row.get(5)?,
+        year: row.get(6)?,
+        date: None,
+    })
+}
+
+fn validate(holiday: &Holiday) -> Result<(), String> {
+    if holiday.name.trim().is_empty() {
+        return Err("A name is required for the holiday.".into());
+    }
+    if !HOLIDAY_TYPES.contains(&holiday.type_.as_str()) {
+        return Err(format!("The holiday type must be one of: {}.", HOLIDAY_TYPES.join(", ")));
+    }
+    // a leap year, so February 29 passes for holidays that repeat every year
+    if NaiveDate::from_ymd_opt(holiday.year.unwrap_or(2024), holiday.month, holiday.day).is_none() {
+        return Err("The holiday date is not a valid date.".into());
+    }
+    Ok(())
+}
+
+// Holidays in effect. Given a year, only those falling in it are listed, in date order.
+#[tauri::command]
+pub fn fetch_holidays_command(year: Option<i32>) -> Result<Vec<Holiday>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, code, name, type_, month, day, year FROM holidays
+             WHERE active = 1 AND (?1 IS NULL OR year IS NULL OR year = ?1)
+             ORDER BY year IS NOT NULL, year, month, day",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![year], holiday_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut holidays = Vec::new();
+    for holiday in rows {
+        let mut holiday = holiday.map_err(|e| e.to_string())?;
+        if let Some(year) = year {
+            // February 29 holidays only fall in leap years
+            let Some(date) = NaiveDate::from_ymd_opt(year, holiday.month, holiday.day) else {
+                continue;
+            };
+            holiday.date = Some(date.to_string());
+        }
+        holidays.push(holiday);
+    }
+    if year.is_some() {
+        holidays.sort_by(|a, b| a.date.cmp(&b.date));
+    }
+
+    Ok(holidays)
+}
+
+#[tauri::command]
+pub fn save_holiday_command(holiday: Holiday) -> Result<i32, String> {
+    validate(&holiday)?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    if let Some(id) = holiday.id {
+        conn.execute(
+            "UPDATE holidays SET name = ?1, type_ = ?2, month = ?3, day = ?4, year = ?5 WHERE id = ?6",
+            params![holiday.name.trim(), holiday.type_, holiday.month, holiday.day, holiday.year, id],
+        )
+        .map_err(|e| e.to_string())?;
+        return Ok(id);
+    }
+
+    conn.execute(
+        "INSERT INTO holidays (name, type_, month, day, year) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![holiday.name.trim(), holiday.type_, holiday.month, holiday.day, holiday.year],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+// Built-in holidays are switched off rather than deleted, so they aren't seeded again
+#[tauri::command]
+pub fn delete_holiday_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("UPDATE holidays SET active = 0 WHERE id = ?1 AND code IS NOT NULL", params![id])
+        .map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM holidays WHERE id = ?1 AND code IS NULL", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -1,15 +1,37 @@
+use crate::commands::ledger::{post_income, unpost};
+use crate::commands::receipts::assign_or_number;
 use crate::database::connection::establish_connection;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
 use crate::models::income::Income;
 
+// Certificate fees and their reversals only change through the certificate
+fn check_not_certificate_fee(conn: &rusqlite::Connection, id: i32, action: &str) -> Result<(), String> {
+    let certificate_id: Option<i32> = conn
+        .query_row("SELECT certificate_id FROM incomes WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .flatten();
+    match certificate_id {
+        Some(certificate_id) => Err(format!(
+            "This income is the fee for certificate #{}. Void the certificate instead of {} the income.",
+            certificate_id, action
+        )),
+        None => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub fn delete_income_command(id: i32) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    check_not_certificate_fee(&conn, id, "deleting")?;
 
-    conn.execute(
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    unpost(&tx, "income", id as i64).map_err(|e| e.to_string())?;
+    tx.execute(
         "DELETE FROM incomes WHERE id = ?1",
         params![id],
     ).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -20,7 +42,7 @@ pub fn fetch_all_incomes_command() -> Result<Vec<Income>, String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare("SELECT id, type_, amount, or_number, received_from, received_by, category, date FROM incomes")
+        .prepare("SELECT id, type_, amount, or_number, received_from, received_by, category, date, certificate_id, flag, or_booklet_id FROM incomes")
         .map_err(|e| e.to_string())?;
 
     let income_iter = stmt
@@ -34,6 +56,9 @@ pub fn fetch_all_incomes_command() -> Result<Vec<Income>, String> {
                 received_by: row.get(5)?,
                 category: row.get(6)?,
                 date: row.get(7)?,
+                certificate_id: row.get(8)?,
+                flag: row.get(9)?,
+                or_booklet_id: row.get(10)?,
             })
         })
         .map_err(|e| e.to_string())?;
@@ -46,50 +71,78 @@ pub fn fetch_all_incomes_command() -> Result<Vec<Income>, String> {
     Ok(incomes)
 }
 
+// Leave or_number at 0 to take the next receipt from the collector's booklet.
+// Returns the OR number the income was recorded under.
 #[tauri::command]
-pub fn insert_income_command(income: Income) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+pub fn insert_income_command(income: Income) -> Result<i32, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+    let (or_number, booklet_id) = receipt_for(&tx, &income)?;
 
-    conn.execute(
-        "INSERT INTO incomes (type_, amount, or_number, received_from, received_by, category, date)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    tx.execute(
+        "INSERT INTO incomes (type_, amount, or_number, received_from, received_by, category, date, or_booklet_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
         params![
             income.type_,
             income.amount,
-            income.or_number,
+            or_number,
             income.received_from,
             income.received_by,
             income.category,
             income.date,
+            booklet_id,
         ],
     ).map_err(|e| e.to_string())?;
+    post_income(&tx, tx.last_insert_rowid()).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(or_number)
+}
+
+// Negative incomes reverse an earlier one and take no receipt number, so the
+// original OR is not counted twice
+fn receipt_for(conn: &rusqlite::Connection, income: &Income) -> Result<(i32, Option<i32>), String> {
+    if income.amount.is_negative() {
+        return Ok((0, None));
+    }
+    assign_or_number(conn, income.or_number, &income.received_by, income.id)
 }
 
 #[tauri::command]
-pub fn update_income_command(income: Income) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+pub fn update_income_command(income: Income) -> Result<i32, String> {
+    let id = income.id.ok_or("Income id is required.")?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    check_not_certificate_fee(&conn, id, "editing")?;
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+    let (or_number, booklet_id) = receipt_for(&tx, &income)?;
 
-    conn.execute(
-        "UPDATE incomes SET type_ = ?1, amount = ?2, or_number = ?3, received_from = ?4, received_by = ?5, date = ?6, category = ?7 WHERE id = ?8",
+    tx.execute(
+        "UPDATE incomes SET type_ = ?1, amount = ?2, or_number = ?3, received_from = ?4, received_by = ?5, date = ?6, category = ?7,
+            or_booklet_id = ?8 WHERE id = ?9",
         rusqlite::params![
             income.type_,
             income.amount,
-            income.or_number,
+            or_number,
             income.received_from,
             income.received_by,
             income.date,
             income.category,
-            income.id
+            booklet_id,
+            id
         ],
     ).map_err(|e| e.to_string())?;
+    post_income(&tx, id as i64).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok(or_number)
 }
 
 #[tauri::command]
-pub fn save_income_command(income: Income) -> Result<(), String> {
+pub fn save_income_command(income: Income) -> Result<i32, String> {
     if let Some(_) = income.id {
         update_income_command(income)
     } else {
@@ -0,0 +1,215 @@
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::commands::attendance::Calendar;
+use crate::commands::vouchers::{signatory, CAPTAIN_ROLE};
+use crate::database::connection::establish_connection;
+use crate::models::leave::LeaveRequest;
+use crate::pdf::document::local_date;
+
+// Leave types under the Civil Service leave rules
+const LEAVE_TYPES: &[&str] = &[
+    "Vacation Leave",
+    "Sick Leave",
+    "Mandatory/Forced Leave",
+    "Special Privilege Leave",
+    "Maternity Leave",
+    "Paternity Leave",
+    "Solo Parent Leave",
+    "Study Leave",
+    "Rehabilitation Leave",
+    "Special Leave Benefits for Women",
+    "Special Emergency (Calamity) Leave",
+    "Adoption Leave",
+    "Others",
+];
+
+const LEAVE_QUERY: &str = "SELECT l.id, l.official_id, o.name, l.leave_type, l.start_date, l.end_date, l.reason, l.status,
+            l.decided_by, d.name, l.decided_at, l.rejection_reason, l.created_at
+     FROM leave_requests l
+     LEFT JOIN officials o ON o.id = l.official_id
+     LEFT JOIN officials d ON d.id = l.decided_by";
+
+fn leave_from_row(row: &Row) -> rusqlite::Result<LeaveRequest> {
+    Ok(LeaveRequest {
+        id: row.get(0)?,
+        official_id: row.get(1)?,
+        official_name: row.get(2)?,
+        leave_type: row.get(3)?,
+        start_date: row.get(4)?,
+        end_date: row.get(5)?,
+        reason: row.get(6)?,
+        status: row.get(7)?,
+        working_days: 0,
+        decided_by: row.get(8)?,
+        decided_by_name: row.get(9)?,
+        decided_at: row.get(10)?,
+        rejection_reason: row.get(11)?,
+        created_at: row.get(12)?,
+    })
+}
+
+fn dates(leave: &LeaveRequest) -> Result<(NaiveDate, NaiveDate), String> {
+    let start = local_date(&leave.start_date).ok_or("The start date is not a valid date.")?;
+    let end = local_date(&leave.end_date).ok_or("The end date is not a valid date.")?;
+    if end < start {
+        return Err("The end date must be on or after the start date.".into());
+    }
+    Ok((start, end))
+}
+
+// Days of the leave the official would otherwise have had to report for work
+fn working_days(calendar: &Calendar, start: NaiveDate, end: NaiveDate) -> i32 {
+    start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .filter(|date| calendar.is_working_day(*date))
+        .count() as i32
+}
+
+fn fetch_leave(conn: &Connection, id: i32) -> Result<LeaveRequest, String> {
+    let mut leave = conn
+        .query_row(&format!("{} WHERE l.id = ?1", LEAVE_QUERY), params![id], leave_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Leave request {} does not exist.", id))?;
+    if let Ok((start, end)) = dates(&leave) {
+        leave.working_days = working_days(&Calendar::load(conn)?, start, end);
+    }
+    Ok(leave)
+}
+
+// Moves a leave request on to `to`, provided it is in one of the `from` statuses
+fn decide(conn: &Connection, id: i32, from: &[&str], to: &str, official_id: i32, reason: Option<&str>) -> Result<LeaveRequest, String> {
+    signatory(conn, official_id, CAPTAIN_ROLE)?;
+    let leave = fetch_leave(conn, id)?;
+    let status = leave.status.clone().unwrap_or_default();
+    if !from.contains(&status.as_str()) {
+        return Err(format!("This leave request is {}; only {} requests can be {}.", status, from.join(" or "), to.to_lowercase()));
+    }
+
+    conn.execute(
+        "UPDATE leave_requests SET status = ?1, decided_by = ?2, decided_at = CURRENT_TIMESTAMP, rejection_reason = ?3
+         WHERE id = ?4",
+        params![to, official_id, reason, id],
+    )
+    .map_err(|e| e.to_string())?;
+    fetch_leave(conn, id)
+}
+
+#[tauri::command]
+pub fn fetch_leave_requests_command(official_id: Option<i32>) -> Result<Vec<LeaveRequest>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let calendar = Calendar::load(&conn)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 IS NULL OR l.official_id = ?1 ORDER BY l.start_date DESC, l.id DESC",
+            LEAVE_QUERY
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![official_id], leave_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut leaves = Vec::new();
+    for leave in rows {
+        let mut leave = leave.map_err(|e| e.to_string())?;
+        if let Ok((start, end)) = dates(&leave) {
+            leave.working_days = working_days(&calendar, start, end);
+        }
+        leaves.push(leave);
+    }
+
+    Ok(leaves)
+}
+
+// Files a new request, or edits one that is still pending
+#[tauri::command]
+pub fn save_leave_request_command(leave: LeaveRequest) -> Result<i32, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    if !LEAVE_TYPES.contains(&leave.leave_type.as_str()) {
+        return Err(format!("The leave type must be one of: {}.", LEAVE_TYPES.join(", ")));
+    }
+    let (start, end) = dates(&leave)?;
+    if working_days(&Calendar::load(&conn)?, start, end) == 0 {
+        return Err("The leave falls entirely on weekends and holidays.".into());
+    }
+    let official: Option<i32> = conn
+        .query_row("SELECT id FROM officials WHERE id = ?1", params![leave.official_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if official.is_none() {
+        return Err(format!("Official {} does not exist.", leave.official_id));
+    }
+    if let Some(id) = leave.id {
+        let status = fetch_leave(&conn, id)?.status.unwrap_or_default();
+        if status != "Pending" {
+            return Err(format!("This leave request is already {}; file a new one instead.", status.to_lowercase()));
+        }
+    }
+
+    // leave the official already asked for, or was granted, on any of these days
+    let overlapping: Option<(String, String)> = conn
+        .query_row(
+            "SELECT start_date, end_date FROM leave_requests
+             WHERE official_id = ?1 AND status IN ('Pending', 'Approved') AND id IS NOT ?2
+               AND start_date <= ?3 AND end_date >= ?4",
+            params![leave.official_id, leave.id, end.to_string(), start.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some((from, to)) = overlapping {
+        return Err(format!("This overlaps another leave request from {} to {}.", from, to));
+    }
+
+    let reason = leave.reason.as_deref().map(str::trim).filter(|reason| !reason.is_empty());
+    if let Some(id) = leave.id {
+        conn.execute(
+            "UPDATE leave_requests SET official_id = ?1, leave_type = ?2, start_date = ?3, end_date = ?4, reason = ?5 WHERE id = ?6",
+            params![leave.official_id, leave.leave_type, start.to_string(), end.to_string(), reason, id],
+        )
+        .map_err(|e| e.to_string())?;
+        return Ok(id);
+    }
+
+    conn.execute(
+        "INSERT INTO leave_requests (official_id, leave_type, start_date, end_date, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![leave.official_id, leave.leave_type, start.to_string(), end.to_string(), reason],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+// Approved leave excuses the official's absences on its working days
+#[tauri::command]
+pub fn approve_leave_request_command(id: i32, official_id: i32) -> Result<LeaveRequest, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    decide(&conn, id, &["Pending"], "Approved", official_id, None)
+}
+
+// Also withdraws leave that was approved by mistake
+#[tauri::command]
+pub fn reject_leave_request_command(id: i32, official_id: i32, reason: String) -> Result<LeaveRequest, String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err("Give a reason for rejecting the leave request.".into());
+    }
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    decide(&conn, id, &["Pending", "Approved"], "Rejected", official_id, Some(reason))
+}
+
+#[tauri::command]
+pub fn delete_leave_request_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    if fetch_leave(&conn, id)?.status.as_deref() == Some("Approved") {
+        return Err("Approved leave cannot be deleted. Reject it first if it was granted by mistake.".into());
+    }
+    conn.execute("DELETE FROM leave_requests WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
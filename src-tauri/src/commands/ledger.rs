@@ -0,0 +1,516 @@
+use std::collections::BTreeMap;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::commands::vouchers::expense_is_final;
+use crate::database::connection::establish_connection;
+use crate::models::ledger::{Account, JournalEntry, JournalLine, TrialBalance, TrialBalanceRow};
+use crate::models::money::Money;
+use crate::pdf::document::local_date;
+
+pub const CASH_ACCOUNT: &str = "1-01-01-010";
+pub const CASH_IN_BANK_ACCOUNT: &str = "1-01-02-010";
+const ADVANCES_ACCOUNT: &str = "1-03-05-030";
+const OTHER_INCOME_ACCOUNT: &str = "4-06-01-990";
+const OTHER_EXPENSE_ACCOUNT: &str = "5-02-99-990";
+
+pub const GENERAL_FUND: &str = "General Fund";
+pub const DEVELOPMENT_FUND: &str = "20% Development Fund";
+
+const ACCOUNT_TYPES: &[&str] = &["Asset", "Liability", "Equity", "Income", "Expense"];
+const BUDGET_CLASSES: &[&str] = &["PS", "MOOE", "CO", "DF"];
+
+struct Posting {
+    account_id: i64,
+    debit: Money,
+    credit: Money,
+    memo: Option<String>,
+}
+
+fn account_id(conn: &Connection, code: &str) -> rusqlite::Result<i64> {
+    conn.query_row("SELECT id FROM accounts WHERE code = ?1", params![code], |row| row.get(0))
+}
+
+// Account a category is filed under, or the catch-all account when nothing maps to it.
+// Returns (account id, budget class).
+fn category_account(conn: &Connection, type_: &str, category: &str, fallback: &str) -> rusqlite::Result<(i64, Option<String>)> {
+    let mapped = conn
+        .query_row(
+            "SELECT id, budget_class FROM accounts WHERE type_ = ?1 AND category = ?2 COLLATE NOCASE AND active = 1",
+            params![type_, category.trim()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+
+    match mapped {
+        Some(account) => Ok(account),
+        None => conn.query_row(
+            "SELECT id, budget_class FROM accounts WHERE code = ?1",
+            params![fallback],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ),
+    }
+}
+
+// Account code and budget class a category is reported under
+pub fn category_account_code(conn: &Connection, type_: &str, category: &str) -> rusqlite::Result<(String, Option<String>)> {
+    let fallback = if type_ == "Income" { OTHER_INCOME_ACCOUNT } else { OTHER_EXPENSE_ACCOUNT };
+    let (id, budget_class) = category_account(conn, type_, category, fallback)?;
+    let code = conn.query_row("SELECT code FROM accounts WHERE id = ?1", params![id], |row| row.get(0))?;
+    Ok((code, budget_class))
+}
+
+pub fn unpost(conn: &Connection, source: &str, source_id: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM journal_lines WHERE entry_id IN (SELECT id FROM journal_entries WHERE source = ?1 AND source_id = ?2)",
+        params![source, source_id],
+    )?;
+    conn.execute(
+        "DELETE FROM journal_entries WHERE source = ?1 AND source_id = ?2",
+        params![source, source_id],
+    )?;
+    Ok(())
+}
+
+fn write_entry(
+    conn: &Connection,
+    date: &str,
+    description: &str,
+    fund: &str,
+    source: &str,
+    source_id: Option<i64>,
+    postings: &[Posting],
+) -> rusqlite::Result<i64> {
+    conn.execute(
+        "INSERT INTO journal_entries (date, description, fund, source, source_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![date, description, fund, source, source_id],
+    )?;
+    let entry_id = conn.last_insert_rowid();
+
+    for posting in postings {
+        conn.execute(
+            "INSERT INTO journal_lines (entry_id, account_id, debit, credit, memo) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![entry_id, posting.account_id, posting.debit, posting.credit, posting.memo],
+        )?;
+    }
+
+    Ok(entry_id)
+}
+
+// Moves `amount` from `from` into `to`; negative amounts (reversals) run the other way
+fn transfer(to: i64, from: i64, amount: Money) -> [Posting; 2] {
+    let (debit_account, credit_account) = if amount.is_negative() { (from, to) } else { (to, from) };
+    let amount = amount.abs();
+    [
+        Posting { account_id: debit_account, debit: amount, credit: Money::ZERO, memo: None },
+        Posting { account_id: credit_account, debit: Money::ZERO, credit: amount, memo: None },
+    ]
+}
+
+// Replaces the journal entry for an income: debit cash, credit the income account.
+// Call inside the transaction that writes the income row.
+pub fn post_income(conn: &Connection, income_id: i64) -> rusqlite::Result<()> {
+    let (type_, amount, category, date, received_from): (String, Money, String, String, String) = conn.query_row(
+        "SELECT type_, amount, category, date, received_from FROM incomes WHERE id = ?1",
+        params![income_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+    )?;
+
+    unpost(conn, "income", income_id)?;
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    let cash = account_id(conn, CASH_ACCOUNT)?;
+    let (revenue, _) = category_account(conn, "Income", &category, OTHER_INCOME_ACCOUNT)?;
+    write_entry(
+        conn,
+        &date,
+        &format!("{} from {}", type_, received_from),
+        GENERAL_FUND,
+        "income",
+        Some(income_id),
+        &transfer(cash, revenue, amount),
+    )?;
+
+    Ok(())
+}
+
+// Replaces the journal entry for an expense: debit the expense account, credit cash.
+// Development Fund accounts put the entry under that fund. Expenses whose voucher
+// is not yet approved stay off the books.
+pub fn post_expense(conn: &Connection, expense_id: i64) -> rusqlite::Result<()> {
+    let (type_, amount, category, date, paid_to, cash_advance_id, payment_mode): (String, Money, String, String, String, Option<i64>, Option<String>) = conn.query_row(
+        "SELECT e.type_, e.amount, e.category, e.date, e.paid_to, e.cash_advance_id, v.payment_mode
+         FROM expenses e LEFT JOIN disbursement_vouchers v ON v.expense_id = e.id
+         WHERE e.id = ?1",
+        params![expense_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?)),
+    )?;
+
+    unpost(conn, "expense", expense_id)?;
+    if amount.is_zero() || !expense_is_final(conn, expense_id)? {
+        return Ok(());
+    }
+
+    // a liquidation is paid out of the advance already released, a check out of
+    // the bank account, anything else out of cash on hand
+    let paid_from = if cash_advance_id.is_some() {
+        ADVANCES_ACCOUNT
+    } else if payment_mode.as_deref() == Some("Check") {
+        CASH_IN_BANK_ACCOUNT
+    } else {
+        CASH_ACCOUNT
+    };
+    let cash = account_id(conn, paid_from)?;
+    let (expense, budget_class) = category_account(conn, "Expense", &category, OTHER_EXPENSE_ACCOUNT)?;
+    let fund = if budget_class.as_deref() == Some("DF") { DEVELOPMENT_FUND } else { GENERAL_FUND };
+    write_entry(
+        conn,
+        &date,
+        &format!("{} paid to {}", type_, paid_to),
+        fund,
+        "expense",
+        Some(expense_id),
+        &transfer(expense, cash, amount),
+    )?;
+
+    Ok(())
+}
+
+// Replaces the entries for a cash advance: releasing it moves cash to the advances
+// account, and any unspent amount returned moves back.
+pub fn post_cash_advance(conn: &Connection, advance_id: i64) -> rusqlite::Result<()> {
+    let (amount, refunded, date_granted, refund_date, purpose, official): (Money, Money, String, Option<String>, String, String) =
+        conn.query_row(
+            "SELECT c.amount, c.refunded, c.date_granted, c.refund_date, c.purpose, o.name
+             FROM cash_advances c JOIN officials o ON o.id = c.official_id
+             WHERE c.id = ?1",
+            params![advance_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+        )?;
+
+    unpost(conn, "cash_advance", advance_id)?;
+    unpost(conn, "cash_advance_refund", advance_id)?;
+    let cash = account_id(conn, CASH_ACCOUNT)?;
+    let advances = account_id(conn, ADVANCES_ACCOUNT)?;
+    write_entry(
+        conn,
+        &date_granted,
+        &format!("Cash advance to {}: {}", official, purpose),
+        GENERAL_FUND,
+        "cash_advance",
+        Some(advance_id),
+        &transfer(advances, cash, amount),
+    )?;
+    if !refunded.is_zero() {
+        write_entry(
+            conn,
+            refund_date.as_deref().unwrap_or(&date_granted),
+            &format!("Refund of unspent cash advance from {}", official),
+            GENERAL_FUND,
+            "cash_advance_refund",
+            Some(advance_id),
+            &transfer(cash, advances, refunded),
+        )?;
+    }
+
+    Ok(())
+}
+
+// Journalises incomes and expenses recorded before the ledger existed
+pub fn post_unposted_records(conn: &Connection) -> rusqlite::Result<()> {
+    let unposted = |table: &str, source: &str| -> rusqlite::Result<Vec<i64>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id FROM {} WHERE id NOT IN (SELECT source_id FROM journal_entries WHERE source = ?1 AND source_id IS NOT NULL)",
+            table
+        ))?;
+        let ids = stmt.query_map(params![source], |row| row.get(0))?;
+        ids.collect()
+    };
+
+    for income_id in unposted("incomes", "income")? {
+        post_income(conn, income_id)?;
+    }
+    for expense_id in unposted("expenses", "expense")? {
+        post_expense(conn, expense_id)?;
+    }
+
+    Ok(())
+}
+
+fn account_from_row(row: &Row) -> rusqlite::Result<Account> {
+    Ok(Account {
+        id: row.get(0)?,
+        code: row.get(1)?,
+        name: row.get(2)?,
+        type_: row.get(3)?,
+        budget_class: row.get(4)?,
+        category: row.get(5)?,
+        active: row.get(6)?,
+    })
+}
+
+#[tauri::command]
+pub fn fetch_accounts_command() -> Result<Vec<Account>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, code, name, type_, budget_class, category, active FROM accounts ORDER BY code")
+        .map_err(|e| e.to_string())?;
+
+    let account_iter = stmt
+        .query_map([], account_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut accounts = Vec::new();
+    for account in account_iter {
+        accounts.push(account.map_err(|e| e.to_string())?);
+    }
+
+    Ok(accounts)
+}
+
+#[tauri::command]
+pub fn save_account_command(account: Account) -> Result<(), String> {
+    if account.code.trim().is_empty() || account.name.trim().is_empty() {
+        return Err("Account code and name are required.".into());
+    }
+    if !ACCOUNT_TYPES.contains(&account.type_.as_str()) {
+        return Err(format!("Account type must be one of: {}", ACCOUNT_TYPES.join(", ")));
+    }
+    if let Some(class) = account.budget_class.as_deref().filter(|class| !BUDGET_CLASSES.contains(class)) {
+        return Err(format!("Unknown budget class '{}'. Use one of: {}", class, BUDGET_CLASSES.join(", ")));
+    }
+    let category = account.category.as_deref().map(str::trim).filter(|category| !category.is_empty());
+
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    if let Some(id) = account.id {
+        conn.execute(
+            "UPDATE accounts SET code = ?1, name = ?2, type_ = ?3, budget_class = ?4, category = ?5, active = ?6 WHERE id = ?7",
+            params![account.code.trim(), account.name.trim(), account.type_, account.budget_class, category, account.active, id],
+        ).map_err(|e| e.to_string())?;
+    } else {
+        conn.execute(
+            "INSERT INTO accounts (code, name, type_, budget_class, category, active) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![account.code.trim(), account.name.trim(), account.type_, account.budget_class, category, account.active],
+        ).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn within(date: &str, from: &Option<String>, to: &Option<String>) -> bool {
+    let Some(date) = local_date(date) else {
+        return true;
+    };
+    let after_start = from.as_deref().and_then(local_date).is_none_or(|from| date >= from);
+    let before_end = to.as_deref().and_then(local_date).is_none_or(|to| date <= to);
+    after_start && before_end
+}
+
+#[tauri::command]
+pub fn fetch_journal_entries_command(from: Option<String>, to: Option<String>) -> Result<Vec<JournalEntry>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.id, e.date, e.description, e.fund, e.source, e.source_id,
+                    l.id, l.account_id, a.code, a.name, l.debit, l.credit, l.memo
+             FROM journal_entries e
+             JOIN journal_lines l ON l.entry_id = e.id
+             JOIN accounts a ON a.id = l.account_id
+             ORDER BY e.date, e.id, l.id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let entry = JournalEntry {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                description: row.get(2)?,
+                fund: row.get(3)?,
+                source: row.get(4)?,
+                source_id: row.get(5)?,
+                lines: Vec::new(),
+            };
+            let line = JournalLine {
+                id: row.get(6)?,
+                account_id: row.get(7)?,
+                account_code: row.get(8)?,
+                account_name: row.get(9)?,
+                debit: row.get(10)?,
+                credit: row.get(11)?,
+                memo: row.get(12)?,
+            };
+            Ok((entry, line))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut entries: Vec<JournalEntry> = Vec::new();
+    for row in rows {
+        let (entry, line) = row.map_err(|e| e.to_string())?;
+        if !within(&entry.date, &from, &to) {
+            continue;
+        }
+        match entries.last_mut() {
+            Some(last) if last.id == entry.id => last.lines.push(line),
+            _ => entries.push(JournalEntry { lines: vec![line], ..entry }),
+        }
+    }
+
+    Ok(entries)
+}
+
+// Manual adjustments, e.g. opening balances or deposits from the treasury to the bank.
+// Every line is either a debit or a credit and both sides must agree to the centavo.
+#[tauri::command]
+pub fn insert_journal_entry_command(entry: JournalEntry) -> Result<i64, String> {
+    if entry.description.trim().is_empty() {
+        return Err("A description is required.".into());
+    }
+    if entry.lines.len() < 2 {
+        return Err("A journal entry needs at least two lines.".into());
+    }
+
+    let mut postings = Vec::new();
+    for line in &entry.lines {
+        let (debit, credit) = (line.debit, line.credit);
+        if debit.is_negative() || credit.is_negative() || debit.is_zero() == credit.is_zero() {
+            return Err("Each line must have either a debit or a credit amount, not both.".into());
+        }
+        postings.push(Posting {
+            account_id: line.account_id as i64,
+            debit,
+            credit,
+            memo: line.memo.clone().filter(|memo| !memo.trim().is_empty()),
+        });
+    }
+
+    let total_debit: Money = postings.iter().map(|posting| posting.debit).sum();
+    let total_credit: Money = postings.iter().map(|posting| posting.credit).sum();
+    if total_debit != total_credit {
+        return Err(format!(
+            "Entry is out of balance: debits {}, credits {}.",
+            total_debit, total_credit
+        ));
+    }
+
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    for posting in &postings {
+        let exists: Option<i64> = tx
+            .query_row("SELECT id FROM accounts WHERE id = ?1", params![posting.account_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if exists.is_none() {
+            return Err(format!("Account {} does not exist.", posting.account_id));
+        }
+    }
+
+    let fund = entry.fund.as_deref().filter(|fund| !fund.trim().is_empty()).unwrap_or(GENERAL_FUND);
+    let id = write_entry(&tx, &entry.date, entry.description.trim(), fund, "manual", None, &postings)
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
+
+// Only manual entries can be removed; income and expense entries follow their records
+#[tauri::command]
+pub fn delete_journal_entry_command(id: i32) -> Result<(), String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let source: Option<String> = tx
+        .query_row("SELECT source FROM journal_entries WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match source.as_deref() {
+        None => return Err(format!("Journal entry {} does not exist.", id)),
+        Some("manual") => {}
+        Some(source) => {
+            return Err(format!("This entry was posted from an {} record. Edit or delete that record instead.", source));
+        }
+    }
+
+    tx.execute("DELETE FROM journal_lines WHERE entry_id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM journal_entries WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Net balance of every account with activity up to `as_of` (inclusive), shown on its debit
+// or credit side.
+#[tauri::command]
+pub fn fetch_trial_balance_command(as_of: Option<String>) -> Result<TrialBalance, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.date, a.id, a.code, a.name, a.type_, a.budget_class, l.debit, l.credit
+             FROM journal_lines l
+             JOIN journal_entries e ON e.id = l.entry_id
+             JOIN accounts a ON a.id = l.account_id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                TrialBalanceRow {
+                    account_id: row.get(1)?,
+                    code: row.get(2)?,
+                    name: row.get(3)?,
+                    type_: row.get(4)?,
+                    budget_class: row.get(5)?,
+                    debit: Money::ZERO,
+                    credit: Money::ZERO,
+                },
+                row.get::<_, Money>(6)? - row.get::<_, Money>(7)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut balances: BTreeMap<String, (TrialBalanceRow, Money)> = BTreeMap::new();
+    for row in rows {
+        let (date, account, net) = row.map_err(|e| e.to_string())?;
+        if !within(&date, &None, &as_of) {
+            continue;
+        }
+        balances.entry(account.code.clone()).or_insert((account, Money::ZERO)).1 += net;
+    }
+
+    let mut total_debit = Money::ZERO;
+    let mut total_credit = Money::ZERO;
+    let mut trial_rows = Vec::new();
+    for (_, (mut row, net)) in balances {
+        if net.is_zero() {
+            continue;
+        }
+        if net.is_negative() {
+            total_credit -= net;
+            row.credit = -net;
+        } else {
+            total_debit += net;
+            row.debit = net;
+        }
+        trial_rows.push(row);
+    }
+
+    Ok(TrialBalance {
+        as_of,
+        rows: trial_rows,
+        total_debit,
+        total_credit,
+        balanced: total_debit == total_credit,
+    })
+}
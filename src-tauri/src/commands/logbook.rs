@@ -1,48 +1,303 @@
-use rusqlite::params;
+use chrono::{Days, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use crate::commands::attendance::Calendar;
+use crate::commands::settings::DEFAULT_SCHEDULE;
 use crate::database::connection::establish_connection;
 use crate::models::logbook::Logbook;
+use crate::pdf::document::local_date;
 
-#[tauri::command]
-pub fn fetch_all_logbook_entries_command() -> Result<Vec<Logbook>, String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+// The official's current name, role and section come from officials; the name typed
+// on the entry is only shown for entries that were never linked
+const LOGBOOK_COLUMNS: &str = "l.id, COALESCE(o.name, l.official_name), l.date, l.time_in_am, l.time_out_am, l.time_in_pm, l.time_out_pm,
+     l.remarks, l.status, l.total_hours, l.created_at, l.updated_at, l.undertime_minutes, l.official_id, o.role, o.section
+     FROM logbook l
+     LEFT JOIN officials o ON o.id = l.official_id";
 
-    let mut stmt = conn.prepare(
-        "SELECT id, official_name, date, time_in_am, time_out_am, time_in_pm, time_out_pm, remarks, status, total_hours, created_at, updated_at FROM logbook"
-    ).map_err(|e| e.to_string())?;
+fn logbook_from_row(row: &Row) -> rusqlite::Result<Logbook> {
+    Ok(Logbook {
+        id: row.get(0)?,
+        official_id: row.get(13)?,
+        official_name: row.get(1)?,
+        official_role: row.get(14)?,
+        official_section: row.get(15)?,
+        date: row.get(2)?,
+        time_in_am: row.get(3)?,
+        time_out_am: row.get(4)?,
+        time_in_pm: row.get(5)?,
+        time_out_pm: row.get(6)?,
+        remarks: row.get(7)?,
+        status: row.get(8)?,
+        total_hours: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        undertime_minutes: row.get(12)?,
+    })
+}
 
-    let rows = stmt
-        .query_map([], |row| {
-            Ok(Logbook {
-                id: row.get(0)?,
-                official_name: row.get(1)?,
-                date: row.get(2)?,
-                time_in_am: row.get(3)?,
-                time_out_am: row.get(4)?,
-                time_in_pm: row.get(5)?,
-                time_out_pm: row.get(6)?,
-                remarks: row.get(7)?,
-                status: row.get(8)?,
-                total_hours: row.get(9)?,
-                created_at: row.get(10)?,
-                updated_at: row.get(11)?,
+// Office hours from Settings: the morning and afternoon windows, plus the grace
+// period before an arrival counts as late
+struct Schedule {
+    am: (NaiveTime, NaiveTime),
+    pm: (NaiveTime, NaiveTime),
+    grace: i64,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .ok()
+}
+
+fn schedule(conn: &Connection) -> Schedule {
+    let time = |value: Option<String>, index: usize| {
+        value
+            .as_deref()
+            .and_then(parse_time)
+            .or_else(|| parse_time(DEFAULT_SCHEDULE[index]))
+            .unwrap_or_default()
+    };
+    conn.query_row(
+        "SELECT office_am_in, office_am_out, office_pm_in, office_pm_out, grace_minutes FROM settings WHERE id = 1",
+        [],
+        |row| {
+            Ok(Schedule {
+                am: (time(row.get(0)?, 0), time(row.get(1)?, 1)),
+                pm: (time(row.get(2)?, 2), time(row.get(3)?, 3)),
+                grace: row.get::<_, Option<i64>>(4)?.unwrap_or(0),
             })
+        },
+    )
+    .unwrap_or_else(|_| Schedule {
+        am: (time(None, 0), time(None, 1)),
+        pm: (time(None, 2), time(None, 3)),
+        grace: 0,
+    })
+}
+
+fn minutes(from: NaiveTime, to: NaiveTime) -> i64 {
+    (to - from).num_minutes().max(0)
+}
+
+// Minutes of [start, end] that fall inside the window
+fn overlap((start, end): (NaiveTime, NaiveTime), window: (NaiveTime, NaiveTime)) -> i64 {
+    minutes(start.max(window.0), end.min(window.1))
+}
+
+// Works out total_hours, status and undertime_minutes from the punches. A day is
+// closed once the afternoon punch-out is in or the date has passed; until then
+// missing time isn't counted against the official.
+fn compute_attendance(schedule: &Schedule, entry: &mut Logbook, today: NaiveDate) -> Result<(), String> {
+    let slot = |value: &Option<String>, label: &str| -> Result<Option<NaiveTime>, String> {
+        match value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(value) => parse_time(value)
+                .map(Some)
+                .ok_or_else(|| format!("The {} time '{}' is not a valid HH:MM time.", label, value)),
+            None => Ok(None),
+        }
+    };
+    let punches = [
+        slot(&entry.time_in_am, "morning time-in")?,
+        slot(&entry.time_out_am, "morning time-out")?,
+        slot(&entry.time_in_pm, "afternoon time-in")?,
+        slot(&entry.time_out_pm, "afternoon time-out")?,
+    ];
+
+    // every punch must come after the one before it, and no time-out without a time-in
+    let mut last: Option<NaiveTime> = None;
+    for punch in punches.iter().flatten() {
+        if last.is_some_and(|last| *punch < last) {
+            return Err("Punches are out of order. Times must run morning in, morning out, afternoon in, afternoon out.".into());
+        }
+        last = Some(*punch);
+    }
+    if (punches[1].is_some() && punches[0].is_none()) || (punches[3].is_some() && punches[2].is_none()) {
+        return Err("A time-out needs a matching time-in.".into());
+    }
+
+    let required = minutes(schedule.am.0, schedule.am.1) + minutes(schedule.pm.0, schedule.pm.1);
+    let worked: i64 = [(punches[0], punches[1]), (punches[2], punches[3])]
+        .into_iter()
+        .filter_map(|session| match session {
+            (Some(start), Some(end)) => Some(overlap((start, end), schedule.am) + overlap((start, end), schedule.pm)),
+            _ => None,
         })
+        .sum();
+
+    let closed = punches[3].is_some() || local_date(&entry.date).is_some_and(|date| date < today);
+    let first_in = punches[0].or(punches[2]);
+    let late = first_in.map_or(0, |arrival| {
+        let start = if arrival < schedule.pm.0 { schedule.am.0 } else { schedule.pm.0 };
+        minutes(start, arrival)
+    });
+    // lateness within the grace period is excused
+    let excused = if late <= schedule.grace { late } else { 0 };
+    let short = if closed { required - worked - excused } else { late - excused };
+
+    let status = match first_in {
+        None => "Absent",
+        Some(_) if late > schedule.grace => "Late",
+        Some(_) if closed && short > 0 => "Undertime",
+        Some(_) => "Present",
+    };
+
+    entry.total_hours = Some((worked as f64 / 60.0 * 100.0).round() / 100.0);
+    entry.undertime_minutes = Some(short.max(0) as i32);
+    entry.status = Some(status.to_string());
+    Ok(())
+}
+
+fn official_name(conn: &Connection, official_id: i32) -> Result<String, String> {
+    conn.query_row("SELECT name FROM officials WHERE id = ?1", params![official_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Official {} does not exist.", official_id))
+}
+
+// Fills in official_id from the name when the form only sent a name, and the name
+// from officials otherwise, so every saved entry points at a real official
+fn link_official(conn: &Connection, entry: &mut Logbook) -> Result<(), String> {
+    if let Some(official_id) = entry.official_id {
+        entry.official_name = official_name(conn, official_id)?;
+        return Ok(());
+    }
+
+    let name = entry.official_name.trim();
+    if name.is_empty() {
+        return Err("Choose the official for this entry.".into());
+    }
+    entry.official_id = conn
+        .query_row(
+            "SELECT id FROM officials WHERE LOWER(TRIM(name)) = LOWER(?1) ORDER BY id DESC LIMIT 1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()
         .map_err(|e| e.to_string())?;
+    if entry.official_id.is_none() {
+        return Err(format!("There is no official named '{}'.", name));
+    }
+    Ok(())
+}
+
+// The official's logbook entry for a day, if there is one yet, other than `except`
+fn entry_for_day(conn: &Connection, official_id: i32, day: NaiveDate, except: Option<i32>) -> Result<Option<Logbook>, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} WHERE l.official_id = ?1 ORDER BY l.id", LOGBOOK_COLUMNS))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![official_id], logbook_from_row).map_err(|e| e.to_string())?;
 
-    let mut entries = Vec::new();
     for entry in rows {
-        entries.push(entry.map_err(|e| e.to_string())?);
+        let entry = entry.map_err(|e| e.to_string())?;
+        if local_date(&entry.date) == Some(day) && (except.is_none() || entry.id != except) {
+            return Ok(Some(entry));
+        }
     }
+    Ok(None)
+}
 
-    Ok(entries)
+// An official has one entry per day; punches and edits go into that entry
+fn check_one_per_day(conn: &Connection, entry: &Logbook) -> Result<(), String> {
+    let day = local_date(&entry.date).ok_or_else(|| format!("The date '{}' is not a valid date.", entry.date))?;
+    let Some(official_id) = entry.official_id else {
+        return Ok(());
+    };
+    match entry_for_day(conn, official_id, day, entry.id)? {
+        Some(other) => Err(format!(
+            "{} already has a logbook entry for {} (#{}). Edit that entry instead.",
+            entry.official_name,
+            day,
+            other.id.unwrap_or_default()
+        )),
+        None => Ok(()),
+    }
 }
 
-#[tauri::command]
-pub fn insert_logbook_entry_command(entry: Logbook) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+// Stamps `at` into the official's entry for that day. Time-ins go to the morning
+// slot before the afternoon session starts and to the afternoon slot after; a
+// time-out closes whichever session is open.
+pub fn punch(conn: &Connection, official_id: i32, at: NaiveDateTime, clock_in: bool) -> Result<Logbook, String> {
+    let name = official_name(conn, official_id)?;
+    let schedule = schedule(conn);
+    let time = at.time().format("%H:%M").to_string();
+    let mut entry = entry_for_day(conn, official_id, at.date(), None)?.unwrap_or_else(|| Logbook {
+        id: None,
+        official_id: Some(official_id),
+        official_name: name.clone(),
+        official_role: None,
+        official_section: None,
+        date: at.date().to_string(),
+        time_in_am: None,
+        time_out_am: None,
+        time_in_pm: None,
+        time_out_pm: None,
+        remarks: None,
+        status: None,
+        total_hours: None,
+        undertime_minutes: None,
+        created_at: None,
+        updated_at: None,
+    });
+
+    let set = |value: &Option<String>| value.as_deref().is_some_and(|value| !value.trim().is_empty());
+    let open_am = set(&entry.time_in_am) && !set(&entry.time_out_am);
+    let open_pm = set(&entry.time_in_pm) && !set(&entry.time_out_pm);
+
+    if clock_in {
+        if open_am || open_pm {
+            let since = if open_pm { &entry.time_in_pm } else { &entry.time_in_am };
+            return Err(format!("{} is already clocked in since {}.", name, since.clone().unwrap_or_default()));
+        }
+        if at.time() < schedule.pm.0 && !set(&entry.time_in_am) && !set(&entry.time_in_pm) {
+            entry.time_in_am = Some(time);
+        } else {
+            if set(&entry.time_in_pm) {
+                return Err(format!("{} already clocked in and out this afternoon.", name));
+            }
+            entry.time_in_pm = Some(time);
+        }
+    } else if open_pm {
+        entry.time_out_pm = Some(time);
+    } else if open_am {
+        entry.time_out_am = Some(time);
+    } else {
+        return Err(format!("{} is not clocked in.", name));
+    }
+
+    compute_attendance(&schedule, &mut entry, at.date())?;
+    match entry.id {
+        Some(id) => conn
+            .execute(
+                "UPDATE logbook SET time_in_am = ?1, time_out_am = ?2, time_in_pm = ?3, time_out_pm = ?4,
+                 status = ?5, total_hours = ?6, undertime_minutes = ?7, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = ?8",
+                params![
+                    entry.time_in_am,
+                    entry.time_out_am,
+                    entry.time_in_pm,
+                    entry.time_out_pm,
+                    entry.status,
+                    entry.total_hours,
+                    entry.undertime_minutes,
+                    id
+                ],
+            )
+            .map_err(|e| e.to_string())?,
+        None => write_entry(conn, &entry)?,
+    };
 
+    let id = entry.id.unwrap_or_else(|| conn.last_insert_rowid() as i32);
+    let mut entry = conn
+        .query_row(&format!("SELECT {} WHERE l.id = ?1", LOGBOOK_COLUMNS), params![id], logbook_from_row)
+        .map_err(|e| e.to_string())?;
+    Calendar::load(conn)?.settle(&mut entry);
+    Ok(entry)
+}
+
+fn write_entry(conn: &Connection, entry: &Logbook) -> Result<usize, String> {
     conn.execute(
         "INSERT INTO logbook (
+            official_id,
             official_name,
             date,
             time_in_am,
@@ -51,9 +306,11 @@ pub fn insert_logbook_entry_command(entry: Logbook) -> Result<(), String> {
             time_out_pm,
             remarks,
             status,
-            total_hours
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            total_hours,
+            undertime_minutes
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         params![
+            entry.official_id,
             entry.official_name,
             entry.date,
             entry.time_in_am,
@@ -62,16 +319,136 @@ pub fn insert_logbook_entry_command(entry: Logbook) -> Result<(), String> {
             entry.time_out_pm,
             entry.remarks,
             entry.status,
-            entry.total_hours
+            entry.total_hours,
+            entry.undertime_minutes
         ],
-    ).map_err(|e| e.to_string())?;
+    ).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clock_in_command(official_id: i32) -> Result<Logbook, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    punch(&conn, official_id, Local::now().naive_local(), true)
+}
+
+#[tauri::command]
+pub fn clock_out_command(official_id: i32) -> Result<Logbook, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    punch(&conn, official_id, Local::now().naive_local(), false)
+}
+
+// Days left open (no afternoon time-out) are settled against the schedule once they
+// have passed, so missing time then counts. Runs at startup. Entries whose times
+// can't be worked out keep what was stored and are reported. Returns how many
+// entries changed.
+pub fn settle_past_days(conn: &Connection) -> Result<usize, String> {
+    let today = Local::now().date_naive();
+    // stored UTC timestamps can run a day behind, so the exact check is below
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} WHERE (l.time_out_pm IS NULL OR TRIM(l.time_out_pm) = '') AND l.date < ?1",
+            LOGBOOK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let entries = stmt
+        .query_map(params![today.to_string()], logbook_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<Logbook>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let schedule = schedule(conn);
+    let mut changed = 0;
+    for mut entry in entries {
+        if local_date(&entry.date).is_none_or(|date| date >= today) {
+            continue;
+        }
+        let stored = (entry.status.clone(), entry.total_hours, entry.undertime_minutes);
+        if let Err(e) = compute_attendance(&schedule, &mut entry, today) {
+            eprintln!("❌ Logbook entry #{} can't be settled: {}", entry.id.unwrap_or_default(), e);
+            continue;
+        }
+        if (entry.status.clone(), entry.total_hours, entry.undertime_minutes) != stored {
+            changed += conn
+                .execute(
+                    "UPDATE logbook SET status = ?1, total_hours = ?2, undertime_minutes = ?3 WHERE id = ?4",
+                    params![entry.status, entry.total_hours, entry.undertime_minutes, entry.id],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(changed)
+}
+
+// Entries of one official or everyone, optionally within an inclusive date range.
+// Dates are stored either as YYYY-MM-DD or as UTC timestamps, so the SQL range is
+// widened by a day on each side and the exact local-date check is done afterwards.
+pub fn fetch_entries(
+    conn: &Connection,
+    official_id: Option<i32>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Vec<Logbook>, String> {
+    let lower = from.and_then(|date| date.checked_sub_days(Days::new(1))).map(|date| date.to_string());
+    let upper = to.and_then(|date| date.checked_add_days(Days::new(2))).map(|date| date.to_string());
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} WHERE (?1 IS NULL OR l.official_id = ?1) AND (?2 IS NULL OR l.date >= ?2) AND (?3 IS NULL OR l.date < ?3)
+             ORDER BY l.date, l.id",
+            LOGBOOK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![official_id, lower, upper], logbook_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let calendar = Calendar::load(conn)?;
+    let mut entries = Vec::new();
+    for entry in rows {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        if from.is_some() || to.is_some() {
+            let Some(date) = local_date(&entry.date) else {
+                continue;
+            };
+            if from.is_some_and(|from| date < from) || to.is_some_and(|to| date > to) {
+                continue;
+            }
+        }
+        calendar.settle(&mut entry);
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+// All entries, or only one official's when `official_id` is given
+#[tauri::command]
+pub fn fetch_all_logbook_entries_command(official_id: Option<i32>) -> Result<Vec<Logbook>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    fetch_entries(&conn, official_id, None, None)
+}
+
+// Hours, status and undertime are worked out here from the times entered,
+// whatever the form sent
+#[tauri::command]
+pub fn insert_logbook_entry_command(mut entry: Logbook) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    link_official(&conn, &mut entry)?;
+    check_one_per_day(&conn, &entry)?;
+    compute_attendance(&schedule(&conn), &mut entry, Local::now().date_naive())?;
+
+    write_entry(&conn, &entry)?;
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn update_logbook_entry_command(entry: Logbook) -> Result<(), String> {
+pub fn update_logbook_entry_command(mut entry: Logbook) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
+    link_official(&conn, &mut entry)?;
+    check_one_per_day(&conn, &entry)?;
+    compute_attendance(&schedule(&conn), &mut entry, Local::now().date_naive())?;
 
     conn.execute(
         "UPDATE logbook SET
@@ -83,8 +460,11 @@ pub fn update_logbook_entry_command(entry: Logbook) -> Result<(), String> {
             time_out_pm = ?6,
             remarks = ?7,
             status = ?8,
-            total_hours = ?9
-         WHERE id = ?10",
+            total_hours = ?9,
+            undertime_minutes = ?10,
+            official_id = ?11,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?12",
         params![
             entry.official_name,
             entry.date,
@@ -95,6 +475,8 @@ pub fn update_logbook_entry_command(entry: Logbook) -> Result<(), String> {
             entry.remarks,
             entry.status,
             entry.total_hours,
+            entry.undertime_minutes,
+            entry.official_id,
             entry.id
         ],
     ).map_err(|e| e.to_string())?;
@@ -121,3 +503,110 @@ pub fn delete_logbook_entry_command(id: i32) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        parse_time(value).unwrap()
+    }
+
+    // 08:00-12:00 and 13:00-17:00 with a ten-minute grace period
+    fn office_hours() -> Schedule {
+        Schedule {
+            am: (time("08:00"), time("12:00")),
+            pm: (time("13:00"), time("17:00")),
+            grace: 10,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    fn entry(date: &str, punches: [Option<&str>; 4]) -> Logbook {
+        let [time_in_am, time_out_am, time_in_pm, time_out_pm] = punches.map(|punch| punch.map(str::to_string));
+        Logbook {
+            id: None,
+            official_id: Some(1),
+            official_name: "Maria Santos".into(),
+            official_role: None,
+            official_section: None,
+            date: date.into(),
+            time_in_am,
+            time_out_am,
+            time_in_pm,
+            time_out_pm,
+            remarks: None,
+            status: None,
+            total_hours: None,
+            undertime_minutes: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn attendance(date: &str, punches: [Option<&str>; 4]) -> Result<(String, f64, i32), String> {
+        let mut entry = entry(date, punches);
+        compute_attendance(&office_hours(), &mut entry, today())?;
+        Ok((entry.status.unwrap(), entry.total_hours.unwrap(), entry.undertime_minutes.unwrap()))
+    }
+
+    #[test]
+    fn refuses_punches_out_of_order() {
+        let morning_out_before_in = attendance("2026-10-16", [Some("09:00"), Some("08:00"), None, None]);
+        assert!(morning_out_before_in.unwrap_err().contains("out of order"));
+
+        let afternoon_before_morning_out = attendance("2026-10-16", [Some("08:00"), Some("12:30"), Some("12:15"), Some("17:00")]);
+        assert!(afternoon_before_morning_out.unwrap_err().contains("out of order"));
+
+        let out_without_in = attendance("2026-10-16", [None, Some("12:00"), Some("13:00"), Some("17:00")]);
+        assert!(out_without_in.unwrap_err().contains("matching time-in"));
+
+        let not_a_time = attendance("2026-10-16", [Some("8 am"), None, None, None]);
+        assert!(not_a_time.unwrap_err().contains("not a valid HH:MM time"));
+    }
+
+    #[test]
+    fn full_day_on_time_is_present() {
+        let day = attendance("2026-10-16", [Some("07:45"), Some("12:00"), Some("13:00"), Some("17:30")]);
+        assert_eq!(day, Ok(("Present".to_string(), 8.0, 0)));
+    }
+
+    #[test]
+    fn lateness_within_grace_is_excused() {
+        let day = attendance("2026-10-16", [Some("08:10"), Some("12:00"), Some("13:00"), Some("17:00")]);
+        assert_eq!(day, Ok(("Present".to_string(), 7.83, 0)));
+    }
+
+    #[test]
+    fn lateness_past_grace_counts_in_full() {
+        let day = attendance("2026-10-16", [Some("08:11"), Some("12:00"), Some("13:00"), Some("17:00")]);
+        assert_eq!(day, Ok(("Late".to_string(), 7.82, 11)));
+
+        let afternoon_only = attendance("2026-10-16", [None, None, Some("13:30"), Some("17:00")]);
+        assert_eq!(afternoon_only, Ok(("Late".to_string(), 3.5, 270)));
+    }
+
+    #[test]
+    fn open_day_only_counts_lateness() {
+        let day = attendance("2026-10-19", [Some("08:05"), Some("12:00"), None, None]);
+        assert_eq!(day, Ok(("Present".to_string(), 3.92, 0)));
+
+        let late = attendance("2026-10-19", [Some("08:20"), None, None, None]);
+        assert_eq!(late, Ok(("Late".to_string(), 0.0, 20)));
+    }
+
+    #[test]
+    fn past_day_left_open_is_undertime() {
+        let day = attendance("2026-10-16", [Some("08:00"), Some("12:00"), None, None]);
+        assert_eq!(day, Ok(("Undertime".to_string(), 4.0, 240)));
+    }
+
+    #[test]
+    fn no_punches_is_absent() {
+        let day = attendance("2026-10-16", [None, None, None, None]);
+        assert_eq!(day, Ok(("Absent".to_string(), 0.0, 480)));
+    }
+}
@@ -9,4 +9,20 @@ pub mod certificates;
 pub mod user;
 pub mod households;
 pub mod barangay_map;
-pub mod logbook;
\ No newline at end of file
+pub mod logbook;
+pub mod verification;
+pub mod certificate_types;
+pub mod reports;
+pub mod ledger;
+pub mod budget;
+pub mod receipts;
+pub mod finance;
+pub mod vouchers;
+pub mod cash_advances;
+pub mod attendance;
+pub mod holidays;
+pub mod leaves;
+pub mod committees;
+pub mod signatories;
+pub mod venues;
+pub mod event_attendance;
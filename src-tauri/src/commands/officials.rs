@@ -1,15 +1,167 @@
 use crate::database::connection::establish_connection;
-use crate::models::official::Official;
-use rusqlite::params;
+use crate::models::official::{Official, OfficialTerm};
+use crate::pdf::document::local_date;
+use chrono::{Days, Local, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 
+// Terms run until they are ended. A term whose scheduled end has passed without
+// being ended is a holdover and still counts as in office.
+pub const IN_OFFICE: &str = "(t.term_start IS NULL OR t.term_start <= ?1) AND (t.ended_on IS NULL OR t.ended_on >= ?1)";
+
+const TERM_QUERY: &str = "SELECT t.id, t.official_id, o.name, t.role, t.section, t.term_start, t.term_end, t.ended_on, t.end_reason
+     FROM official_terms t
+     JOIN officials o ON o.id = t.official_id";
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+// A date typed by the user, or today when left blank
+pub fn date_or_today(value: Option<&str>) -> Result<NaiveDate, String> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => local_date(value).ok_or_else(|| format!("'{}' is not a valid date.", value)),
+        None => Ok(today()),
+    }
+}
+
+fn term_from_row(row: &Row, today: NaiveDate) -> rusqlite::Result<OfficialTerm> {
+    let term_end: Option<String> = row.get(6)?;
+    let ended_on: Option<String> = row.get(7)?;
+    let days_remaining = match ended_on {
+        Some(_) => None,
+        None => term_end.as_deref().and_then(local_date).map(|end| (end - today).num_days()),
+    };
+
+    Ok(OfficialTerm {
+        id: row.get(0)?,
+        official_id: row.get(1)?,
+        official_name: row.get(2)?,
+        role: row.get(3)?,
+        section: row.get(4)?,
+        term_start: row.get(5)?,
+        term_end,
+        ended_on,
+        end_reason: row.get(8)?,
+        days_remaining,
+    })
+}
+
+fn fetch_terms(conn: &Connection, filter: &str, values: &[&dyn rusqlite::ToSql]) -> Result<Vec<OfficialTerm>, String> {
+    let mut stmt = conn
+        .prepare(&format!("{} WHERE {}", TERM_QUERY, filter))
+        .map_err(|e| e.to_string())?;
+    let today = today();
+    let rows = stmt
+        .query_map(values, |row| term_from_row(row, today))
+        .map_err(|e| e.to_string())?;
+
+    let mut terms = Vec::new();
+    for term in rows {
+        terms.push(term.map_err(|e| e.to_string())?);
+    }
+    Ok(terms)
+}
+
+// The official holding `role` on a date, as (id, name). When two terms overlap,
+// the one that started last wins.
+pub fn holder_on(conn: &Connection, role: &str, date: NaiveDate) -> Result<Option<(i32, String)>, String> {
+    conn.query_row(
+        &format!(
+            "SELECT o.id, o.name FROM official_terms t
+             JOIN officials o ON o.id = t.official_id
+             WHERE {} AND t.role = ?2 COLLATE NOCASE
+             ORDER BY t.term_start DESC, t.id DESC LIMIT 1",
+            IN_OFFICE
+        ),
+        params![date.to_string(), role],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+// Start and end of term as YYYY-MM-DD; either may be left blank
+fn term_dates(official: &Official) -> Result<(Option<String>, Option<String>), String> {
+    let parse = |value: &str, label: &str| -> Result<Option<NaiveDate>, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        local_date(value)
+            .map(Some)
+            .ok_or_else(|| format!("The {} '{}' is not a valid date.", label, value))
+    };
+    let start = parse(&official.term_start, "start of term")?;
+    let end = parse(&official.term_end, "end of term")?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err("The end of term must come after its start.".into());
+        }
+    }
+    Ok((start.map(|date| date.to_string()), end.map(|date| date.to_string())))
+}
+
+// The term the official is serving, as (id, term_start)
+pub fn open_term(conn: &Connection, official_id: i32) -> Result<Option<(i32, Option<String>)>, String> {
+    conn.query_row(
+        "SELECT id, term_start FROM official_terms WHERE official_id = ?1 AND ended_on IS NULL
+         ORDER BY term_start DESC, id DESC LIMIT 1",
+        params![official_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn start_term(conn: &Connection, official_id: i32, official: &Official, start: &Option<String>, end: &Option<String>) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO official_terms (official_id, role, section, term_start, term_end) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![official_id, official.role, official.section, start, end],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn end_term(conn: &Connection, term_id: i32, ended_on: NaiveDate, reason: &str) -> Result<(), String> {
+    conn.execute(
+        "UPDATE official_terms SET ended_on = ?1, end_reason = ?2 WHERE id = ?3",
+        params![ended_on.to_string(), reason, term_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// The person's details, with the position and term columns mirroring their current term
+fn write_official(conn: &Connection, id: i32, official: &Official, start: &Option<String>, end: &Option<String>) -> Result<(), String> {
+    conn.execute(
+        "UPDATE officials SET name = ?1, role = ?2, image = ?3, section = ?4, age = ?5, contact = ?6, term_start = ?7, term_end = ?8, zone = ?9 WHERE id = ?10",
+        params![
+            official.name,
+            official.role,
+            official.image,
+            official.section,
+            official.age,
+            official.contact,
+            start.clone().unwrap_or_default(),
+            end.clone().unwrap_or_default(),
+            official.zone,
+            id,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// The current roster: officials serving a term that hasn't been ended
 #[tauri::command]
 pub fn fetch_all_officials_command() -> Result<Vec<Official>, String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, name, role, image, section, age, contact, term_start, term_end, zone 
-             FROM officials",
+            "SELECT id, name, role, image, section, age, contact, term_start, term_end, zone
+             FROM officials o
+             WHERE EXISTS (SELECT 1 FROM official_terms t WHERE t.official_id = o.id AND t.ended_on IS NULL)",
         )
         .map_err(|e| e.to_string())?;
 
@@ -38,72 +190,187 @@ pub fn fetch_all_officials_command() -> Result<Vec<Official>, String> {
     Ok(officials)
 }
 
+// Adds an official to the roster. Someone who served before gets a new term on
+// their existing record, so their history stays together.
 #[tauri::command]
 pub fn insert_official_command(official: Official) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let (start, end) = term_dates(&official)?;
 
     // Check for duplicate first
     let mut stmt = conn
-        .prepare("SELECT COUNT(*) FROM officials WHERE LOWER(name) = LOWER(?1) AND section = ?2")
+        .prepare(
+            "SELECT COUNT(*) FROM officials o
+             JOIN official_terms t ON t.official_id = o.id AND t.ended_on IS NULL
+             WHERE LOWER(TRIM(o.name)) = LOWER(TRIM(?1)) AND t.section = ?2",
+        )
         .map_err(|e| e.to_string())?;
 
     let count: i32 = stmt
         .query_row(params![official.name, official.section], |row| row.get(0))
         .map_err(|e| e.to_string())?;
+    drop(stmt);
 
     if count > 0 {
         return Err("An official with this name and section already exists.".into());
     }
 
-    // Insert official if no duplicate
-    conn.execute(
-        "INSERT INTO officials (name, role, image, section, age, contact, term_start, term_end, zone)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-        params![
-            official.name,
-            official.role,
-            official.image,
-            official.section,
-            official.age,
-            official.contact,
-            official.term_start,
-            official.term_end,
-            official.zone
-        ],
-    ).map_err(|e| e.to_string())?;
+    let returning: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM officials o
+             WHERE LOWER(TRIM(name)) = LOWER(TRIM(?1))
+               AND NOT EXISTS (SELECT 1 FROM official_terms t WHERE t.official_id = o.id AND t.ended_on IS NULL)
+             ORDER BY id DESC LIMIT 1",
+            params![official.name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = match returning {
+        Some(id) => {
+            write_official(&tx, id, &official, &start, &end)?;
+            id
+        }
+        None => {
+            tx.execute(
+                "INSERT INTO officials (name, role, image, section, age, contact, term_start, term_end, zone)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    official.name,
+                    official.role,
+                    official.image,
+                    official.section,
+                    official.age,
+                    official.contact,
+                    start.clone().unwrap_or_default(),
+                    end.clone().unwrap_or_default(),
+                    official.zone
+                ],
+            ).map_err(|e| e.to_string())?;
+            tx.last_insert_rowid() as i32
+        }
+    };
+    start_term(&tx, id, &official, &start, &end)?;
+    tx.commit().map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+// Edits the official and their current term. A later start of term means a new
+// term (e.g. re-election or a new position): the current one is closed the day
+// before and kept in the history. Any other change corrects the current term.
+// A former official only has their personal details corrected; bringing them
+// back into office goes through insert_official_command.
 #[tauri::command]
 pub fn save_official_command(official: Official) -> Result<(), String> {
-    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let id = official.id.ok_or("Choose the official to update.")?;
+    let (start, end) = term_dates(&official)?;
 
-    conn.execute(
-        "UPDATE officials SET name = ?1, role = ?2, image = ?3, section = ?4, age = ?5, contact = ?6, term_start = ?7, term_end = ?8, zone = ?9 WHERE id = ?10",
-        params![
-            official.name,
-            official.role,
-            official.image,
-            official.section,
-            official.age,
-            official.contact,
-            official.term_start,
-            official.term_end,
-            official.zone,
-            official.id,
-        ],
-    ).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    match open_term(&tx, id)? {
+        Some((term_id, current_start)) if start.is_some() && current_start.as_ref().is_some_and(|current| start.as_ref() > Some(current)) => {
+            write_official(&tx, id, &official, &start, &end)?;
+            let new_start = start.as_deref().and_then(local_date).unwrap_or_else(today);
+            let ended_on = new_start.checked_sub_days(Days::new(1)).unwrap_or(new_start);
+            end_term(&tx, term_id, ended_on, "Succeeded by a new term")?;
+            start_term(&tx, id, &official, &start, &end)?;
+        }
+        Some((term_id, _)) => {
+            write_official(&tx, id, &official, &start, &end)?;
+            tx.execute(
+                "UPDATE official_terms SET role = ?1, section = ?2, term_start = ?3, term_end = ?4 WHERE id = ?5",
+                params![official.role, official.section, start, end, term_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        None => {
+            tx.execute(
+                "UPDATE officials SET name = ?1, image = ?2, age = ?3, contact = ?4, zone = ?5 WHERE id = ?6",
+                params![official.name, official.image, official.age, official.contact, official.zone, id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+// Officials are never removed outright; their term ends today so past councils,
+// attendance and signatures still resolve to them
 #[tauri::command]
 pub fn delete_official_command(id: i32) -> Result<(), String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
-    conn.execute("DELETE FROM officials WHERE id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
+    if let Some((term_id, _)) = open_term(&conn, id)? {
+        end_term(&conn, term_id, today(), "Removed from the roster")?;
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Ends the official's current term on `ended_on` (today by default)
+#[tauri::command]
+pub fn end_official_term_command(official_id: i32, ended_on: Option<String>, reason: Option<String>) -> Result<OfficialTerm, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let (term_id, term_start) = open_term(&conn, official_id)?
+        .ok_or_else(|| format!("Official {} is not serving a term.", official_id))?;
+    let ended_on = date_or_today(ended_on.as_deref())?;
+    if term_start.as_deref().and_then(local_date).is_some_and(|start| ended_on < start) {
+        return Err("A term cannot end before it started.".into());
+    }
+    let reason = reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .unwrap_or("End of term");
+    end_term(&conn, term_id, ended_on, reason)?;
+
+    fetch_terms(&conn, "t.id = ?1", &[&term_id])?
+        .pop()
+        .ok_or_else(|| format!("Term {} does not exist.", term_id))
+}
+
+// Everyone in office on a date (today by default), the captain first
+#[tauri::command]
+pub fn fetch_council_command(date: Option<String>) -> Result<Vec<OfficialTerm>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let date = date_or_today(date.as_deref())?;
+
+    fetch_terms(
+        &conn,
+        &format!("{} ORDER BY t.role = 'Barangay Captain' DESC, t.role, o.name", IN_OFFICE),
+        &[&date.to_string()],
+    )
+}
+
+// Every term served, newest first; one official's when `official_id` is given
+#[tauri::command]
+pub fn fetch_official_terms_command(official_id: Option<i32>) -> Result<Vec<OfficialTerm>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    fetch_terms(
+        &conn,
+        "?1 IS NULL OR t.official_id = ?1 ORDER BY t.term_start DESC, t.id DESC",
+        &[&official_id],
+    )
+}
+
+// Terms still being served whose scheduled end is within `within_days` (90 by
+// default), including those already past it
+#[tauri::command]
+pub fn fetch_expiring_terms_command(within_days: Option<u64>) -> Result<Vec<OfficialTerm>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let until = today()
+        .checked_add_days(Days::new(within_days.unwrap_or(90)))
+        .ok_or("The number of days is out of range.")?;
+
+    fetch_terms(
+        &conn,
+        "t.ended_on IS NULL AND t.term_end IS NOT NULL AND t.term_end <= ?1 ORDER BY t.term_end, o.name",
+        &[&until.to_string()],
+    )
+}
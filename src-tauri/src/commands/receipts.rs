@@ -0,0 +1,341 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::database::connection::establish_connection;
+use crate::models::receipt::{CancelledReceipt, DuplicateReceipt, OrBooklet, OrBookletReport};
+
+const BOOKLET_COLUMNS: &str = "id, series_start, series_end, collecting_officer, issued_date, active";
+
+fn booklet_from_row(row: &Row) -> rusqlite::Result<OrBooklet> {
+    Ok(OrBooklet {
+        id: row.get(0)?,
+        series_start: row.get(1)?,
+        series_end: row.get(2)?,
+        collecting_officer: row.get(3)?,
+        issued_date: row.get(4)?,
+        active: row.get(5)?,
+        next_number: None,
+    })
+}
+
+// (income id, OR number) of receipts issued within a range. Reversal rows carry the
+// number of the income they reverse and are negative, so they don't count as issued.
+fn issued_receipts(conn: &Connection, start: i32, end: i32) -> rusqlite::Result<Vec<(i32, i32)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, or_number FROM incomes WHERE or_number BETWEEN ?1 AND ?2 AND amount >= 0 ORDER BY or_number, id",
+    )?;
+    let rows = stmt.query_map(params![start, end], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+fn cancelled_receipts(conn: &Connection, booklet_id: i32) -> rusqlite::Result<Vec<CancelledReceipt>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, booklet_id, or_number, reason, cancelled_at FROM cancelled_receipts WHERE booklet_id = ?1 ORDER BY or_number",
+    )?;
+    let rows = stmt.query_map(params![booklet_id], |row| {
+        Ok(CancelledReceipt {
+            id: row.get(0)?,
+            booklet_id: row.get(1)?,
+            or_number: row.get(2)?,
+            reason: row.get(3)?,
+            cancelled_at: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+// Receipts are used in order, so the next one follows the highest number issued or
+// cancelled so far. Anything lower that is still free shows up as skipped.
+fn next_number(conn: &Connection, booklet: &OrBooklet) -> rusqlite::Result<Option<i32>> {
+    let last_used: Option<i32> = conn.query_row(
+        "SELECT MAX(n) FROM (
+            SELECT or_number AS n FROM incomes WHERE or_number BETWEEN ?1 AND ?2 AND amount >= 0
+            UNION ALL
+            SELECT or_number AS n FROM cancelled_receipts WHERE booklet_id = ?3
+        )",
+        params![booklet.series_start, booklet.series_end, booklet.id],
+        |row| row.get(0),
+    )?;
+    let next = last_used.map_or(booklet.series_start, |last| last + 1);
+    Ok(Some(next).filter(|next| *next <= booklet.series_end))
+}
+
+fn booklet_containing(conn: &Connection, or_number: i32) -> rusqlite::Result<Option<OrBooklet>> {
+    conn.query_row(
+        &format!("SELECT {} FROM or_booklets WHERE ?1 BETWEEN series_start AND series_end", BOOKLET_COLUMNS),
+        params![or_number],
+        booklet_from_row,
+    )
+    .optional()
+}
+
+// Settles the OR number an income is recorded under. A blank (0) number takes the next
+// receipt from the collector's active booklet; a typed number must not be in use or
+// cancelled. Returns the number with its booklet; a collector with no receipts left in an
+// active booklet has to type the number in.
+pub fn assign_or_number(
+    conn: &Connection,
+    requested: i32,
+    collector: &str,
+    income_id: Option<i32>,
+) -> Result<(i32, Option<i32>), String> {
+    if requested > 0 {
+        let used_by: Option<i32> = conn
+            .query_row(
+                "SELECT id FROM incomes WHERE or_number = ?1 AND amount >= 0 AND (?2 IS NULL OR id != ?2) LIMIT 1",
+                params![requested, income_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if let Some(other) = used_by {
+            return Err(format!("OR No. {} is already used by income #{}.", requested, other));
+        }
+
+        let booklet = booklet_containing(conn, requested).map_err(|e| e.to_string())?;
+        if let Some(booklet) = &booklet {
+            let cancelled: Option<String> = conn
+                .query_row(
+                    "SELECT reason FROM cancelled_receipts WHERE booklet_id = ?1 AND or_number = ?2",
+                    params![booklet.id, requested],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if let Some(reason) = cancelled {
+                return Err(format!("OR No. {} was cancelled ({}) and cannot be issued.", requested, reason));
+            }
+        }
+        return Ok((requested, booklet.and_then(|booklet| booklet.id)));
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM or_booklets WHERE active = 1 AND collecting_officer = ?1 COLLATE NOCASE ORDER BY series_start",
+            BOOKLET_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let booklets = stmt
+        .query_map(params![collector.trim()], booklet_from_row)
+        .map_err(|e| e.to_string())?;
+    for booklet in booklets {
+        let booklet = booklet.map_err(|e| e.to_string())?;
+        if let Some(next) = next_number(conn, &booklet).map_err(|e| e.to_string())? {
+            return Ok((next, booklet.id));
+        }
+    }
+
+    Err(format!(
+        "{} has no active OR booklet with receipts left. Register a booklet or enter the OR number.",
+        if collector.trim().is_empty() { "The collector" } else { collector.trim() }
+    ))
+}
+
+fn validate(conn: &Connection, booklet: &OrBooklet) -> Result<(), String> {
+    if booklet.collecting_officer.trim().is_empty() {
+        return Err("A collecting officer is required.".into());
+    }
+    if booklet.series_start <= 0 || booklet.series_end < booklet.series_start {
+        return Err("The receipt series must run from a positive number up to an equal or higher one.".into());
+    }
+
+    let overlapping: Option<(i32, i32)> = conn
+        .query_row(
+            "SELECT series_start, series_end FROM or_booklets
+             WHERE series_start <= ?2 AND series_end >= ?1 AND (?3 IS NULL OR id != ?3) LIMIT 1",
+            params![booklet.series_start, booklet.series_end, booklet.id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some((start, end)) = overlapping {
+        return Err(format!("The series overlaps the booklet for OR Nos. {}-{}.", start, end));
+    }
+
+    // a resized booklet still has to cover every receipt cancelled against it
+    if let Some(id) = booklet.id {
+        let outside: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM cancelled_receipts WHERE booklet_id = ?1 AND or_number NOT BETWEEN ?2 AND ?3",
+                params![id, booklet.series_start, booklet.series_end],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if outside > 0 {
+            return Err("Cancelled receipts of this booklet fall outside the new series.".into());
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn fetch_or_booklets_command() -> Result<Vec<OrBooklet>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM or_booklets ORDER BY series_start", BOOKLET_COLUMNS))
+        .map_err(|e| e.to_string())?;
+
+    let booklet_iter = stmt
+        .query_map([], booklet_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut booklets = Vec::new();
+    for booklet in booklet_iter {
+        let mut booklet = booklet.map_err(|e| e.to_string())?;
+        booklet.next_number = next_number(&conn, &booklet).map_err(|e| e.to_string())?;
+        booklets.push(booklet);
+    }
+
+    Ok(booklets)
+}
+
+#[tauri::command]
+pub fn save_or_booklet_command(booklet: OrBooklet) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    validate(&conn, &booklet)?;
+
+    if let Some(id) = booklet.id {
+        conn.execute(
+            "UPDATE or_booklets SET series_start = ?1, series_end = ?2, collecting_officer = ?3, issued_date = ?4, active = ?5
+             WHERE id = ?6",
+            params![
+                booklet.series_start,
+                booklet.series_end,
+                booklet.collecting_officer.trim(),
+                booklet.issued_date,
+                booklet.active,
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    } else {
+        conn.execute(
+            "INSERT INTO or_booklets (series_start, series_end, collecting_officer, issued_date, active)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                booklet.series_start,
+                booklet.series_end,
+                booklet.collecting_officer.trim(),
+                booklet.issued_date,
+                booklet.active
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+// Only booklets nothing was issued or cancelled from can be removed
+#[tauri::command]
+pub fn delete_or_booklet_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let (start, end): (i32, i32) = conn
+        .query_row(
+            "SELECT series_start, series_end FROM or_booklets WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let issued = issued_receipts(&conn, start, end).map_err(|e| e.to_string())?;
+    let cancelled = cancelled_receipts(&conn, id).map_err(|e| e.to_string())?;
+    if !issued.is_empty() || !cancelled.is_empty() {
+        return Err("Receipts from this booklet have already been used. Mark it inactive instead.".into());
+    }
+
+    conn.execute("DELETE FROM or_booklets WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Records a spoiled receipt so the number is accounted for instead of showing as skipped
+#[tauri::command]
+pub fn cancel_or_number_command(or_number: i32, reason: String) -> Result<(), String> {
+    if reason.trim().is_empty() {
+        return Err("A reason is required to cancel a receipt.".into());
+    }
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let booklet = booklet_containing(&conn, or_number)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("OR No. {} is not part of any registered booklet.", or_number))?;
+    let used_by: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM incomes WHERE or_number = ?1 AND amount >= 0 LIMIT 1",
+            params![or_number],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some(income_id) = used_by {
+        return Err(format!("OR No. {} was issued for income #{}. Delete or renumber that income first.", or_number, income_id));
+    }
+
+    conn.execute(
+        "INSERT INTO cancelled_receipts (booklet_id, or_number, reason) VALUES (?1, ?2, ?3)",
+        params![booklet.id, or_number, reason.trim()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Accountability of each booklet: receipts issued, still unused, skipped, issued
+// more than once and cancelled. Pass an id to report on a single booklet.
+#[tauri::command]
+pub fn fetch_or_booklet_report_command(booklet_id: Option<i32>) -> Result<Vec<OrBookletReport>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM or_booklets WHERE ?1 IS NULL OR id = ?1 ORDER BY series_start",
+            BOOKLET_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let booklets = stmt
+        .query_map(params![booklet_id], booklet_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<OrBooklet>>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut reports = Vec::new();
+    for mut booklet in booklets {
+        let id = booklet.id.unwrap_or_default();
+        booklet.next_number = next_number(&conn, &booklet).map_err(|e| e.to_string())?;
+
+        let mut by_number: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+        for (income_id, or_number) in issued_receipts(&conn, booklet.series_start, booklet.series_end)
+            .map_err(|e| e.to_string())?
+        {
+            by_number.entry(or_number).or_default().push(income_id);
+        }
+        let cancelled = cancelled_receipts(&conn, id).map_err(|e| e.to_string())?;
+        let cancelled_numbers: BTreeSet<i32> = cancelled.iter().map(|receipt| receipt.or_number).collect();
+
+        let next = booklet.next_number.unwrap_or(booklet.series_end + 1);
+        let skipped = (booklet.series_start..next)
+            .filter(|n| !by_number.contains_key(n) && !cancelled_numbers.contains(n))
+            .collect();
+        let duplicates = by_number
+            .iter()
+            .filter(|(_, income_ids)| income_ids.len() > 1)
+            .map(|(or_number, income_ids)| DuplicateReceipt { or_number: *or_number, income_ids: income_ids.clone() })
+            .collect();
+
+        reports.push(OrBookletReport {
+            issued: by_number.keys().copied().collect(),
+            unused: (next..=booklet.series_end).collect(),
+            skipped,
+            duplicates,
+            cancelled,
+            booklet,
+        });
+    }
+
+    Ok(reports)
+}
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::pdf::reports::REPORTS;
+use crate::pdf::write_report;
+
+#[tauri::command]
+pub fn fetch_report_types_command() -> Vec<String> {
+    REPORTS.iter().map(|report| report.to_string()).collect()
+}
+
+// Writes the PDF (or an XLSX workbook for the financial statements) to
+// `output_path` and returns the path it was saved to
+#[tauri::command]
+pub fn render_report_command(report: String, params: Option<serde_json::Value>, output_path: String) -> Result<String, String> {
+    let output = PathBuf::from(output_path.trim());
+    if output.as_os_str().is_empty() {
+        return Err("Choose where to save the report.".into());
+    }
+
+    write_report(&report, params, &output)?;
+
+    Ok(output.to_string_lossy().into_owned())
+}
@@ -1,14 +1,59 @@
 use crate::database::connection::establish_connection;
 use crate::models::settings::Settings;
+use chrono::NaiveTime;
 use rusqlite::params;
 
+// Regular office hours: morning in/out, afternoon in/out
+pub const DEFAULT_SCHEDULE: [&str; 4] = ["08:00", "12:00", "13:00", "17:00"];
+
+// Schedule times must be HH:MM and run in order through the day
+fn validate_schedule(settings: &Settings) -> Result<(), String> {
+    let times = [
+        &settings.office_am_in,
+        &settings.office_am_out,
+        &settings.office_pm_in,
+        &settings.office_pm_out,
+    ];
+    if times.iter().all(|time| time.is_none()) {
+        return Ok(());
+    }
+
+    let mut previous: Option<NaiveTime> = None;
+    for (time, default) in times.into_iter().zip(DEFAULT_SCHEDULE) {
+        let value = time.as_deref().unwrap_or(default);
+        let parsed = NaiveTime::parse_from_str(value.trim(), "%H:%M")
+            .map_err(|_| format!("Office hours must be written as HH:MM, not '{}'.", value))?;
+        if previous.is_some_and(|previous| parsed <= previous) {
+            return Err("Office hours must run in order: morning in, morning out, afternoon in, afternoon out.".into());
+        }
+        previous = Some(parsed);
+    }
+    if settings.grace_minutes.is_some_and(|minutes| !(0..=120).contains(&minutes)) {
+        return Err("The grace period must be between 0 and 120 minutes.".into());
+    }
+
+    Ok(())
+}
+
+// Policies are either "warn" (flag and continue) or "block" (refuse)
+fn validate_policy(label: &str, policy: &Option<String>) -> Result<(), String> {
+    match policy.as_deref() {
+        None | Some("warn") | Some("block") => Ok(()),
+        Some(other) => Err(format!("The {} policy must be 'warn' or 'block', not '{}'.", label, other)),
+    }
+}
+
 #[tauri::command]
 pub fn fetch_settings_command() -> Result<Settings, String> {
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     // Try fetching the settings
     let mut stmt = conn
-        .prepare("SELECT id, barangay, municipality, province, phone_number, email, logo, logo_municipality FROM settings WHERE id = 1")
+        .prepare(
+            "SELECT id, barangay, municipality, province, phone_number, email, logo, logo_municipality, clearance_blotter_policy, budget_overspend_policy,
+                 office_am_in, office_am_out, office_pm_in, office_pm_out, grace_minutes
+                 FROM settings WHERE id = 1",
+        )
         .map_err(|e| e.to_string())?;
 
     let result = stmt.query_row([], |row| {
@@ -21,6 +66,13 @@ pub fn fetch_settings_command() -> Result<Settings, String> {
             email: row.get(5)?,
             logo: row.get(6)?,
             logo_municipality: row.get(7)?,
+            clearance_blotter_policy: row.get(8)?,
+            budget_overspend_policy: row.get(9)?,
+            office_am_in: row.get(10)?,
+            office_am_out: row.get(11)?,
+            office_pm_in: row.get(12)?,
+            office_pm_out: row.get(13)?,
+            grace_minutes: row.get(14)?,
         })
     });
 
@@ -42,6 +94,13 @@ pub fn fetch_settings_command() -> Result<Settings, String> {
                 email: "".to_string(),
                 logo: Some("".to_string()),
                 logo_municipality: Some("".to_string()),
+                clearance_blotter_policy: Some("warn".to_string()),
+                budget_overspend_policy: Some("warn".to_string()),
+                office_am_in: Some(DEFAULT_SCHEDULE[0].to_string()),
+                office_am_out: Some(DEFAULT_SCHEDULE[1].to_string()),
+                office_pm_in: Some(DEFAULT_SCHEDULE[2].to_string()),
+                office_pm_out: Some(DEFAULT_SCHEDULE[3].to_string()),
+                grace_minutes: Some(0),
             })
         }
     }
@@ -49,11 +108,19 @@ pub fn fetch_settings_command() -> Result<Settings, String> {
 
 #[tauri::command]
 pub fn save_settings_command(settings: Settings) -> Result<(), String> {
+    validate_schedule(&settings)?;
+    validate_policy("clearance blotter", &settings.clearance_blotter_policy)?;
+    validate_policy("budget overspend", &settings.budget_overspend_policy)?;
     let conn = establish_connection().map_err(|e| e.to_string())?;
 
     if settings.id.is_some() {
         conn.execute(
-            "UPDATE settings SET barangay = ?1, municipality = ?2, province = ?3, phone_number = ?4, email = ?5, logo = ?6, logo_municipality = ?7 WHERE id = ?8",
+            "UPDATE settings SET barangay = ?1, municipality = ?2, province = ?3, phone_number = ?4, email = ?5, logo = ?6, logo_municipality = ?7,
+                clearance_blotter_policy = COALESCE(?8, clearance_blotter_policy),
+                budget_overspend_policy = COALESCE(?9, budget_overspend_policy),
+                office_am_in = COALESCE(?10, office_am_in), office_am_out = COALESCE(?11, office_am_out),
+                office_pm_in = COALESCE(?12, office_pm_in), office_pm_out = COALESCE(?13, office_pm_out),
+                grace_minutes = COALESCE(?14, grace_minutes) WHERE id = ?15",
             params![
                 settings.barangay,
                 settings.municipality,
@@ -62,6 +129,13 @@ pub fn save_settings_command(settings: Settings) -> Result<(), String> {
                 settings.email,
                 settings.logo,
                 settings.logo_municipality,
+                settings.clearance_blotter_policy,
+                settings.budget_overspend_policy,
+                settings.office_am_in,
+                settings.office_am_out,
+                settings.office_pm_in,
+                settings.office_pm_out,
+                settings.grace_minutes,
                 settings.id
                 
             ],
@@ -69,7 +143,8 @@ pub fn save_settings_command(settings: Settings) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
     } else {
         conn.execute(
-            "INSERT INTO settings (barangay, municipality, province, phone_number, email, logo, logo_municipality) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO settings (barangay, municipality, province, phone_number, email, logo, logo_municipality, clearance_blotter_policy, budget_overspend_policy)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, COALESCE(?8, 'warn'), COALESCE(?9, 'warn'))",
             params![
                 settings.barangay,
                 settings.municipality,
@@ -77,7 +152,9 @@ pub fn save_settings_command(settings: Settings) -> Result<(), String> {
                 settings.phone_number,
                 settings.email,
                 settings.logo,
-                settings.logo_municipality
+                settings.logo_municipality,
+                settings.clearance_blotter_policy,
+                settings.budget_overspend_policy
             ],
         )
         .map_err(|e| e.to_string())?;
@@ -0,0 +1,276 @@
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::commands::officials::{date_or_today, holder_on};
+use crate::database::connection::establish_connection;
+use crate::models::signatory::{Signatory, Signer};
+use crate::pdf::document::{decode_image, local_date};
+
+// Documents whose signature blocks are configured here
+pub const SIGNED_DOCUMENTS: &[&str] = &[
+    "certificate",
+    "disbursement_voucher",
+    "daily_time_record",
+    "cashbook",
+    "receipts_expenditures",
+    "collections_deposits",
+    "event_attendance",
+];
+
+// The voucher's boxes follow the COA form, so blocks can be edited but not added
+const FIXED_LAYOUT: &[&str] = &["disbursement_voucher"];
+
+const SIGNATORY_QUERY: &str = "SELECT s.id, s.document_type, s.slot, s.label, s.role, s.title, s.sort_order, s.built_in,
+            s.oic_official_id, o.name, s.oic_from, s.oic_to
+     FROM signatories s
+     LEFT JOIN officials o ON o.id = s.oic_official_id";
+
+fn signatory_from_row(row: &Row) -> rusqlite::Result<Signatory> {
+    Ok(Signatory {
+        id: row.get(0)?,
+        document_type: row.get(1)?,
+        slot: row.get(2)?,
+        label: row.get(3)?,
+        role: row.get(4)?,
+        title: row.get(5)?,
+        sort_order: row.get(6)?,
+        built_in: row.get(7)?,
+        oic_official_id: row.get(8)?,
+        oic_official_name: row.get(9)?,
+        oic_from: row.get(10)?,
+        oic_to: row.get(11)?,
+    })
+}
+
+fn fetch_signatories(conn: &Connection, document_type: Option<&str>) -> Result<Vec<Signatory>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 IS NULL OR s.document_type = ?1 ORDER BY s.document_type, s.sort_order, s.id",
+            SIGNATORY_QUERY
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![document_type], signatory_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut signatories = Vec::new();
+    for signatory in rows {
+        signatories.push(signatory.map_err(|e| e.to_string())?);
+    }
+    Ok(signatories)
+}
+
+pub fn signature_image(conn: &Connection, official_id: i32) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT image FROM signature_images WHERE official_id = ?1",
+        params![official_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn official_name(conn: &Connection, official_id: i32) -> Result<String, String> {
+    conn.query_row("SELECT name FROM officials WHERE id = ?1", params![official_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Official {} does not exist.", official_id))
+}
+
+// Whether the officer-in-charge designation covers `on`
+fn oic_on(signatory: &Signatory, on: NaiveDate) -> Option<i32> {
+    let bound = |value: &Option<String>| value.as_deref().and_then(local_date);
+    let official_id = signatory.oic_official_id?;
+    let started = bound(&signatory.oic_from).is_none_or(|from| from <= on);
+    let running = bound(&signatory.oic_to).is_none_or(|to| on <= to);
+    (started && running).then_some(official_id)
+}
+
+// The signature blocks of a document dated `on`. Each is signed by the officer-in-charge
+// designated for that date, or else whoever held the role. `recorded` holds signers
+// already on file for the document, as (slot, official id, name), and takes precedence.
+pub fn signers(conn: &Connection, document_type: &str, on: NaiveDate, recorded: &[(&str, i32, &str)]) -> Result<Vec<Signer>, String> {
+    let mut signers = Vec::new();
+    for signatory in fetch_signatories(conn, Some(document_type))? {
+        let mut signer = Signer {
+            slot: signatory.slot.clone(),
+            label: signatory.label.clone(),
+            position: signatory.title.clone(),
+            ..Default::default()
+        };
+        let signed_by = match recorded.iter().find(|(slot, _, _)| *slot == signatory.slot) {
+            Some((_, official_id, name)) => Some((*official_id, name.to_string())),
+            None => match oic_on(&signatory, on) {
+                Some(official_id) => {
+                    signer.oic = true;
+                    signer.position = format!("OIC, for and in the absence of the {}", signatory.title);
+                    Some((official_id, official_name(conn, official_id)?))
+                }
+                None => holder_on(conn, &signatory.role, on)?,
+            },
+        };
+        if let Some((official_id, name)) = signed_by {
+            signer.name = name;
+            signer.official_id = Some(official_id);
+            signer.signature = signature_image(conn, official_id)?;
+        }
+        signers.push(signer);
+    }
+    Ok(signers)
+}
+
+fn slug(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[tauri::command]
+pub fn fetch_signatories_command(document_type: Option<String>) -> Result<Vec<Signatory>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    fetch_signatories(&conn, document_type.as_deref())
+}
+
+// Signature blocks as they would print on a document dated `date` (today by default)
+#[tauri::command]
+pub fn fetch_document_signers_command(document_type: String, date: Option<String>) -> Result<Vec<Signer>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    if !SIGNED_DOCUMENTS.contains(&document_type.as_str()) {
+        return Err(format!("'{}' has no signatories.", document_type));
+    }
+    let date = date_or_today(date.as_deref())?;
+    signers(&conn, &document_type, date, &[])
+}
+
+#[tauri::command]
+pub fn save_signatory_command(signatory: Signatory) -> Result<i32, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let role = signatory.role.trim();
+    let title = signatory.title.trim();
+    if role.is_empty() {
+        return Err("Choose the role that signs this block.".into());
+    }
+    if title.is_empty() {
+        return Err("A position title is required, e.g. 'Punong Barangay'.".into());
+    }
+
+    let date = |value: &Option<String>, label: &str| -> Result<Option<String>, String> {
+        match value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(value) => local_date(value)
+                .map(|date| Some(date.to_string()))
+                .ok_or_else(|| format!("The {} '{}' is not a valid date.", label, value)),
+            None => Ok(None),
+        }
+    };
+    let (oic_from, oic_to) = match signatory.oic_official_id {
+        Some(official_id) => {
+            official_name(&conn, official_id)?;
+            (date(&signatory.oic_from, "start of the OIC designation")?, date(&signatory.oic_to, "end of the OIC designation")?)
+        }
+        None => (None, None),
+    };
+    if let (Some(from), Some(to)) = (&oic_from, &oic_to) {
+        if to < from {
+            return Err("The OIC designation must end after it starts.".into());
+        }
+    }
+
+    if let Some(id) = signatory.id {
+        // the document and slot stay as they are, since the renderer looks them up
+        let updated = conn
+            .execute(
+                "UPDATE signatories SET label = ?1, role = ?2, title = ?3, sort_order = ?4,
+                     oic_official_id = ?5, oic_from = ?6, oic_to = ?7
+                 WHERE id = ?8",
+                params![signatory.label.trim(), role, title, signatory.sort_order, signatory.oic_official_id, oic_from, oic_to, id],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            return Err(format!("Signatory {} does not exist.", id));
+        }
+        return Ok(id);
+    }
+
+    let document_type = signatory.document_type.as_str();
+    if !SIGNED_DOCUMENTS.contains(&document_type) {
+        return Err(format!("'{}' has no signatories.", document_type));
+    }
+    if FIXED_LAYOUT.contains(&document_type) {
+        return Err("The signature boxes on this document are fixed; edit the existing ones instead.".into());
+    }
+    let slot = slug(title);
+    let taken: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM signatories WHERE document_type = ?1 AND slot = ?2)",
+            params![document_type, slot],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if taken {
+        return Err(format!("This document already has a '{}' signatory.", title));
+    }
+
+    conn.execute(
+        "INSERT INTO signatories (document_type, slot, label, role, title, sort_order, oic_official_id, oic_from, oic_to)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![document_type, slot, signatory.label.trim(), role, title, signatory.sort_order, signatory.oic_official_id, oic_from, oic_to],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+// Built-in blocks are part of the document's layout and can only be edited
+#[tauri::command]
+pub fn delete_signatory_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let built_in: Option<bool> = conn
+        .query_row("SELECT built_in FROM signatories WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if built_in == Some(true) {
+        return Err("This signature block is part of the document and cannot be removed.".into());
+    }
+    conn.execute("DELETE FROM signatories WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn fetch_signature_image_command(official_id: i32) -> Result<Option<String>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    signature_image(&conn, official_id)
+}
+
+// Stores an official's captured signature, replacing any earlier one
+#[tauri::command]
+pub fn save_signature_image_command(official_id: i32, image: String) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    official_name(&conn, official_id)?;
+    if decode_image(&image).is_none() {
+        return Err("The signature image could not be read.".into());
+    }
+
+    conn.execute(
+        "INSERT INTO signature_images (official_id, image) VALUES (?1, ?2)
+         ON CONFLICT (official_id) DO UPDATE SET image = excluded.image, captured_at = CURRENT_TIMESTAMP",
+        params![official_id, image.trim()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_signature_image_command(official_id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM signature_images WHERE official_id = ?1", params![official_id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::database::connection::establish_connection;
+use crate::models::venue::Venue;
+
+#[tauri::command]
+pub fn fetch_venues_command() -> Result<Vec<Venue>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, name, address, capacity FROM venues WHERE active = 1 ORDER BY name COLLATE NOCASE")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(Venue {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                address: row.get(2)?,
+                capacity: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut venues = Vec::new();
+    for venue in rows {
+        venues.push(venue.map_err(|e| e.to_string())?);
+    }
+    Ok(venues)
+}
+
+// Renaming a venue renames it on the events booked there
+#[tauri::command]
+pub fn save_venue_command(venue: Venue) -> Result<i32, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let name = venue.name.trim();
+    if name.is_empty() {
+        return Err("A name is required for the venue.".into());
+    }
+    if venue.capacity.is_some_and(|capacity| capacity <= 0) {
+        return Err("The capacity must be a positive number of people.".into());
+    }
+    let taken: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM venues WHERE name = ?1 AND id IS NOT ?2",
+            params![name, venue.id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if taken.is_some() {
+        return Err(format!("A venue named '{}' already exists.", name));
+    }
+    let address = venue.address.as_deref().map(str::trim).filter(|address| !address.is_empty());
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = match venue.id {
+        Some(id) => {
+            tx.execute(
+                "UPDATE venues SET name = ?1, address = ?2, capacity = ?3, active = 1 WHERE id = ?4",
+                params![name, address, venue.capacity, id],
+            )
+            .map_err(|e| e.to_string())?;
+            tx.execute("UPDATE events SET venue = ?1 WHERE venue_id = ?2", params![name, id])
+                .map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            tx.execute(
+                "INSERT INTO venues (name, address, capacity) VALUES (?1, ?2, ?3)",
+                params![name, address, venue.capacity],
+            )
+            .map_err(|e| e.to_string())?;
+            tx.last_insert_rowid() as i32
+        }
+    };
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
+
+// Venues with bookings on file are retired rather than deleted, and take no new ones
+#[tauri::command]
+pub fn delete_venue_command(id: i32) -> Result<(), String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let used: bool = conn
+        .query_row("SELECT EXISTS (SELECT 1 FROM events WHERE venue_id = ?1)", params![id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if used {
+        conn.execute("UPDATE venues SET active = 0 WHERE id = ?1", params![id])
+    } else {
+        conn.execute("DELETE FROM venues WHERE id = ?1", params![id])
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use std::io::Cursor;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chrono::{Local, NaiveDate};
+use hmac::{Hmac, Mac};
+use image::{DynamicImage, ImageBuffer, ImageOutputFormat, Luma};
+use qrcode::{Color, QrCode};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::Sha256;
+
+use crate::commands::certificates::{certificate_content_hash, certificate_from_row, CERTIFICATE_COLUMNS};
+use crate::database::connection::establish_connection;
+use crate::models::certificate::{Certificate, CertificateQr, CertificateVerification};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// QR payload layout: BMSCERT1|<control number>|<content hash>|<signature>
+const PAYLOAD_PREFIX: &str = "BMSCERT1";
+
+// The barangay key is created on first use and never leaves the database
+pub fn signing_key(conn: &Connection) -> Result<Vec<u8>, String> {
+    let existing: Option<String> = conn
+        .query_row("SELECT secret FROM signing_key WHERE id = 1", [], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let secret = match existing {
+        Some(secret) => secret,
+        None => {
+            let mut key = [0u8; 32];
+            getrandom::getrandom(&mut key).map_err(|e| e.to_string())?;
+            conn.execute(
+                "INSERT OR IGNORE INTO signing_key (id, secret) VALUES (1, ?1)",
+                params![STANDARD.encode(key)],
+            ).map_err(|e| e.to_string())?;
+
+            conn.query_row("SELECT secret FROM signing_key WHERE id = 1", [], |row| row.get(0))
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    STANDARD.decode(secret).map_err(|e| e.to_string())
+}
+
+fn mac(key: &[u8], control_number: &str, content_hash: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(control_number.as_bytes());
+    mac.update(b"|");
+    mac.update(content_hash.as_bytes());
+    mac
+}
+
+pub fn certificate_qr_payload(conn: &Connection, control_number: &str, content_hash: &str) -> Result<String, String> {
+    let key = signing_key(conn)?;
+    let signature = URL_SAFE_NO_PAD.encode(mac(&key, control_number, content_hash).finalize().into_bytes());
+
+    Ok(format!("{}|{}|{}|{}", PAYLOAD_PREFIX, control_number, content_hash, signature))
+}
+
+fn signature_valid(key: &[u8], control_number: &str, content_hash: &str, signature: &str) -> bool {
+    match URL_SAFE_NO_PAD.decode(signature) {
+        Ok(bytes) => mac(key, control_number, content_hash).verify_slice(&bytes).is_ok(),
+        Err(_) => false,
+    }
+}
+
+// Returns (control number, content hash, signature) for a scanned payload
+fn parse_payload(code: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = code.split('|');
+    if parts.next()? != PAYLOAD_PREFIX {
+        return None;
+    }
+    let parsed = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(parsed)
+}
+
+pub fn qr_png(payload: &str) -> Result<Vec<u8>, String> {
+    const SCALE: u32 = 8;
+    const QUIET_ZONE: u32 = 4;
+
+    let code = QrCode::new(payload.as_bytes()).map_err(|e| e.to_string())?;
+    let width = code.width() as u32;
+    let colors = code.to_colors();
+    let size = (width + QUIET_ZONE * 2) * SCALE;
+
+    let image = ImageBuffer::from_fn(size, size, |x, y| {
+        let (mx, my) = (x / SCALE, y / SCALE);
+        let dark = mx >= QUIET_ZONE
+            && my >= QUIET_ZONE
+            && mx < width + QUIET_ZONE
+            && my < width + QUIET_ZONE
+            && colors[((my - QUIET_ZONE) * width + (mx - QUIET_ZONE)) as usize] == Color::Dark;
+        Luma([if dark { 0u8 } else { 255u8 }])
+    });
+
+    let mut bytes = Vec::new();
+    DynamicImage::ImageLuma8(image)
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .map_err(|e| e.to_string())?;
+
+    Ok(bytes)
+}
+
+fn find_by_control_number(conn: &Connection, control_number: &str) -> Result<Option<Certificate>, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM certificates WHERE control_number = ?1 COLLATE NOCASE", CERTIFICATE_COLUMNS),
+        params![control_number],
+        certificate_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn fetch_certificate_qr_command(id: i32) -> Result<CertificateQr, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let cert = conn
+        .query_row(
+            &format!("SELECT {} FROM certificates WHERE id = ?1", CERTIFICATE_COLUMNS),
+            params![id],
+            certificate_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let (Some(control_number), Some(content_hash)) = (cert.control_number, cert.content_hash) else {
+        return Err("This certificate was saved before control numbers were introduced and cannot be verified.".into());
+    };
+
+    let payload = certificate_qr_payload(&conn, &control_number, &content_hash)?;
+    let image = format!("data:image/png;base64,{}", STANDARD.encode(qr_png(&payload)?));
+
+    Ok(CertificateQr {
+        control_number,
+        payload,
+        image,
+    })
+}
+
+fn verification(status: &str, message: String, certificate: Option<Certificate>) -> CertificateVerification {
+    CertificateVerification {
+        status: status.to_string(),
+        valid: status == "valid",
+        message,
+        certificate,
+    }
+}
+
+// Accepts either the scanned QR payload or a control number typed off the paper copy
+#[tauri::command]
+pub fn verify_certificate(code: String) -> Result<CertificateVerification, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let code = code.trim();
+    let (control_number, scanned) = match parse_payload(code) {
+        Some((control_number, hash, signature)) => (control_number, Some((hash, signature))),
+        None => (code, None),
+    };
+
+    let Some(cert) = find_by_control_number(&conn, control_number)? else {
+        return Ok(verification(
+            "not_found",
+            format!("No certificate with control number {} was issued by this barangay.", control_number),
+            None,
+        ));
+    };
+
+    let issued_number = cert.control_number.clone().unwrap_or_default();
+    let stored_hash = cert.content_hash.clone().unwrap_or_default();
+
+    if certificate_content_hash(&conn, &cert, &issued_number).map_err(|e| e.to_string())? != stored_hash {
+        return Ok(verification(
+            "altered",
+            "The issuance record has been modified since the certificate was issued.".into(),
+            Some(cert),
+        ));
+    }
+
+    if let Some((hash, signature)) = scanned {
+        let key = signing_key(&conn)?;
+        if !signature_valid(&key, control_number, hash, signature) {
+            return Ok(verification(
+                "altered",
+                "The QR code signature is invalid. It was not produced by this barangay or has been tampered with.".into(),
+                Some(cert),
+            ));
+        }
+        if hash != stored_hash {
+            return Ok(verification(
+                "altered",
+                "The QR code does not match the certificate on record.".into(),
+                Some(cert),
+            ));
+        }
+    }
+
+    if cert.voided {
+        let reason = cert.void_reason.clone().unwrap_or_default();
+        return Ok(verification(
+            "revoked",
+            format!("Certificate {} has been voided: {}", issued_number, reason),
+            Some(cert),
+        ));
+    }
+
+    let today = Local::now().date_naive();
+    let expired_on = cert
+        .valid_until
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .filter(|until| *until < today);
+    if let Some(until) = expired_on {
+        return Ok(verification(
+            "expired",
+            format!("Certificate {} is genuine but expired on {}.", issued_number, until.format("%B %-d, %Y")),
+            Some(cert),
+        ));
+    }
+
+    Ok(verification(
+        "valid",
+        format!("Certificate {} is genuine and in force.", issued_number),
+        Some(cert),
+    ))
+}
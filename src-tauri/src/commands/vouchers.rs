@@ -0,0 +1,422 @@
+use chrono::{Datelike, Local};
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+
+use crate::commands::budget::check_appropriation;
+use crate::commands::ledger::post_expense;
+use crate::commands::officials::{holder_on, IN_OFFICE};
+use crate::database::connection::establish_connection;
+use crate::models::expense::Expense;
+use crate::models::voucher::DisbursementVoucher;
+
+pub const TREASURER_ROLE: &str = "Treasurer";
+pub const CAPTAIN_ROLE: &str = "Barangay Captain";
+const PAYMENT_MODES: &[&str] = &["Cash", "Check"];
+
+
+const VOUCHER_COLUMNS: &str = "v.id, v.expense_id, v.dv_number, v.status, v.particulars, v.payment_mode, v.check_number, v.check_date, v.bank,
+     v.prepared_by, p.name, v.prepared_at, v.certified_by, c.name, v.certified_at, v.approved_by, a.name, v.approved_at,
+     v.paid_at, v.rejected_by, r.name, v.rejected_at, v.rejection_reason, e.paid_to, e.amount, e.category, e.date";
+
+const VOUCHER_JOINS: &str = "FROM disbursement_vouchers v
+     JOIN expenses e ON e.id = v.expense_id
+     LEFT JOIN officials p ON p.id = v.prepared_by
+     LEFT JOIN officials c ON c.id = v.certified_by
+     LEFT JOIN officials a ON a.id = v.approved_by
+     LEFT JOIN officials r ON r.id = v.rejected_by";
+
+fn voucher_from_row(row: &Row) -> rusqlite::Result<DisbursementVoucher> {
+    Ok(DisbursementVoucher {
+        id: row.get(0)?,
+        expense_id: row.get(1)?,
+        dv_number: row.get(2)?,
+        status: row.get(3)?,
+        particulars: row.get(4)?,
+        payment_mode: row.get(5)?,
+        check_number: row.get(6)?,
+        check_date: row.get(7)?,
+        bank: row.get(8)?,
+        prepared_by: row.get(9)?,
+        prepared_by_name: row.get(10)?,
+        prepared_at: row.get(11)?,
+        certified_by: row.get(12)?,
+        certified_by_name: row.get(13)?,
+        certified_at: row.get(14)?,
+        approved_by: row.get(15)?,
+        approved_by_name: row.get(16)?,
+        approved_at: row.get(17)?,
+        paid_at: row.get(18)?,
+        rejected_by: row.get(19)?,
+        rejected_by_name: row.get(20)?,
+        rejected_at: row.get(21)?,
+        rejection_reason: row.get(22)?,
+        payee: row.get(23)?,
+        amount: row.get(24)?,
+        category: row.get(25)?,
+        expense_date: row.get(26)?,
+    })
+}
+
+pub fn fetch_voucher(conn: &Connection, id: i32) -> Result<DisbursementVoucher, String> {
+    conn.query_row(
+        &format!("SELECT {} {} WHERE v.id = ?1", VOUCHER_COLUMNS, VOUCHER_JOINS),
+        params![id],
+        voucher_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Disbursement voucher {} does not exist.", id))
+}
+
+// SQL condition on the expenses row aliased `alias`: final once its voucher is
+// approved. Only expenses recorded before vouchers were required (requires_voucher = 0)
+// count as paid without one.
+pub fn final_expense(alias: &str) -> String {
+    format!(
+        "(EXISTS (SELECT 1 FROM disbursement_vouchers v WHERE v.expense_id = {0}.id AND v.status IN ('Approved', 'Paid'))
+          OR ({0}.requires_voucher = 0 AND NOT EXISTS (SELECT 1 FROM disbursement_vouchers v WHERE v.expense_id = {0}.id)))",
+        alias
+    )
+}
+
+// An expense is final, and so posted to the ledger, once its voucher is approved
+pub fn expense_is_final(conn: &Connection, expense_id: i64) -> rusqlite::Result<bool> {
+    conn.query_row(
+        &format!("SELECT {} FROM expenses x WHERE x.id = ?1", final_expense("x")),
+        params![expense_id],
+        |row| row.get(0),
+    )
+}
+
+// Voucher status of an expense, if it has one
+pub fn voucher_status(conn: &Connection, expense_id: i32) -> Result<Option<(String, String)>, String> {
+    conn.query_row(
+        "SELECT dv_number, status FROM disbursement_vouchers WHERE expense_id = ?1",
+        params![expense_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+// Name of the official signing, who must be in office as `role` today. A term
+// ended today, e.g. by removal from the roster, no longer signs.
+pub fn signatory(conn: &Connection, official_id: i32, role: &str) -> Result<String, String> {
+    let name: String = conn
+        .query_row("SELECT name FROM officials WHERE id = ?1", params![official_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Official {} does not exist.", official_id))?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT t.role FROM official_terms t
+             WHERE t.official_id = ?2 AND {} AND (t.ended_on IS NULL OR t.ended_on > ?1)
+             ORDER BY t.term_start DESC, t.id DESC",
+            IN_OFFICE
+        ))
+        .map_err(|e| e.to_string())?;
+    let held = stmt
+        .query_map(params![Local::now().date_naive().to_string(), official_id], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    if held.iter().any(|held| held.eq_ignore_ascii_case(role)) {
+        return Ok(name);
+    }
+    match held.first() {
+        Some(held) => Err(format!("{} is the {}; this step must be signed by the {}.", name, held, role)),
+        None => Err(format!("{} is no longer in office; this step must be signed by the {}.", name, role)),
+    }
+}
+
+fn official_in_role(conn: &Connection, role: &str) -> Result<i32, String> {
+    holder_on(conn, role, Local::now().date_naive())?
+        .map(|(id, _)| id)
+        .ok_or_else(|| format!("No official is recorded as {}.", role))
+}
+
+// DV numbers run per month, e.g. 2026-02-0007
+fn next_dv_number(conn: &Connection) -> Result<String, String> {
+    let today = Local::now().date_naive();
+    let prefix = format!("{}-{:02}-", today.year(), today.month());
+    let last: Option<String> = conn
+        .query_row(
+            "SELECT MAX(dv_number) FROM disbursement_vouchers WHERE dv_number LIKE ?1 || '%'",
+            params![prefix],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let sequence = last
+        .and_then(|number| number.rsplit('-').next().and_then(|n| n.parse::<u32>().ok()))
+        .unwrap_or(0);
+    Ok(format!("{}{:04}", prefix, sequence + 1))
+}
+
+fn validate_payment(voucher: &DisbursementVoucher) -> Result<(), String> {
+    if !PAYMENT_MODES.contains(&voucher.payment_mode.as_str()) {
+        return Err(format!("Payment mode must be one of: {}.", PAYMENT_MODES.join(", ")));
+    }
+    if voucher.payment_mode == "Check" && voucher.check_number.as_deref().is_none_or(|n| n.trim().is_empty()) {
+        return Err("A check number is required for check payments.".into());
+    }
+    Ok(())
+}
+
+// Moves a voucher from one of `from` to `to`, stamping who signed the step
+fn advance(conn: &Connection, id: i32, from: &[&str], to: &str, set: &str, values: &[&dyn rusqlite::ToSql]) -> Result<DisbursementVoucher, String> {
+    let voucher = fetch_voucher(conn, id)?;
+    let status = voucher.status.clone().unwrap_or_default();
+    if !from.contains(&status.as_str()) {
+        return Err(format!(
+            "Voucher {} is {}; it must be {} first.",
+            voucher.dv_number.unwrap_or_default(),
+            status,
+            from.join(" or ")
+        ));
+    }
+
+    let mut values = values.to_vec();
+    values.push(&to);
+    values.push(&id);
+    conn.execute(
+        &format!(
+            "UPDATE disbursement_vouchers SET {}, status = ?{} WHERE id = ?{}",
+            set,
+            values.len() - 1,
+            values.len()
+        ),
+        values.as_slice(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    post_expense(conn, voucher.expense_id as i64).map_err(|e| e.to_string())?;
+    fetch_voucher(conn, id)
+}
+
+#[tauri::command]
+pub fn fetch_disbursement_vouchers_command(status: Option<String>) -> Result<Vec<DisbursementVoucher>, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} {} WHERE ?1 IS NULL OR v.status = ?1 ORDER BY v.id DESC",
+            VOUCHER_COLUMNS, VOUCHER_JOINS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let voucher_iter = stmt
+        .query_map(params![status], voucher_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut vouchers = Vec::new();
+    for voucher in voucher_iter {
+        vouchers.push(voucher.map_err(|e| e.to_string())?);
+    }
+
+    Ok(vouchers)
+}
+
+// Creates the voucher for an expense, or revises one that is still being prepared
+// or was rejected. Either way it goes back to the Punong Barangay for certification.
+#[tauri::command]
+pub fn prepare_disbursement_voucher_command(voucher: DisbursementVoucher) -> Result<DisbursementVoucher, String> {
+    validate_payment(&voucher)?;
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let prepared_by = match voucher.prepared_by {
+        Some(id) => id,
+        None => official_in_role(&tx, TREASURER_ROLE)?,
+    };
+    signatory(&tx, prepared_by, TREASURER_ROLE)?;
+
+    let id = match voucher.id {
+        Some(id) => {
+            let existing = fetch_voucher(&tx, id)?;
+            let status = existing.status.unwrap_or_default();
+            if status != "Prepared" && status != "Rejected" {
+                return Err(format!(
+                    "Voucher {} is already {} and can no longer be revised.",
+                    existing.dv_number.unwrap_or_default(),
+                    status
+                ));
+            }
+            tx.execute(
+                "UPDATE disbursement_vouchers SET particulars = ?1, payment_mode = ?2, check_number = ?3, check_date = ?4, bank = ?5,
+                 status = 'Prepared', prepared_by = ?6, prepared_at = CURRENT_TIMESTAMP,
+                 rejected_by = NULL, rejected_at = NULL, rejection_reason = NULL
+                 WHERE id = ?7",
+                params![
+                    voucher.particulars,
+                    voucher.payment_mode,
+                    voucher.check_number,
+                    voucher.check_date,
+                    voucher.bank,
+                    prepared_by,
+                    id
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            let expense: Option<i32> = tx
+                .query_row("SELECT id FROM expenses WHERE id = ?1", params![voucher.expense_id], |row| row.get(0))
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if expense.is_none() {
+                return Err(format!("Expense {} does not exist.", voucher.expense_id));
+            }
+            if let Some((dv_number, _)) = voucher_status(&tx, voucher.expense_id)? {
+                return Err(format!("This expense already has voucher {}.", dv_number));
+            }
+
+            tx.execute(
+                "INSERT INTO disbursement_vouchers (expense_id, dv_number, particulars, payment_mode, check_number, check_date, bank, prepared_by, prepared_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, CURRENT_TIMESTAMP)",
+                params![
+                    voucher.expense_id,
+                    next_dv_number(&tx)?,
+                    voucher.particulars,
+                    voucher.payment_mode,
+                    voucher.check_number,
+                    voucher.check_date,
+                    voucher.bank,
+                    prepared_by
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            tx.last_insert_rowid() as i32
+        }
+    };
+
+    // pending again, so the expense leaves the books until approval
+    post_expense(&tx, voucher.expense_id as i64).map_err(|e| e.to_string())?;
+    let saved = fetch_voucher(&tx, id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(saved)
+}
+
+#[tauri::command]
+pub fn certify_disbursement_voucher_command(id: i32, official_id: i32) -> Result<DisbursementVoucher, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    signatory(&tx, official_id, CAPTAIN_ROLE)?;
+    let voucher = advance(
+        &tx,
+        id,
+        &["Prepared"],
+        "Certified",
+        "certified_by = ?1, certified_at = CURRENT_TIMESTAMP",
+        &[&official_id],
+    )?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(voucher)
+}
+
+fn voucher_expense(conn: &Connection, id: i32) -> Result<Expense, String> {
+    conn.query_row(
+        "SELECT e.id, e.type_, e.amount, e.or_number, e.paid_to, e.paid_by, e.category, e.date, e.cash_advance_id, e.requires_voucher,
+             v.payment_mode
+         FROM disbursement_vouchers v JOIN expenses e ON e.id = v.expense_id WHERE v.id = ?1",
+        params![id],
+        |row| {
+            Ok(Expense {
+                id: row.get(0)?,
+                type_: row.get(1)?,
+                amount: row.get(2)?,
+                or_number: row.get(3)?,
+                paid_to: row.get(4)?,
+                paid_by: row.get(5)?,
+                category: row.get(6)?,
+                date: row.get(7)?,
+                voucher_status: None,
+                payment_mode: row.get(10)?,
+                cash_advance_id: row.get(8)?,
+                requires_voucher: row.get(9)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Disbursement voucher {} does not exist.", id))
+}
+
+// Approval makes the expense final and posts it to the ledger
+#[tauri::command]
+pub fn approve_disbursement_voucher_command(id: i32, official_id: i32) -> Result<DisbursementVoucher, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(|e| e.to_string())?;
+
+    signatory(&tx, official_id, CAPTAIN_ROLE)?;
+    // Pending expenses were each checked against the appropriation on their own;
+    // checked again now that others may have been approved first
+    check_appropriation(&tx, &voucher_expense(&tx, id)?)?;
+    let voucher = advance(
+        &tx,
+        id,
+        &["Certified"],
+        "Approved",
+        "approved_by = ?1, approved_at = CURRENT_TIMESTAMP",
+        &[&official_id],
+    )?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(voucher)
+}
+
+#[tauri::command]
+pub fn reject_disbursement_voucher_command(id: i32, official_id: i32, reason: String) -> Result<DisbursementVoucher, String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err("A reason is required to reject a voucher.".into());
+    }
+
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    signatory(&tx, official_id, CAPTAIN_ROLE)?;
+    let voucher = advance(
+        &tx,
+        id,
+        &["Prepared", "Certified"],
+        "Rejected",
+        "rejected_by = ?1, rejected_at = CURRENT_TIMESTAMP, rejection_reason = ?2, certified_by = NULL, certified_at = NULL",
+        &[&official_id, &reason],
+    )?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(voucher)
+}
+
+// Records the release of the cash or check; `paid_at` defaults to now
+#[tauri::command]
+pub fn pay_disbursement_voucher_command(id: i32, paid_at: Option<String>, check_number: Option<String>) -> Result<DisbursementVoucher, String> {
+    let mut conn = establish_connection().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let mut voucher = fetch_voucher(&tx, id)?;
+    if let Some(number) = check_number.filter(|number| !number.trim().is_empty()) {
+        voucher.check_number = Some(number.trim().to_string());
+    }
+    validate_payment(&voucher)?;
+
+    let paid_at = paid_at.filter(|date| !date.trim().is_empty());
+    let voucher = advance(
+        &tx,
+        id,
+        &["Approved"],
+        "Paid",
+        "paid_at = COALESCE(?1, CURRENT_TIMESTAMP), check_number = ?2",
+        &[&paid_at, &voucher.check_number],
+    )?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(voucher)
+}
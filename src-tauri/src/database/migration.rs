@@ -1,4 +1,8 @@
-use rusqlite::{Connection, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+use super::seed::seed;
+use crate::commands::ledger::post_unposted_records;
+use crate::pdf::document::local_date;
 
 pub fn migrate(conn: &Connection) -> Result<()> {
     conn.execute_batch(
@@ -23,7 +27,7 @@ pub fn migrate(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS incomes (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             type_ TEXT NOT NULL,
-            amount REAL NOT NULL,
+            amount INTEGER NOT NULL, -- centavos
             or_number INTEGER NOT NULL,
             received_from TEXT NOT NULL,
             received_by TEXT NOT NULL,
@@ -33,7 +37,7 @@ pub fn migrate(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS expenses (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             type_ TEXT NOT NULL,
-            amount REAL NOT NULL,
+            amount INTEGER NOT NULL, -- centavos
             paid_to TEXT NOT NULL,
             paid_by TEXT NOT NULL,
             date TEXT NOT NULL,
@@ -105,7 +109,6 @@ pub fn migrate(conn: &Connection) -> Result<()> {
             zone TEXT,
             section TEXT
         );
-        DROP TABLE IF EXISTS officials; -- Ensure no conflicts with existing table
         CREATE TABLE IF NOT EXISTS officials (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             name TEXT NOT NULL,
@@ -149,7 +152,458 @@ pub fn migrate(conn: &Connection) -> Result<()> {
             username TEXT NOT NULL UNIQUE,
             password TEXT NOT NULL
         );
+
+        CREATE TABLE IF NOT EXISTS certificate_overrides (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            certificate_id INTEGER NOT NULL,
+            blotter_ids TEXT NOT NULL, -- comma-separated blotter ids
+            reason TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS certificate_series (
+            type_ TEXT NOT NULL,
+            year INTEGER NOT NULL,
+            prefix TEXT NOT NULL,
+            last_number INTEGER NOT NULL,
+            PRIMARY KEY (type_, year)
+        );
+
+        CREATE TABLE IF NOT EXISTS signing_key (
+            id INTEGER PRIMARY KEY CHECK (id = 1), -- only one row
+            secret TEXT NOT NULL, -- base64, generated on first use
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS certificate_types (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            code TEXT NOT NULL UNIQUE,
+            name TEXT NOT NULL UNIQUE,
+            prefix TEXT NOT NULL UNIQUE,
+            active BOOLEAN NOT NULL DEFAULT 1,
+            current_version INTEGER NOT NULL DEFAULT 1
+        );
+        CREATE TABLE IF NOT EXISTS certificate_type_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            certificate_type_id INTEGER NOT NULL REFERENCES certificate_types (id),
+            version INTEGER NOT NULL,
+            body TEXT NOT NULL,
+            fee INTEGER NOT NULL DEFAULT 0, -- centavos
+            required_fields TEXT NOT NULL DEFAULT '[]', -- JSON array of Certificate field names
+            validity_days INTEGER,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE (certificate_type_id, version)
+        );
+
+        CREATE TABLE IF NOT EXISTS accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            code TEXT NOT NULL UNIQUE,
+            name TEXT NOT NULL,
+            type_ TEXT NOT NULL, -- Asset, Liability, Equity, Income or Expense
+            budget_class TEXT, -- PS, MOOE, CO or DF for expense accounts
+            category TEXT, -- income/expense category posted to this account
+            active BOOLEAN NOT NULL DEFAULT 1
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_accounts_category ON accounts (type_, category);
+        CREATE TABLE IF NOT EXISTS journal_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            description TEXT NOT NULL,
+            fund TEXT NOT NULL DEFAULT 'General Fund',
+            source TEXT NOT NULL DEFAULT 'manual', -- income, expense or manual
+            source_id INTEGER,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_journal_entries_source ON journal_entries (source, source_id);
+        CREATE TABLE IF NOT EXISTS journal_lines (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_id INTEGER NOT NULL REFERENCES journal_entries (id) ON DELETE CASCADE,
+            account_id INTEGER NOT NULL REFERENCES accounts (id),
+            debit INTEGER NOT NULL DEFAULT 0, -- centavos
+            credit INTEGER NOT NULL DEFAULT 0, -- centavos
+            memo TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS budgets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            fiscal_year INTEGER NOT NULL,
+            category TEXT NOT NULL, -- expense category
+            amount INTEGER NOT NULL, -- centavos
+            kind TEXT NOT NULL DEFAULT 'Original', -- Original or Supplemental
+            reference TEXT, -- appropriation or supplemental budget ordinance
+            approved_date TEXT,
+            notes TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_budgets_year ON budgets (fiscal_year, category);
+
+        CREATE TABLE IF NOT EXISTS or_booklets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            series_start INTEGER NOT NULL,
+            series_end INTEGER NOT NULL, -- inclusive
+            collecting_officer TEXT NOT NULL,
+            issued_date TEXT,
+            active BOOLEAN NOT NULL DEFAULT 1,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE TABLE IF NOT EXISTS cancelled_receipts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            booklet_id INTEGER NOT NULL REFERENCES or_booklets (id),
+            or_number INTEGER NOT NULL,
+            reason TEXT NOT NULL,
+            cancelled_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE (booklet_id, or_number)
+        );
+
+        CREATE TABLE IF NOT EXISTS cash_advances (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            official_id INTEGER NOT NULL REFERENCES officials (id),
+            event_id INTEGER REFERENCES events (id),
+            amount INTEGER NOT NULL, -- centavos
+            purpose TEXT NOT NULL,
+            date_granted TEXT NOT NULL,
+            due_date TEXT NOT NULL, -- liquidation deadline
+            refunded INTEGER NOT NULL DEFAULT 0, -- unspent cash returned, in centavos
+            refund_date TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS disbursement_vouchers (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            expense_id INTEGER NOT NULL UNIQUE REFERENCES expenses (id),
+            dv_number TEXT NOT NULL UNIQUE, -- YYYY-MM-NNNN
+            status TEXT NOT NULL DEFAULT 'Prepared', -- Prepared, Certified, Approved, Paid or Rejected
+            particulars TEXT,
+            payment_mode TEXT NOT NULL DEFAULT 'Cash', -- Cash or Check
+            check_number TEXT,
+            check_date TEXT,
+            bank TEXT,
+            prepared_by INTEGER REFERENCES officials (id),
+            prepared_at TEXT,
+            certified_by INTEGER REFERENCES officials (id),
+            certified_at TEXT,
+            approved_by INTEGER REFERENCES officials (id),
+            approved_at TEXT,
+            paid_at TEXT,
+            rejected_by INTEGER REFERENCES officials (id),
+            rejected_at TEXT,
+            rejection_reason TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS holidays (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            code TEXT UNIQUE, -- set on built-in holidays so they are seeded only once
+            name TEXT NOT NULL,
+            type_ TEXT NOT NULL, -- Regular, Special Non-Working or Special Working
+            month INTEGER NOT NULL,
+            day INTEGER NOT NULL,
+            year INTEGER, -- NULL for holidays that fall on the same date every year
+            active BOOLEAN NOT NULL DEFAULT 1
+        );
+
+        CREATE TABLE IF NOT EXISTS leave_requests (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            official_id INTEGER NOT NULL REFERENCES officials (id),
+            leave_type TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            reason TEXT,
+            status TEXT NOT NULL DEFAULT 'Pending', -- Pending, Approved or Rejected
+            decided_by INTEGER REFERENCES officials (id),
+            decided_at TEXT,
+            rejection_reason TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_leave_requests_official ON leave_requests (official_id, start_date);
+
+        -- officials are the people; each position they hold is a term here
+        CREATE TABLE IF NOT EXISTS official_terms (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            official_id INTEGER NOT NULL REFERENCES officials (id),
+            role TEXT NOT NULL,
+            section TEXT NOT NULL DEFAULT '',
+            term_start TEXT, -- YYYY-MM-DD; NULL when never recorded
+            term_end TEXT, -- scheduled end, YYYY-MM-DD
+            ended_on TEXT, -- set once the term is over; until then the official is in office
+            end_reason TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_official_terms_official ON official_terms (official_id, term_start);
+        CREATE INDEX IF NOT EXISTS idx_official_terms_role ON official_terms (role, term_start);
+
+        CREATE TABLE IF NOT EXISTS committees (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            description TEXT,
+            active BOOLEAN NOT NULL DEFAULT 1
+        );
+
+        -- a seat on a committee lasts as long as the term it was given under
+        CREATE TABLE IF NOT EXISTS committee_assignments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            committee_id INTEGER NOT NULL REFERENCES committees (id),
+            term_id INTEGER NOT NULL REFERENCES official_terms (id),
+            position TEXT NOT NULL, -- Chair or Member
+            UNIQUE (committee_id, term_id)
+        );
+
+        -- who signs each document, by role; the PDF prints whoever holds the role
+        -- on the document's date, or the officer-in-charge while one is designated
+        CREATE TABLE IF NOT EXISTS signatories (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            document_type TEXT NOT NULL, -- report name, e.g. certificate or cashbook
+            slot TEXT NOT NULL,
+            label TEXT NOT NULL DEFAULT '', -- printed above the signature, e.g. 'Noted by:'
+            role TEXT NOT NULL,
+            title TEXT NOT NULL, -- printed under the name, e.g. 'Punong Barangay'
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            built_in BOOLEAN NOT NULL DEFAULT 0,
+            oic_official_id INTEGER REFERENCES officials (id),
+            oic_from TEXT, -- YYYY-MM-DD; either end may be open
+            oic_to TEXT,
+            UNIQUE (document_type, slot)
+        );
+
+        CREATE TABLE IF NOT EXISTS venues (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            address TEXT,
+            capacity INTEGER,
+            active BOOLEAN NOT NULL DEFAULT 1
+        );
+
+        -- one check-in per resident per sitting; recurring events take attendance
+        -- at every sitting
+        CREATE TABLE IF NOT EXISTS event_attendance (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_id INTEGER NOT NULL REFERENCES events (id),
+            resident_id INTEGER NOT NULL REFERENCES residents (id),
+            sitting_date TEXT NOT NULL, -- YYYY-MM-DD
+            checked_in_at TEXT NOT NULL, -- local YYYY-MM-DDTHH:MM
+            items_received TEXT, -- goods handed out at distributions
+            recorded_by TEXT,
+            UNIQUE (event_id, resident_id, sitting_date)
+        );
+        CREATE INDEX IF NOT EXISTS idx_event_attendance_resident ON event_attendance (resident_id);
+
+        CREATE TABLE IF NOT EXISTS signature_images (
+            official_id INTEGER PRIMARY KEY REFERENCES officials (id),
+            image TEXT NOT NULL, -- data URL, as captured
+            captured_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
         ",
     )?;
+
+    add_column_if_missing(conn, "certificates", "resident_id", "INTEGER")?;
+    add_column_if_missing(conn, "certificates", "control_number", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "content_hash", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "voided", "BOOLEAN NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "certificates", "void_reason", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "voided_at", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "or_number", "INTEGER")?;
+    add_column_if_missing(conn, "certificates", "payer", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "income_id", "INTEGER")?;
+    add_column_if_missing(conn, "incomes", "certificate_id", "INTEGER")?;
+    add_column_if_missing(conn, "incomes", "flag", "TEXT")?;
+    add_column_if_missing(conn, "certificates", "template_version_id", "INTEGER")?;
+    add_column_if_missing(conn, "certificates", "valid_until", "TEXT")?;
+    add_column_if_missing(conn, "incomes", "or_booklet_id", "INTEGER")?;
+    // fee reversals once copied the receipt they cancel; they now carry no OR of their own
+    conn.execute(
+        "UPDATE incomes SET flag = 'Reverses OR ' || or_number || COALESCE(' (' || SUBSTR(flag, 10) || ')', ''),
+             or_number = 0, or_booklet_id = NULL
+         WHERE amount < 0 AND certificate_id IS NOT NULL AND or_number > 0",
+        [],
+    )?;
+    add_column_if_missing(conn, "expenses", "cash_advance_id", "INTEGER")?;
+    // expenses already on file when vouchers came in count as paid without one
+    add_column_if_missing(conn, "expenses", "requires_voucher", "BOOLEAN NOT NULL DEFAULT 0")?;
+    convert_to_centavos(conn, "incomes", "amount")?;
+    convert_to_centavos(conn, "expenses", "amount")?;
+    convert_to_centavos(conn, "certificate_type_versions", "fee")?;
+    // created after the conversion, which rebuilds both tables
+    conn.execute_batch(
+        "
+        CREATE INDEX IF NOT EXISTS idx_incomes_date ON incomes (date);
+        CREATE INDEX IF NOT EXISTS idx_expenses_date ON expenses (date);
+        ",
+    )?;
+
+    seed(conn)?;
+    post_unposted_records(conn)?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_certificates_control_number ON certificates (control_number)",
+        [],
+    )?;
+    add_column_if_missing(conn, "settings", "clearance_blotter_policy", "TEXT NOT NULL DEFAULT 'warn'")?;
+    add_column_if_missing(conn, "settings", "budget_overspend_policy", "TEXT NOT NULL DEFAULT 'warn'")?;
+    add_column_if_missing(conn, "settings", "office_am_in", "TEXT NOT NULL DEFAULT '08:00'")?;
+    add_column_if_missing(conn, "settings", "office_am_out", "TEXT NOT NULL DEFAULT '12:00'")?;
+    add_column_if_missing(conn, "settings", "office_pm_in", "TEXT NOT NULL DEFAULT '13:00'")?;
+    add_column_if_missing(conn, "settings", "office_pm_out", "TEXT NOT NULL DEFAULT '17:00'")?;
+    add_column_if_missing(conn, "settings", "grace_minutes", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "logbook", "undertime_minutes", "INTEGER")?;
+    add_column_if_missing(conn, "logbook", "official_id", "INTEGER REFERENCES officials (id)")?;
+    link_logbook_to_officials(conn)?;
+    backfill_official_terms(conn)?;
+    add_column_if_missing(conn, "blotters", "committee_id", "INTEGER REFERENCES committees (id)")?;
+    add_column_if_missing(conn, "events", "committee_id", "INTEGER REFERENCES committees (id)")?;
+    add_column_if_missing(conn, "expenses", "committee_id", "INTEGER REFERENCES committees (id)")?;
+    add_column_if_missing(conn, "events", "starts_at", "TEXT")?;
+    add_column_if_missing(conn, "events", "ends_at", "TEXT")?;
+    add_column_if_missing(conn, "events", "venue_id", "INTEGER REFERENCES venues (id)")?;
+    add_column_if_missing(conn, "events", "recurrence", "TEXT")?;
+    add_column_if_missing(conn, "events", "claim_per_household", "BOOLEAN NOT NULL DEFAULT 0")?;
+    schedule_legacy_events(conn)?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_events_venue ON events (venue_id, starts_at)", [])?;
+
+    Ok(())
+}
+
+// Events saved with only a date run all day. Their typed venues become
+// venue records so bookings can be checked against them.
+fn schedule_legacy_events(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT id, date, TRIM(venue) FROM events WHERE starts_at IS NULL")?;
+    let events = stmt
+        .query_map([], |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>>>()?;
+    for (id, date, venue) in events {
+        let Some(date) = local_date(&date) else {
+            continue;
+        };
+        if !venue.is_empty() {
+            conn.execute("INSERT OR IGNORE INTO venues (name) VALUES (?1)", params![venue])?;
+        }
+        conn.execute(
+            "UPDATE events SET date = ?1, starts_at = ?1 || 'T00:00', ends_at = ?1 || 'T23:59',
+                 venue_id = (SELECT v.id FROM venues v WHERE v.name = ?2)
+             WHERE id = ?3",
+            params![date.to_string(), venue, id],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Officials saved before term history existed get their current position as an
+// open term, with the typed term dates parsed where they can be
+fn backfill_official_terms(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT id, role, section, term_start, term_end FROM officials o
+         WHERE NOT EXISTS (SELECT 1 FROM official_terms t WHERE t.official_id = o.id)",
+    )?;
+    let officials = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    let parse = |value: &Option<String>| value.as_deref().and_then(local_date).map(|date| date.to_string());
+    for (id, role, section, term_start, term_end) in officials {
+        conn.execute(
+            "INSERT INTO official_terms (official_id, role, section, term_start, term_end) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, role, section, parse(&term_start), parse(&term_end)],
+        )?;
+    }
+    Ok(())
+}
+
+// Logbook entries used to carry only the official's typed name. Entries whose name
+// matches an official (ignoring case and surrounding spaces) get linked to them;
+// the rest stay unlinked until edited.
+fn link_logbook_to_officials(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "UPDATE logbook SET official_id = (
+             SELECT o.id FROM officials o
+             WHERE LOWER(TRIM(o.name)) = LOWER(TRIM(logbook.official_name))
+             ORDER BY o.id DESC LIMIT 1
+         )
+         WHERE official_id IS NULL",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_logbook_official ON logbook (official_id, date)",
+        [],
+    )?;
+    Ok(())
+}
+
+// CREATE TABLE IF NOT EXISTS won't touch tables that already exist,
+// so columns added after the first release go through here instead.
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<String>>>()?;
+
+    if !columns.iter().any(|c| c == column) {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+
     Ok(())
 }
+
+// Money columns were REAL pesos before amounts became integer centavos. SQLite can't
+// change a column's type in place, so the table is rebuilt from its own schema.
+fn convert_to_centavos(conn: &Connection, table: &str, column: &str) -> Result<()> {
+    let declared: Option<String> = conn
+        .query_row(
+            "SELECT type FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if !declared.is_some_and(|declared| declared.eq_ignore_ascii_case("REAL")) {
+        return Ok(());
+    }
+
+    let schema: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![table],
+        |row| row.get(0),
+    )?;
+    let rebuilt = format!("{}_centavos", table);
+    let create = schema
+        .replacen(table, &rebuilt, 1)
+        .replacen(&format!("{} REAL", column), &format!("{} INTEGER", column), 1);
+
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<String>>>()?;
+    let values: Vec<String> = columns
+        .iter()
+        .map(|c| if c == column { format!("CAST(ROUND({} * 100) AS INTEGER)", c) } else { c.clone() })
+        .collect();
+    let sequence: Option<i64> = conn
+        .query_row("SELECT seq FROM sqlite_sequence WHERE name = ?1", params![table], |row| row.get(0))
+        .optional()?;
+
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(&format!(
+        "{create};
+         INSERT INTO {rebuilt} ({columns}) SELECT {values} FROM {table};
+         DROP TABLE {table};
+         ALTER TABLE {rebuilt} RENAME TO {table};",
+        create = create,
+        rebuilt = rebuilt,
+        table = table,
+        columns = columns.join(", "),
+        values = values.join(", "),
+    ))?;
+    // keep AUTOINCREMENT from handing out ids of rows deleted before the rebuild
+    if let Some(sequence) = sequence {
+        tx.execute(
+            "UPDATE sqlite_sequence SET seq = MAX(seq, ?1) WHERE name = ?2",
+            params![sequence, table],
+        )?;
+    }
+    tx.commit()
+}
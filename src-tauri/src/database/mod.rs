@@ -1,2 +1,3 @@
 pub mod connection;
 pub mod migration;
+pub mod seed;
@@ -0,0 +1,340 @@
+use rusqlite::{params, Connection, Result};
+
+use crate::models::money::Money;
+
+struct SeedCertificateType {
+    code: &'static str,
+    name: &'static str,
+    prefix: &'static str,
+    fee: f64,
+    required_fields: &'static str, // JSON array
+    validity_days: Option<i32>,
+    body: &'static str,
+}
+
+const CERTIFICATE_TYPES: &[SeedCertificateType] = &[
+    SeedCertificateType {
+        code: "clearance",
+        name: "Barangay Clearance",
+        prefix: "BC",
+        fee: 10.0,
+        required_fields: r#"["age", "civil_status", "purpose"]"#,
+        validity_days: Some(365),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}} years old, {{civil_status}}, and a resident of zone {{zone}}, \
+         Barangay {{barangay}}, {{municipality}}, {{province}}. He/She is known to me of good moral character and at present \
+         has no criminal record and/or any case in this Barangay.\n\n\
+         Purpose of Certificate: {{purpose}}\n\n\
+         This certification is being issued upon request of the above-named person for record, reference and other legal \
+         matters this may serve.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "indigency",
+        name: "Indigency Certificate",
+        prefix: "IND",
+        fee: 10.0,
+        required_fields: r#"["age", "civil_status", "purpose"]"#,
+        validity_days: Some(180),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}} years old, {{civil_status}}, is a resident of Barangay \
+         {{barangay}}, {{municipality}}, {{province}}, and belongs to an indigent family in this Barangay.\n\n\
+         This certification is being issued upon request of the above-named person for {{purpose}}.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "residency",
+        name: "Residency Certificate",
+        prefix: "RES",
+        fee: 0.0,
+        required_fields: r#"["age", "civil_status", "purpose"]"#,
+        validity_days: Some(180),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}} years old, {{civil_status}}, is a bonafide resident of \
+         {{barangay}}, {{municipality}}, {{province}}.\n\n\
+         This certification is being issued upon the request of the aforementioned person for residency verification \
+         and for the following purpose: {{purpose}}.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "business",
+        name: "Barangay Business Permit",
+        prefix: "BP",
+        fee: 150.0,
+        required_fields: r#"["ownership_text"]"#,
+        validity_days: Some(365),
+        body: "BARANGAY BUSINESS PERMIT\n\n\
+         {{ownership_text}}\n\n\
+         Owned and operated by {{resident_name}}, who has complied with Barangay Ordinance No. 14 imposing the Barangay \
+         Permit fee required of every business, trade or transaction within the jurisdiction of this Barangay.\n\n\
+         This Barangay permit on business is indorsed to this Municipality for registration purposes only.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "4ps",
+        name: "4Ps Certificate",
+        prefix: "4PS",
+        fee: 10.0,
+        required_fields: r#"["age", "civil_status", "purpose"]"#,
+        validity_days: Some(365),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}} years old, {{civil_status}}, is a resident of Barangay \
+         {{barangay}}, {{municipality}}, {{province}}.\n\n\
+         This certifies further that the above-named person is a member of the 4Ps (Pantawid Pamilyang Pilipino Program) \
+         in this Barangay.\n\n\
+         This certification is being issued upon request of the above-named person for {{purpose}}.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "birth",
+        name: "Birth Certificate",
+        prefix: "BIR",
+        fee: 10.0,
+        required_fields: r#"["purpose"]"#,
+        validity_days: None,
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}} is a resident of Barangay {{barangay}}, {{municipality}}, {{province}}, \
+         and that the facts of birth stated herein are based on the records and documents presented to this office.\n\n\
+         This certification is being issued upon request of the interested party for {{purpose}}.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "marriage",
+        name: "Marriage Certificate",
+        prefix: "MAR",
+        fee: 10.0,
+        required_fields: r#"["purpose"]"#,
+        validity_days: None,
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, a resident of Barangay {{barangay}}, {{municipality}}, {{province}}, \
+         wishes to contract marriage, and that no legal impediment is known to this office.\n\n\
+         This certification is being issued upon request of the interested party for {{purpose}}.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "ownership",
+        name: "Ownership Certificate",
+        prefix: "OWN",
+        fee: 10.0,
+        required_fields: r#"["age", "civil_status", "ownership_text"]"#,
+        validity_days: None,
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}} years old, {{civil_status}}, a resident of Barangay \
+         {{barangay}}, {{municipality}}, {{province}}, is the owner of {{ownership_text}}.\n\n\
+         This certification is being issued upon request of the interested party for record and reference purposes only.\n\n\
+         Issued this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "unemployment",
+        name: "Unemployment Certificate",
+        prefix: "UNE",
+        fee: 10.0,
+        required_fields: r#"["age", "civil_status"]"#,
+        validity_days: Some(180),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         This is to certify that {{resident_name}}, {{age}}, {{civil_status}}, is a resident of Barangay {{barangay}}, \
+         {{municipality}}, {{province}}.\n\n\
+         This certifies further that the above-named person is currently unemployed and is actively seeking employment.\n\n\
+         This certification is issued upon request of the interested party for record and reference purposes.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+    SeedCertificateType {
+        code: "businessClearance",
+        name: "Barangay Business Clearance",
+        prefix: "BBC",
+        fee: 150.0,
+        required_fields: r#"["ownership_text"]"#,
+        validity_days: Some(365),
+        body: "TO WHOM IT MAY CONCERN:\n\n\
+         {{ownership_text}}\n\n\
+         This is to certify that the above-named business, owned by {{resident_name}}, is located within Barangay \
+         {{barangay}}, {{municipality}}, {{province}}, and is duly authorized to operate within the jurisdiction of \
+         this Barangay.\n\n\
+         Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}.",
+    },
+];
+
+// Built-in templates are only inserted once; edits made by the barangay are never overwritten
+fn seed_certificate_types(conn: &Connection) -> Result<()> {
+    for seed in CERTIFICATE_TYPES {
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO certificate_types (code, name, prefix, active, current_version) VALUES (?1, ?2, ?3, 1, 1)",
+            params![seed.code, seed.name, seed.prefix],
+        )?;
+        if inserted == 0 {
+            continue;
+        }
+
+        conn.execute(
+            "INSERT INTO certificate_type_versions (certificate_type_id, version, body, fee, required_fields, validity_days)
+             VALUES (?1, 1, ?2, ?3, ?4, ?5)",
+            params![conn.last_insert_rowid(), seed.body, Money::from_pesos(seed.fee), seed.required_fields, seed.validity_days],
+        )?;
+    }
+
+    Ok(())
+}
+
+struct SeedAccount {
+    code: &'static str,
+    name: &'static str,
+    type_: &'static str,
+    budget_class: Option<&'static str>,
+    category: Option<&'static str>, // income/expense category the UI files under this account
+}
+
+// Chart of accounts following the barangay budget classes: PS, MOOE, CO and the 20% Development Fund
+const ACCOUNTS: &[SeedAccount] = &[
+    SeedAccount { code: "1-01-01-010", name: "Cash - Local Treasury", type_: "Asset", budget_class: None, category: None },
+    SeedAccount { code: "1-01-02-010", name: "Cash in Bank - Local Currency", type_: "Asset", budget_class: None, category: None },
+    SeedAccount { code: "1-03-05-030", name: "Advances to Officers and Employees", type_: "Asset", budget_class: None, category: None },
+    SeedAccount { code: "3-01-01-010", name: "Government Equity", type_: "Equity", budget_class: None, category: None },
+    SeedAccount { code: "4-01-01-010", name: "Tax Revenue", type_: "Income", budget_class: None, category: Some("Tax Revenue") },
+    SeedAccount { code: "4-01-02-010", name: "Local Revenue", type_: "Income", budget_class: None, category: Some("Local Revenue") },
+    SeedAccount { code: "4-02-01-010", name: "Clearance and Certification Fees", type_: "Income", budget_class: None, category: Some("Certificate fees") },
+    SeedAccount { code: "4-02-01-990", name: "Other Service Income", type_: "Income", budget_class: None, category: Some("Service Revenue") },
+    SeedAccount { code: "4-02-02-050", name: "Rent Income", type_: "Income", budget_class: None, category: Some("Rental Income") },
+    SeedAccount { code: "4-02-02-220", name: "Water System Fees", type_: "Income", budget_class: None, category: Some("Water System") },
+    SeedAccount { code: "4-03-01-010", name: "Share from National Tax Allotment and Grants", type_: "Income", budget_class: None, category: Some("Government Grants") },
+    SeedAccount { code: "4-06-01-990", name: "Miscellaneous Income", type_: "Income", budget_class: None, category: Some("Others") },
+    SeedAccount { code: "5-01-01-020", name: "Honoraria", type_: "Expense", budget_class: Some("PS"), category: Some("Honoraria") },
+    SeedAccount { code: "5-02-03-050", name: "Food Supplies Expenses", type_: "Expense", budget_class: Some("MOOE"), category: Some("Foods") },
+    SeedAccount { code: "5-02-04-010", name: "Utility Expenses", type_: "Expense", budget_class: Some("MOOE"), category: Some("Utilities") },
+    SeedAccount { code: "5-02-99-980", name: "Local Fund Expenses", type_: "Expense", budget_class: Some("MOOE"), category: Some("Local Funds") },
+    SeedAccount { code: "5-02-99-990", name: "Other Maintenance and Operating Expenses", type_: "Expense", budget_class: Some("MOOE"), category: Some("Others") },
+    SeedAccount { code: "5-06-01-010", name: "Infrastructure Outlay", type_: "Expense", budget_class: Some("CO"), category: Some("Infrastructure") },
+    SeedAccount { code: "5-06-02-010", name: "20% Development Fund Projects", type_: "Expense", budget_class: Some("DF"), category: Some("IRA") },
+];
+
+fn seed_accounts(conn: &Connection) -> Result<()> {
+    for account in ACCOUNTS {
+        conn.execute(
+            "INSERT OR IGNORE INTO accounts (code, name, type_, budget_class, category) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![account.code, account.name, account.type_, account.budget_class, account.category],
+        )?;
+    }
+
+    Ok(())
+}
+
+struct SeedHoliday {
+    code: &'static str,
+    name: &'static str,
+    type_: &'static str,
+    month: u32,
+    day: u32,
+    year: Option<i32>,
+}
+
+const REGULAR: &str = "Regular";
+const SPECIAL: &str = "Special Non-Working";
+const SPECIAL_WORKING: &str = "Special Working";
+
+// Philippine holidays on fixed dates (RA 9492 and later laws), plus the movable
+// ones proclaimed for 2026. Later years' movable holidays are added by the barangay.
+const HOLIDAYS: &[SeedHoliday] = &[
+    SeedHoliday { code: "new_year", name: "New Year's Day", type_: REGULAR, month: 1, day: 1, year: None },
+    SeedHoliday { code: "edsa_anniversary", name: "EDSA People Power Revolution Anniversary", type_: SPECIAL_WORKING, month: 2, day: 25, year: None },
+    SeedHoliday { code: "araw_ng_kagitingan", name: "Araw ng Kagitingan", type_: REGULAR, month: 4, day: 9, year: None },
+    SeedHoliday { code: "labor_day", name: "Labor Day", type_: REGULAR, month: 5, day: 1, year: None },
+    SeedHoliday { code: "independence_day", name: "Independence Day", type_: REGULAR, month: 6, day: 12, year: None },
+    SeedHoliday { code: "ninoy_aquino_day", name: "Ninoy Aquino Day", type_: SPECIAL, month: 8, day: 21, year: None },
+    SeedHoliday { code: "all_saints_day", name: "All Saints' Day", type_: SPECIAL, month: 11, day: 1, year: None },
+    SeedHoliday { code: "all_souls_day", name: "All Souls' Day", type_: SPECIAL, month: 11, day: 2, year: None },
+    SeedHoliday { code: "bonifacio_day", name: "Bonifacio Day", type_: REGULAR, month: 11, day: 30, year: None },
+    SeedHoliday { code: "immaculate_conception", name: "Feast of the Immaculate Conception of Mary", type_: SPECIAL, month: 12, day: 8, year: None },
+    SeedHoliday { code: "christmas_eve", name: "Christmas Eve", type_: SPECIAL, month: 12, day: 24, year: None },
+    SeedHoliday { code: "christmas_day", name: "Christmas Day", type_: REGULAR, month: 12, day: 25, year: None },
+    SeedHoliday { code: "rizal_day", name: "Rizal Day", type_: REGULAR, month: 12, day: 30, year: None },
+    SeedHoliday { code: "last_day_of_year", name: "Last Day of the Year", type_: SPECIAL, month: 12, day: 31, year: None },
+    SeedHoliday { code: "chinese_new_year_2026", name: "Chinese New Year", type_: SPECIAL, month: 2, day: 17, year: Some(2026) },
+    SeedHoliday { code: "maundy_thursday_2026", name: "Maundy Thursday", type_: REGULAR, month: 4, day: 2, year: Some(2026) },
+    SeedHoliday { code: "good_friday_2026", name: "Good Friday", type_: REGULAR, month: 4, day: 3, year: Some(2026) },
+    SeedHoliday { code: "black_saturday_2026", name: "Black Saturday", type_: SPECIAL, month: 4, day: 4, year: Some(2026) },
+    SeedHoliday { code: "national_heroes_day_2026", name: "National Heroes Day", type_: REGULAR, month: 8, day: 31, year: Some(2026) },
+];
+
+// Removing a built-in holiday only switches it off, so it is not seeded back
+fn seed_holidays(conn: &Connection) -> Result<()> {
+    for holiday in HOLIDAYS {
+        conn.execute(
+            "INSERT OR IGNORE INTO holidays (code, name, type_, month, day, year) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![holiday.code, holiday.name, holiday.type_, holiday.month, holiday.day, holiday.year],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Standing committees of the Sangguniang Barangay
+const COMMITTEES: &[(&str, &str)] = &[
+    ("Peace and Order", "Public safety, the tanods and the Lupong Tagapamayapa"),
+    ("Health", "Health services, sanitation and nutrition"),
+    ("Finance and Appropriations", "Budget, revenue and spending"),
+    ("Infrastructure", "Roads, drainage and public works"),
+    ("Education", "Schools, day care and scholarships"),
+    ("Environment", "Solid waste, cleanliness and disaster preparedness"),
+    ("Agriculture", "Farmers, fisherfolk and livelihood"),
+    ("Women and Family", "Women, children and family welfare"),
+    ("Youth and Sports", "Youth development and sports"),
+];
+
+fn seed_committees(conn: &Connection) -> Result<()> {
+    for (name, description) in COMMITTEES {
+        conn.execute(
+            "INSERT OR IGNORE INTO committees (name, description) VALUES (?1, ?2)",
+            params![name, description],
+        )?;
+    }
+
+    Ok(())
+}
+
+struct SeedSignatory {
+    document_type: &'static str,
+    slot: &'static str,
+    label: &'static str,
+    role: &'static str,
+    title: &'static str,
+}
+
+const CAPTAIN: &str = "Barangay Captain";
+const TREASURER: &str = "Treasurer";
+
+// Default signature blocks for each document, matching what they printed before
+// signatories were configurable
+const SIGNATORIES: &[SeedSignatory] = &[
+    SeedSignatory { document_type: "certificate", slot: "punong_barangay", label: "", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "disbursement_voucher", slot: "certified", label: "A. Certified: expenses necessary, lawful and under my direct supervision", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "disbursement_voucher", slot: "funds_available", label: "B. Certified: funds available, supporting documents complete", role: TREASURER, title: "Barangay Treasurer" },
+    SeedSignatory { document_type: "disbursement_voucher", slot: "approved", label: "C. Approved for payment:", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "daily_time_record", slot: "in_charge", label: "VERIFIED as to the prescribed office hours:", role: CAPTAIN, title: "In Charge" },
+    SeedSignatory { document_type: "cashbook", slot: "prepared", label: "Prepared by:", role: TREASURER, title: "Barangay Treasurer" },
+    SeedSignatory { document_type: "cashbook", slot: "noted", label: "Noted by:", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "receipts_expenditures", slot: "certified", label: "Certified correct:", role: TREASURER, title: "Barangay Treasurer" },
+    SeedSignatory { document_type: "receipts_expenditures", slot: "approved", label: "Approved by:", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "collections_deposits", slot: "certified", label: "Certified correct:", role: TREASURER, title: "Barangay Treasurer" },
+    SeedSignatory { document_type: "collections_deposits", slot: "noted", label: "Noted by:", role: CAPTAIN, title: "Punong Barangay" },
+    SeedSignatory { document_type: "event_attendance", slot: "certified", label: "Certified correct:", role: "Secretary", title: "Barangay Secretary" },
+];
+
+fn seed_signatories(conn: &Connection) -> Result<()> {
+    for (index, signatory) in SIGNATORIES.iter().enumerate() {
+        conn.execute(
+            "INSERT OR IGNORE INTO signatories (document_type, slot, label, role, title, sort_order, built_in)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)",
+            params![signatory.document_type, signatory.slot, signatory.label, signatory.role, signatory.title, index as i32],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn seed(conn: &Connection) -> Result<()> {
+    seed_certificate_types(conn)?;
+    seed_accounts(conn)?;
+    seed_holidays(conn)?;
+    seed_committees(conn)?;
+    seed_signatories(conn)?;
+    Ok(())
+}
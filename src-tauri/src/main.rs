@@ -3,19 +3,36 @@
 mod database;
 mod commands;
 mod models;
+mod pdf;
 
 use commands::households::{fetch_household_heads_command, fetch_residents_by_household_number, fetch_all_residents_with_income, fetch_residents_with_pwd, fetch_residents_with_senior};
 use commands::user::{verify_user_credentials_command, insert_user_command};
-use commands::officials::{fetch_all_officials_command,insert_official_command, save_official_command, delete_official_command};
+use commands::officials::{fetch_all_officials_command,insert_official_command, save_official_command, delete_official_command, end_official_term_command, fetch_council_command, fetch_official_terms_command, fetch_expiring_terms_command};
 use commands::settings::{save_settings_command, fetch_settings_command, fetch_logo_command};
-use commands::events::{save_event_command, insert_event_command, fetch_all_events_command, delete_event_command, update_event_command};
+use commands::events::{save_event_command, insert_event_command, fetch_all_events_command, delete_event_command, update_event_command, fetch_event_occurrences_command, fetch_events_needing_attention_command, refresh_event_statuses, watch_event_statuses};
 use commands::expense::{save_expense_command, insert_expense_command, fetch_all_expenses_command, delete_expense_command, update_expense_command};
 use commands::income::{save_income_command, insert_income_command, fetch_all_incomes_command, delete_income_command, update_income_command};
 use commands::blotters::{save_blotter_command, insert_blotter_command, fetch_all_blotters_command, delete_blotter_command, update_blotter_command};
 use commands::residents::{insert_resident_command, fetch_all_residents_command, delete_resident_command, update_resident_command,save_resident_command};
-use commands::certificates::{insert_certificate_command, fetch_all_certificates_command, update_certificate_command, delete_certificate_command, save_certificate_command};
+use commands::certificates::{check_clearance_eligibility_command, issue_certificate_batch_command, void_certificate_command, insert_certificate_command, fetch_all_certificates_command, update_certificate_command, delete_certificate_command, save_certificate_command};
 use commands::barangay_map::{fetch_households, insert_household, update_household, delete_household, save_household};
-use commands::logbook::{fetch_all_logbook_entries_command, insert_logbook_entry_command, update_logbook_entry_command, save_logbook_entry_command, delete_logbook_entry_command};
+use commands::verification::{fetch_certificate_qr_command, verify_certificate};
+use commands::certificate_types::{fetch_all_certificate_types_command, fetch_certificate_type_versions_command, insert_certificate_type_command, update_certificate_type_command, save_certificate_type_command};
+use commands::reports::{fetch_report_types_command, render_report_command};
+use commands::budget::{fetch_budgets_command, save_budget_command, delete_budget_command, fetch_budget_utilization_command};
+use commands::receipts::{fetch_or_booklets_command, save_or_booklet_command, delete_or_booklet_command, cancel_or_number_command, fetch_or_booklet_report_command};
+use commands::finance::{fetch_finance_summary_command, fetch_finance_year_comparison_command};
+use commands::attendance::{fetch_daily_time_record_command, fetch_attendance_summary_command};
+use commands::holidays::{fetch_holidays_command, save_holiday_command, delete_holiday_command};
+use commands::leaves::{fetch_leave_requests_command, save_leave_request_command, approve_leave_request_command, reject_leave_request_command, delete_leave_request_command};
+use commands::vouchers::{fetch_disbursement_vouchers_command, prepare_disbursement_voucher_command, certify_disbursement_voucher_command, approve_disbursement_voucher_command, reject_disbursement_voucher_command, pay_disbursement_voucher_command};
+use commands::cash_advances::{fetch_cash_advances_command, fetch_cash_advance_liquidations_command, save_cash_advance_command, delete_cash_advance_command, fetch_cash_advance_aging_command};
+use commands::ledger::{fetch_accounts_command, save_account_command, fetch_journal_entries_command, insert_journal_entry_command, delete_journal_entry_command, fetch_trial_balance_command};
+use commands::logbook::{settle_past_days, clock_in_command, clock_out_command, fetch_all_logbook_entries_command, insert_logbook_entry_command, update_logbook_entry_command, save_logbook_entry_command, delete_logbook_entry_command};
+use commands::committees::{fetch_committees_command, save_committee_command, delete_committee_command, assign_committee_member_command, remove_committee_member_command, tag_committee_command, fetch_committee_records_command};
+use commands::signatories::{fetch_signatories_command, fetch_document_signers_command, save_signatory_command, delete_signatory_command, fetch_signature_image_command, save_signature_image_command, delete_signature_image_command};
+use commands::venues::{fetch_venues_command, save_venue_command, delete_venue_command};
+use commands::event_attendance::{fetch_event_attendance_command, register_event_attendee_command, record_items_received_command, remove_event_attendee_command};
 use database::connection::establish_connection;
 use database::migration::migrate;
 use tauri::command;
@@ -41,10 +58,32 @@ fn main() {
         } else {
             println!("✅ Migration ran successfully!");
         }
+        match refresh_event_statuses(&conn) {
+            Ok(changed) => println!("✅ Event statuses updated ({} changed)", changed),
+            Err(e) => eprintln!("❌ Event status update failed: {}", e),
+        }
+        match settle_past_days(&conn) {
+            Ok(changed) => println!("✅ Past logbook days settled ({} changed)", changed),
+            Err(e) => eprintln!("❌ Logbook settlement failed: {}", e),
+        }
     } else {
         eprintln!("❌ Failed to connect to DB");
     }
 
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(result) = pdf::run_headless(&args) {
+        match result {
+            Ok(path) => println!("✅ Report written to {}", path.display()),
+            Err(e) => {
+                eprintln!("❌ Report failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    watch_event_statuses();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .invoke_handler(tauri::generate_handler![
@@ -56,6 +95,12 @@ fn main() {
             delete_event_command,
             update_event_command,
             save_event_command,
+            fetch_event_occurrences_command,
+            fetch_events_needing_attention_command,
+            fetch_event_attendance_command,
+            register_event_attendee_command,
+            record_items_received_command,
+            remove_event_attendee_command,
 
             
             insert_expense_command,
@@ -92,6 +137,30 @@ fn main() {
             insert_official_command,
             save_official_command,
             delete_official_command,
+            end_official_term_command,
+            fetch_council_command,
+            fetch_official_terms_command,
+            fetch_expiring_terms_command,
+
+            fetch_committees_command,
+            save_committee_command,
+            delete_committee_command,
+            assign_committee_member_command,
+            remove_committee_member_command,
+            tag_committee_command,
+            fetch_committee_records_command,
+
+            fetch_signatories_command,
+            fetch_document_signers_command,
+            save_signatory_command,
+            delete_signatory_command,
+            fetch_signature_image_command,
+            save_signature_image_command,
+            delete_signature_image_command,
+
+            fetch_venues_command,
+            save_venue_command,
+            delete_venue_command,
 
             fetch_settings_command,
             save_settings_command,
@@ -102,6 +171,51 @@ fn main() {
             update_certificate_command,
             delete_certificate_command,
             save_certificate_command,
+            check_clearance_eligibility_command,
+            void_certificate_command,
+            issue_certificate_batch_command,
+            fetch_certificate_qr_command,
+            verify_certificate,
+
+            fetch_all_certificate_types_command,
+            fetch_certificate_type_versions_command,
+            insert_certificate_type_command,
+            update_certificate_type_command,
+            save_certificate_type_command,
+
+            fetch_report_types_command,
+            render_report_command,
+
+            fetch_accounts_command,
+            save_account_command,
+            fetch_journal_entries_command,
+            insert_journal_entry_command,
+            delete_journal_entry_command,
+            fetch_trial_balance_command,
+
+            fetch_budgets_command,
+            save_budget_command,
+            delete_budget_command,
+            fetch_budget_utilization_command,
+            fetch_finance_summary_command,
+            fetch_finance_year_comparison_command,
+            fetch_disbursement_vouchers_command,
+            prepare_disbursement_voucher_command,
+            certify_disbursement_voucher_command,
+            approve_disbursement_voucher_command,
+            reject_disbursement_voucher_command,
+            pay_disbursement_voucher_command,
+            fetch_cash_advances_command,
+            fetch_cash_advance_liquidations_command,
+            save_cash_advance_command,
+            delete_cash_advance_command,
+            fetch_cash_advance_aging_command,
+
+            fetch_or_booklets_command,
+            save_or_booklet_command,
+            delete_or_booklet_command,
+            cancel_or_number_command,
+            fetch_or_booklet_report_command,
 
             verify_user_credentials_command,
             insert_user_command,
@@ -117,6 +231,18 @@ fn main() {
             update_logbook_entry_command,
             save_logbook_entry_command,
             delete_logbook_entry_command,
+            fetch_daily_time_record_command,
+            fetch_attendance_summary_command,
+            fetch_holidays_command,
+            save_holiday_command,
+            delete_holiday_command,
+            fetch_leave_requests_command,
+            save_leave_request_command,
+            approve_leave_request_command,
+            reject_leave_request_command,
+            delete_leave_request_command,
+            clock_in_command,
+            clock_out_command,
             
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DtrDay {
+    pub day: u32,
+    pub date: String,    // YYYY-MM-DD
+    pub weekday: String, // e.g. "Monday"
+    pub time_in_am: Option<String>,
+    pub time_out_am: Option<String>,
+    pub time_in_pm: Option<String>,
+    pub time_out_pm: Option<String>,
+    pub status: Option<String>, // Present, Late, Undertime, Absent, On Leave, Holiday or Rest Day; None for days yet to come
+    pub hours: f64,
+    pub undertime_minutes: i32, // tardiness plus undertime
+    pub remarks: Option<String>,
+}
+
+// Daily Time Record (CS Form 48) of one official for a month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyTimeRecord {
+    pub official_id: i32,
+    pub official_name: String,
+    pub official_role: String,
+    pub month: String, // "2026-03"
+    pub days: Vec<DtrDay>,
+    pub days_present: i32,
+    pub absences: i32,
+    pub days_on_leave: i32,
+    pub holidays: i32,
+    pub hours: f64,
+    pub undertime_minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceSummary {
+    pub official_id: i32,
+    pub official_name: String,
+    pub official_role: String,
+    pub official_section: String,
+    pub days_present: i32, // includes late and undertime days
+    pub days_late: i32,
+    pub days_undertime: i32,
+    pub absences: i32,
+    pub days_on_leave: i32,
+    pub hours: f64,
+    pub undertime_minutes: i32,
+}
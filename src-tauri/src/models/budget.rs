@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+// One appropriation line of the Annual Barangay Budget, or a supplemental budget
+// adding to (or, when negative, reverting from) a category's appropriation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Budget {
+    pub id: Option<i32>,
+    pub fiscal_year: i32,
+    pub category: String, // expense category, e.g. "Honoraria"
+    pub amount: Money,
+    pub kind: String, // "Original" or "Supplemental"
+    #[serde(default)]
+    pub reference: Option<String>, // e.g. "Appropriation Ordinance No. 2, s. 2026"
+    #[serde(default)]
+    pub approved_date: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetUtilizationRow {
+    pub category: String,
+    pub original: Money,
+    pub supplemental: Money,
+    pub appropriation: Money, // original + supplemental
+    pub quarters: [Money; 4], // expenses per calendar quarter
+    pub actual: Money,
+    pub balance: Money, // appropriation - actual; negative when overspent
+    pub utilization: f64, // percent of the appropriation spent
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BudgetUtilization {
+    pub fiscal_year: i32,
+    pub rows: Vec<BudgetUtilizationRow>,
+    pub total: BudgetUtilizationRow,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+// Cash released to an official ahead of spending, settled by the expenses that
+// liquidate it and any unspent cash refunded
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CashAdvance {
+    pub id: Option<i32>,
+    pub official_id: i32,
+    #[serde(default)]
+    pub official_name: Option<String>,
+    #[serde(default)]
+    pub event_id: Option<i32>,
+    #[serde(default)]
+    pub event_name: Option<String>,
+    pub amount: Money,
+    pub purpose: String,
+    pub date_granted: String,
+    #[serde(default)]
+    pub due_date: Option<String>, // defaults to 30 days after the advance
+    #[serde(default)]
+    pub refunded: Money,
+    #[serde(default)]
+    pub refund_date: Option<String>,
+    // computed when fetched
+    #[serde(default)]
+    pub liquidated: Money, // sum of the liquidating expenses
+    #[serde(default)]
+    pub balance: Money, // amount - liquidated - refunded
+    #[serde(default)]
+    pub status: Option<String>, // Open, Overdue or Liquidated
+    #[serde(default)]
+    pub days_overdue: i64,
+}
+
+// Outstanding balances of one official, by days past the liquidation due date
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CashAdvanceAging {
+    pub official_id: i32,
+    pub official_name: String,
+    pub advances: usize, // advances still carrying a balance
+    pub current: Money, // not yet due
+    pub days_1_30: Money,
+    pub days_31_60: Money,
+    pub days_61_90: Money,
+    pub over_90: Money,
+    pub total: Money,
+}
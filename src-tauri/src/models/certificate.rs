@@ -11,4 +11,68 @@ pub struct Certificate {
     pub amount: Option<String>,
     pub issued_date: Option<String>,
     pub purpose: Option<String>,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub resident_id: Option<i32>,
+    #[serde(default)]
+    pub control_number: Option<String>, // e.g. BC-2026-000123, assigned on insert
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    #[serde(default)]
+    pub voided: bool,
+    #[serde(default)]
+    pub void_reason: Option<String>,
+    #[serde(default)]
+    pub voided_at: Option<String>,
+    #[serde(default)]
+    pub or_number: Option<i32>,
+    #[serde(default)]
+    pub payer: Option<String>, // defaults to the resident when a fee is posted
+    #[serde(default)]
+    pub income_id: Option<i32>,
+    #[serde(default)]
+    pub template_version_id: Option<i32>, // certificate_type_versions row it was issued under
+    #[serde(default)]
+    pub valid_until: Option<String>,
+}
+
+// Unresolved blotter that names the resident as the respondent
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingBlotter {
+    pub id: i32,
+    pub type_: String,
+    pub reported_by: String,
+    pub involved: String,
+    pub incident_date: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertificateIssuance {
+    pub id: i64,
+    pub control_number: String,
+    pub income_id: Option<i64>,
+    pub or_number: Option<i32>, // receipt the fee was recorded under
+    pub warnings: Vec<String>,
+    pub pending_blotters: Vec<PendingBlotter>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertificateBatch {
+    pub certificates: Vec<CertificateIssuance>,
+    pub output_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertificateQr {
+    pub control_number: String,
+    pub payload: String,
+    pub image: String, // PNG data URL, ready for <Image src=...>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertificateVerification {
+    pub status: String, // "valid", "expired", "revoked", "altered" or "not_found"
+    pub valid: bool,
+    pub message: String,
+    pub certificate: Option<Certificate>,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+// A certificate template together with the content of its current version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateType {
+    pub id: Option<i32>,
+    pub code: String,   // e.g. "clearance", "businessClearance"
+    pub name: String,   // matches Certificate.type_, e.g. "Barangay Clearance"
+    pub prefix: String, // control number prefix, e.g. "BC"
+    pub active: bool,
+    #[serde(default)]
+    pub version: Option<i32>,
+    pub body: String, // paragraphs separated by blank lines, placeholders like {{resident_name}}
+    pub fee: Money,
+    pub required_fields: Vec<String>, // Certificate field names, e.g. ["age", "purpose"]
+    pub validity_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CertificateTypeVersion {
+    pub id: i32,
+    pub certificate_type_id: i32,
+    pub version: i32,
+    pub body: String,
+    pub fee: Money,
+    pub required_fields: Vec<String>,
+    pub validity_days: Option<i32>,
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::blotter::Blotter;
+use crate::models::event::Event;
+use crate::models::expense::Expense;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitteeMember {
+    pub assignment_id: i32,
+    pub official_id: i32,
+    pub official_name: String,
+    pub term_id: i32,
+    pub position: String, // Chair or Member
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Committee {
+    pub id: Option<i32>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub chair: Option<CommitteeMember>,
+    #[serde(default)]
+    pub members: Vec<CommitteeMember>, // everyone seated, the chair included
+}
+
+// Records a committee is responsible for
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitteeRecords {
+    pub committee_id: i32,
+    pub blotters: Vec<Blotter>,
+    pub events: Vec<Event>,
+    pub expenses: Vec<Expense>,
+}
@@ -6,8 +6,64 @@ pub struct Event {
     pub name: String,
     pub type_: String,
     pub status: String,
-    pub date: String, // use String to receive from frontend
-    pub venue: String,
+    pub date: String, // use String to receive from frontend; kept as the start date
+    pub venue: String, // venue name, or free text for places not in the venues list
     pub attendee: String,
     pub notes: String,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub starts_at: Option<String>, // local YYYY-MM-DDTHH:MM; events with only a date run all day
+    #[serde(default)]
+    pub ends_at: Option<String>,
+    #[serde(default)]
+    pub venue_id: Option<i32>,
+    #[serde(default)]
+    pub recurrence: Option<String>, // RRULE, e.g. FREQ=MONTHLY;BYDAY=1MO
+    #[serde(default)]
+    pub claim_per_household: bool, // distributions where each household claims once
+}
+
+// An event someone should look at, with every reason that applies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventAttention {
+    pub event_id: i32,
+    pub name: String,
+    pub status: String,
+    pub starts_at: Option<String>,
+    pub ends_at: Option<String>,
+    pub reasons: Vec<String>,
+}
+
+// A resident checked in to a sitting of an event, with what they received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventAttendance {
+    pub id: Option<i32>,
+    pub event_id: i32,
+    pub resident_id: i32,
+    #[serde(default)]
+    pub resident_name: Option<String>,
+    #[serde(default)]
+    pub zone: Option<String>,
+    #[serde(default)]
+    pub household_number: Option<String>,
+    #[serde(default)]
+    pub sitting_date: Option<String>, // YYYY-MM-DD; defaults to the sitting on the check-in date
+    #[serde(default)]
+    pub checked_in_at: Option<String>, // local YYYY-MM-DDTHH:MM; defaults to now
+    #[serde(default)]
+    pub items_received: Option<String>, // e.g. "5 kg rice, 6 canned goods"; None when only attending
+    #[serde(default)]
+    pub recorded_by: Option<String>,
+}
+
+// One sitting of an event; a recurring event has one per repetition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventOccurrence {
+    pub event_id: i32,
+    pub name: String,
+    pub status: String,
+    pub venue: String,
+    pub venue_id: Option<i32>,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub recurring: bool,
+}
@@ -1,13 +1,34 @@
 use serde::{Serialize, Deserialize};
 
+use crate::models::money::Money;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Expense {
     pub id: Option<i32>,
     pub type_: String,
-    pub amount: f64,
+    pub amount: Money,
     pub or_number: i32,
     pub paid_to: String,
     pub paid_by: String,
     pub date: String,
     pub category: String,
+    #[serde(default)]
+    pub voucher_status: Option<String>, // None when the expense has no disbursement voucher
+    #[serde(default)]
+    pub payment_mode: Option<String>, // "Cash" or "Check", from the voucher
+    #[serde(default)]
+    pub cash_advance_id: Option<i32>, // set when the expense liquidates a cash advance
+    #[serde(default)]
+    pub requires_voucher: bool, // false only for expenses recorded before the voucher workflow
+}
+
+impl Expense {
+    // Final once the voucher is approved; only expenses that predate the workflow
+    // count as paid without one
+    pub fn is_final(&self) -> bool {
+        match self.voucher_status.as_deref() {
+            Some(status) => status == "Approved" || status == "Paid",
+            None => !self.requires_voucher,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct FinanceTotals {
+    pub income: Money,
+    pub expense: Money,
+    pub net: Money, // income - expense
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthTotals {
+    pub month: String, // "2026-02"
+    pub totals: FinanceTotals,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryTotal {
+    pub type_: String, // "Income" or "Expense"
+    pub category: String,
+    pub amount: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FundTotals {
+    pub fund: String, // e.g. "General Fund"
+    pub totals: FinanceTotals,
+}
+
+// Everything the finance charts need for one date range
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FinanceSummary {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub totals: FinanceTotals,
+    pub by_month: Vec<MonthTotals>, // every month in the range, including empty ones
+    pub by_category: Vec<CategoryTotal>,
+    pub by_fund: Vec<FundTotals>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthComparison {
+    pub month: u32, // 1-12
+    pub current: FinanceTotals,
+    pub previous: FinanceTotals,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YearComparison {
+    pub year: i32,
+    pub previous_year: i32,
+    pub months: Vec<MonthComparison>,
+    pub current: FinanceTotals,
+    pub previous: FinanceTotals,
+    pub income_change: Option<f64>, // percent; None when the previous year had nothing
+    pub expense_change: Option<f64>,
+}
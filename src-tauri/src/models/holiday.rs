@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    pub id: Option<i32>,
+    #[serde(default)]
+    pub code: Option<String>, // set on built-in holidays
+    pub name: String,
+    pub type_: String, // Regular, Special Non-Working or Special Working
+    pub month: u32,
+    pub day: u32,
+    #[serde(default)]
+    pub year: Option<i32>, // None when the holiday falls on this date every year
+    #[serde(default)]
+    pub date: Option<String>, // YYYY-MM-DD when listed for a given year
+}
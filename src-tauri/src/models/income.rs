@@ -1,11 +1,19 @@
+use crate::models::money::Money;
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct Income {
     pub id: Option<i32>,
     pub type_: String,
-    pub amount: f64,
+    pub amount: Money, // centavos in the database, pesos on the wire
     pub or_number: i32,
     pub received_from: String,
     pub received_by: String,
     pub category: String,
     pub date: String, // Stored as ISO 8601 string (e.g., "2025-07-21")
+    #[serde(default)]
+    pub certificate_id: Option<i32>, // set when the income is a certificate fee
+    #[serde(default)]
+    pub flag: Option<String>, // e.g. note left when the linked certificate is voided
+    #[serde(default)]
+    pub or_booklet_id: Option<i32>, // booklet the receipt was drawn from
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaveRequest {
+    pub id: Option<i32>,
+    pub official_id: i32,
+    #[serde(default)]
+    pub official_name: Option<String>,
+    pub leave_type: String, // e.g. "Vacation Leave"
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>, // Pending, Approved or Rejected
+    #[serde(default)]
+    pub working_days: i32, // days off work the leave covers, net of weekends and holidays
+    #[serde(default)]
+    pub decided_by: Option<i32>,
+    #[serde(default)]
+    pub decided_by_name: Option<String>,
+    #[serde(default)]
+    pub decided_at: Option<String>,
+    #[serde(default)]
+    pub rejection_reason: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub id: Option<i32>,
+    pub code: String, // e.g. "5-02-04-010"
+    pub name: String,
+    pub type_: String, // Asset, Liability, Equity, Income or Expense
+    pub budget_class: Option<String>, // PS, MOOE, CO or DF
+    pub category: Option<String>, // income/expense category that posts here
+    pub active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalLine {
+    #[serde(default)]
+    pub id: Option<i32>,
+    pub account_id: i32,
+    #[serde(default)]
+    pub account_code: Option<String>,
+    #[serde(default)]
+    pub account_name: Option<String>,
+    #[serde(default)]
+    pub debit: Money,
+    #[serde(default)]
+    pub credit: Money,
+    #[serde(default)]
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: Option<i32>,
+    pub date: String,
+    pub description: String,
+    #[serde(default)]
+    pub fund: Option<String>, // defaults to "General Fund"
+    #[serde(default)]
+    pub source: Option<String>, // "income", "expense" or "manual"
+    #[serde(default)]
+    pub source_id: Option<i32>,
+    pub lines: Vec<JournalLine>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrialBalanceRow {
+    pub account_id: i32,
+    pub code: String,
+    pub name: String,
+    pub type_: String,
+    pub budget_class: Option<String>,
+    pub debit: Money,
+    pub credit: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrialBalance {
+    pub as_of: Option<String>,
+    pub rows: Vec<TrialBalanceRow>,
+    pub total_debit: Money,
+    pub total_credit: Money,
+    pub balanced: bool,
+}
@@ -3,15 +3,23 @@ use serde::{Serialize, Deserialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Logbook {
     pub id: Option<i32>,
+    #[serde(default)]
+    pub official_id: Option<i32>, // officials.id; the name is matched when only that is sent
     pub official_name: String,
+    #[serde(default)]
+    pub official_role: Option<String>,
+    #[serde(default)]
+    pub official_section: Option<String>,
     pub date: String,
     pub time_in_am: Option<String>,
     pub time_out_am: Option<String>,
     pub time_in_pm: Option<String>,
     pub time_out_pm: Option<String>,
     pub remarks: Option<String>,
-    pub status: Option<String>,
-    pub total_hours: Option<f64>,
+    pub status: Option<String>, // Present, Late, Undertime, Absent, On Leave, Holiday or Rest Day
+    pub total_hours: Option<f64>, // computed from the punches, within office hours
+    #[serde(default)]
+    pub undertime_minutes: Option<i32>, // tardiness plus undertime against the schedule
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
 }
\ No newline at end of file
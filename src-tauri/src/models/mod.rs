@@ -9,4 +9,19 @@ pub mod certificate;
 pub mod user;
 pub mod household;
 pub mod barangay_map;
-pub mod logbook;
\ No newline at end of file
+pub mod logbook;
+pub mod certificate_type;
+pub mod ledger;
+pub mod money;
+pub mod budget;
+pub mod receipt;
+pub mod finance;
+pub mod voucher;
+pub mod cash_advance;
+pub mod attendance;
+pub mod holiday;
+pub mod leave;
+pub mod committee;
+pub mod signatory;
+pub mod recurrence;
+pub mod venue;
@@ -0,0 +1,236 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Peso amount held as whole centavos so sums never drift. Stored in INTEGER
+// columns; on the wire it stays a plain peso number (1234.5) for the frontend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    // Rounds to the nearest centavo, so 0.1 + 0.2 from JavaScript lands on 0.30
+    pub fn from_pesos(pesos: f64) -> Self {
+        Money((pesos * 100.0).round() as i64)
+    }
+
+    pub fn pesos(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    pub const fn abs(self) -> Self {
+        Money(self.0.abs())
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    // Reads typed amounts such as "10", "1,250.50", "PHP 50.00" or "₱ 75" without going
+    // through f64. More than two decimal places is rejected rather than rounded.
+    pub fn parse(text: &str) -> Option<Self> {
+        let cleaned: String = text
+            .trim_start_matches(|c: char| c.is_alphabetic() || c.is_whitespace() || c == '₱')
+            .chars()
+            .filter(|c| *c != ',' && !c.is_whitespace())
+            .collect();
+        let (negative, digits) = match cleaned.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, cleaned.as_str()),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || fraction.len() > 2
+            || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+        let fraction: i64 = format!("{:0<2}", fraction).parse().ok()?;
+        let centavos = whole.checked_mul(100)?.checked_add(fraction)?;
+        Some(Money(if negative { -centavos } else { centavos }))
+    }
+}
+
+// "1234.50", "-0.75"; pdf::document::money adds thousands separators
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let centavos = self.0.unsigned_abs();
+        write!(f, "{}{}.{:02}", sign, centavos / 100, centavos % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        Money(iter.map(|money| money.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+impl ToSql for Money {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0))
+    }
+}
+
+impl FromSql for Money {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(centavos) => Ok(Money(centavos)),
+            // columns the migration hasn't converted yet still hold pesos
+            ValueRef::Real(pesos) => Ok(Money::from_pesos(pesos)),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.pesos())
+    }
+}
+
+struct MoneyVisitor;
+
+impl Visitor<'_> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a peso amount")
+    }
+
+    fn visit_f64<E: de::Error>(self, pesos: f64) -> Result<Money, E> {
+        if pesos.is_finite() {
+            Ok(Money::from_pesos(pesos))
+        } else {
+            Err(E::custom("amount must be a finite number"))
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, pesos: i64) -> Result<Money, E> {
+        pesos.checked_mul(100).map(Money).ok_or_else(|| E::custom("amount is too large"))
+    }
+
+    fn visit_u64<E: de::Error>(self, pesos: u64) -> Result<Money, E> {
+        i64::try_from(pesos)
+            .ok()
+            .and_then(|pesos| pesos.checked_mul(100))
+            .map(Money)
+            .ok_or_else(|| E::custom("amount is too large"))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Money, E> {
+        Money::parse(text).ok_or_else(|| E::custom(format!("\"{}\" is not a valid amount", text)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Money;
+
+    fn centavos(text: &str) -> Option<i64> {
+        Money::parse(text).map(|money| money.0)
+    }
+
+    #[test]
+    fn parses_whole_and_decimal_amounts() {
+        assert_eq!(centavos("10"), Some(1000));
+        assert_eq!(centavos("10.5"), Some(1050));
+        assert_eq!(centavos("10.05"), Some(1005));
+        assert_eq!(centavos(".75"), Some(75));
+        assert_eq!(centavos("0"), Some(0));
+    }
+
+    #[test]
+    fn parses_signs() {
+        assert_eq!(centavos("-0.75"), Some(-75));
+        assert_eq!(centavos("-1,250"), Some(-125000));
+        assert_eq!(centavos("PHP -50"), Some(-5000));
+        assert_eq!(centavos("--5"), None);
+        assert_eq!(centavos("+5"), None);
+    }
+
+    #[test]
+    fn ignores_thousands_separators_and_currency() {
+        assert_eq!(centavos("1,250.50"), Some(125050));
+        assert_eq!(centavos("1,000,000"), Some(100000000));
+        assert_eq!(centavos("PHP 50.00"), Some(5000));
+        assert_eq!(centavos("₱ 75"), Some(7500));
+        assert_eq!(centavos(" 1 250 "), Some(125000));
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        assert_eq!(centavos(""), None);
+        assert_eq!(centavos("-"), None);
+        assert_eq!(centavos("."), None);
+        assert_eq!(centavos("12.345"), None);
+        assert_eq!(centavos("1.2.3"), None);
+        assert_eq!(centavos("12a"), None);
+        assert_eq!(centavos("99999999999999999999"), None);
+    }
+
+    #[test]
+    fn displays_with_two_decimals() {
+        assert_eq!(Money(123450).to_string(), "1234.50");
+        assert_eq!(Money(-75).to_string(), "-0.75");
+    }
+}
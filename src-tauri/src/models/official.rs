@@ -12,4 +12,19 @@ pub struct Official {
     pub zone: String,
     pub image: Option<String>,
     pub section: String,
-}
\ No newline at end of file
+}
+
+// A position an official held, or holds, over a term
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfficialTerm {
+    pub id: i32,
+    pub official_id: i32,
+    pub official_name: String,
+    pub role: String,
+    pub section: String,
+    pub term_start: Option<String>, // YYYY-MM-DD
+    pub term_end: Option<String>,   // scheduled end
+    pub ended_on: Option<String>,   // None while the official is in office
+    pub end_reason: Option<String>,
+    pub days_remaining: Option<i64>, // until the scheduled end; negative once it has passed
+}
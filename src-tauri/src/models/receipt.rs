@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+// A pad of pre-numbered official receipts issued to a collecting officer
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrBooklet {
+    pub id: Option<i32>,
+    pub series_start: i32,
+    pub series_end: i32, // inclusive
+    pub collecting_officer: String, // matched against Income.received_by
+    #[serde(default)]
+    pub issued_date: Option<String>,
+    pub active: bool,
+    #[serde(default)]
+    pub next_number: Option<i32>, // computed; None once the booklet is used up
+}
+
+// A spoiled or voided receipt that was never issued for an income
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelledReceipt {
+    pub id: i32,
+    pub booklet_id: i32,
+    pub or_number: i32,
+    pub reason: String,
+    pub cancelled_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateReceipt {
+    pub or_number: i32,
+    pub income_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrBookletReport {
+    pub booklet: OrBooklet,
+    pub issued: Vec<i32>,
+    pub unused: Vec<i32>,  // after the last number used
+    pub skipped: Vec<i32>, // gaps before it that were neither issued nor cancelled
+    pub duplicates: Vec<DuplicateReceipt>,
+    pub cancelled: Vec<CancelledReceipt>,
+}
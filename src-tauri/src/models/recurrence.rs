@@ -0,0 +1,386 @@
+use std::fmt;
+
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+// The part of an iCalendar RRULE (RFC 5545) events use: FREQ, INTERVAL, COUNT,
+// UNTIL, BYDAY and BYMONTHDAY, e.g. "FREQ=MONTHLY;BYDAY=1MO" for the first
+// Monday of every month. Weeks start on Monday.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>, // occurrences in all, the first included
+    pub until: Option<NaiveDate>,
+    pub by_day: Vec<(Option<i32>, Weekday)>, // e.g. (Some(-1), Fri) for the last Friday of the month
+    pub by_month_day: Vec<i32>,              // negative counts back from the month's end
+}
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+// "MO", "1MO" or "-1FR"
+fn parse_day(value: &str) -> Result<(Option<i32>, Weekday), String> {
+    let invalid = || format!("'{}' is not a weekday; use MO, TU, WE, TH, FR, SA or SU, optionally numbered as in 1MO or -1FR.", value);
+    // the weekday code is the last two bytes, so anything but ASCII can't be split safely
+    if !value.is_ascii() {
+        return Err(invalid());
+    }
+    let split = value.len().checked_sub(2).ok_or_else(invalid)?;
+    let (ordinal, code) = value.split_at(split);
+    let weekday = WEEKDAYS
+        .iter()
+        .find(|(name, _)| *name == code)
+        .map(|(_, weekday)| *weekday)
+        .ok_or_else(invalid)?;
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(ordinal.trim_start_matches('+').parse::<i32>().ok().filter(|n| *n != 0 && n.abs() <= 5).ok_or_else(invalid)?),
+    };
+    Ok((ordinal, weekday))
+}
+
+fn day_code(weekday: Weekday) -> &'static str {
+    WEEKDAYS.iter().find(|(_, day)| *day == weekday).map(|(name, _)| *name).unwrap_or("MO")
+}
+
+fn days_in_month(first: NaiveDate) -> u32 {
+    (first + Months::new(1) - Days::new(1)).day()
+}
+
+impl Recurrence {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim().to_uppercase();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(&rule);
+
+        let mut frequency = None;
+        let mut recurrence = Recurrence {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+        };
+        for part in rule.split(';').map(str::trim).filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' is not part of a recurrence rule; expected KEY=VALUE.", part))?;
+            match key {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Events can repeat DAILY, WEEKLY, MONTHLY or YEARLY, not '{}'.", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    recurrence.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| format!("INTERVAL must be a positive number, not '{}'.", value))?
+                }
+                "COUNT" => {
+                    recurrence.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| format!("COUNT must be a positive number, not '{}'.", value))?,
+                    )
+                }
+                "UNTIL" => {
+                    let date = value.get(0..8).unwrap_or(value);
+                    recurrence.until = Some(
+                        NaiveDate::parse_from_str(date, "%Y%m%d")
+                            .map_err(|_| format!("UNTIL must be a date such as 20261231, not '{}'.", value))?,
+                    )
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        recurrence.by_day.push(parse_day(day.trim())?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        let day = day
+                            .trim()
+                            .parse::<i32>()
+                            .ok()
+                            .filter(|day| *day != 0 && day.abs() <= 31)
+                            .ok_or_else(|| format!("BYMONTHDAY must be a day of the month, not '{}'.", day))?;
+                        recurrence.by_month_day.push(day);
+                    }
+                }
+                "WKST" => {}
+                _ => return Err(format!("'{}' is not supported in a recurrence rule.", key)),
+            }
+        }
+
+        recurrence.frequency = frequency.ok_or("A recurrence rule needs a FREQ, e.g. FREQ=MONTHLY.")?;
+        if recurrence.count.is_some() && recurrence.until.is_some() {
+            return Err("A recurrence rule can end with COUNT or UNTIL, not both.".into());
+        }
+        if recurrence.frequency != Frequency::Monthly {
+            if recurrence.by_day.iter().any(|(ordinal, _)| ordinal.is_some()) {
+                return Err("Numbered weekdays such as 1MO only apply to monthly rules.".into());
+            }
+            if !recurrence.by_month_day.is_empty() {
+                return Err("BYMONTHDAY only applies to monthly rules.".into());
+            }
+        }
+        if recurrence.frequency == Frequency::Yearly && !recurrence.by_day.is_empty() {
+            return Err("Yearly rules repeat on the date of the first occurrence and take no BYDAY.".into());
+        }
+        Ok(recurrence)
+    }
+
+    // Start of the `index`th period after the one holding `first`
+    fn period_start(&self, first: NaiveDate, index: u32) -> Option<NaiveDate> {
+        let step = self.interval.checked_mul(index)?;
+        match self.frequency {
+            Frequency::Daily => first.checked_add_days(Days::new(step as u64)),
+            Frequency::Weekly => {
+                let monday = first - Days::new(first.weekday().num_days_from_monday() as u64);
+                monday.checked_add_days(Days::new(step as u64 * 7))
+            }
+            Frequency::Monthly => first.with_day(1)?.checked_add_months(Months::new(step)),
+            Frequency::Yearly => NaiveDate::from_ymd_opt(first.year().checked_add(step as i32)?, 1, 1),
+        }
+    }
+
+    // Dates the rule picks within the period starting at `start`
+    fn period_dates(&self, first: NaiveDate, start: NaiveDate) -> Vec<NaiveDate> {
+        let weekdays: Vec<Weekday> = self.by_day.iter().map(|(_, weekday)| *weekday).collect();
+        let mut dates = match self.frequency {
+            Frequency::Daily if weekdays.is_empty() || weekdays.contains(&start.weekday()) => vec![start],
+            Frequency::Daily => Vec::new(),
+            Frequency::Weekly => {
+                let weekdays = if weekdays.is_empty() { vec![first.weekday()] } else { weekdays };
+                weekdays
+                    .iter()
+                    .map(|weekday| start + Days::new(weekday.num_days_from_monday() as u64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let last_day = days_in_month(start);
+                let month: Vec<NaiveDate> = (1..=last_day).filter_map(|day| start.with_day(day)).collect();
+                if !self.by_day.is_empty() {
+                    let mut dates = Vec::new();
+                    for (ordinal, weekday) in &self.by_day {
+                        let matching: Vec<NaiveDate> = month.iter().copied().filter(|date| date.weekday() == *weekday).collect();
+                        match ordinal {
+                            Some(n) if *n > 0 => dates.extend(matching.get(*n as usize - 1)),
+                            Some(n) => dates.extend(matching.len().checked_sub(n.unsigned_abs() as usize).and_then(|index| matching.get(index))),
+                            None => dates.extend(matching),
+                        }
+                    }
+                    dates
+                } else if !self.by_month_day.is_empty() {
+                    self.by_month_day
+                        .iter()
+                        .filter_map(|day| {
+                            let day = if *day > 0 { *day } else { last_day as i32 + 1 + day };
+                            u32::try_from(day).ok().and_then(|day| start.with_day(day))
+                        })
+                        .collect()
+                } else {
+                    // months without the day, e.g. the 31st, are skipped
+                    start.with_day(first.day()).into_iter().collect()
+                }
+            }
+            Frequency::Yearly => NaiveDate::from_ymd_opt(start.year(), first.month(), first.day()).into_iter().collect(),
+        };
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    // Start times of every occurrence from `start` through the given date. The
+    // first occurrence is always `start` itself, as with DTSTART.
+    pub fn occurrences(&self, start: NaiveDateTime, through: NaiveDate) -> Vec<NaiveDateTime> {
+        let first = start.date();
+        let last = self.until.map_or(through, |until| until.min(through));
+        let limit = self.count.map_or(usize::MAX, |count| count as usize);
+
+        let mut dates = vec![first];
+        let mut index = 0;
+        while dates.len() < limit {
+            let Some(period) = self.period_start(first, index) else {
+                break;
+            };
+            if period > last {
+                break;
+            }
+            for date in self.period_dates(first, period) {
+                if date > first && date <= last && dates.len() < limit {
+                    dates.push(date);
+                }
+            }
+            index += 1;
+        }
+        dates.into_iter().filter(|date| *date <= last).map(|date| date.and_time(start.time())).collect()
+    }
+}
+
+// Written back in a fixed order, e.g. "FREQ=MONTHLY;BYDAY=1MO;COUNT=12"
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|(ordinal, weekday)| format!("{}{}", ordinal.map(|n| n.to_string()).unwrap_or_default(), day_code(*weekday)))
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|day| day.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    // Dates of every occurrence of `rule` starting at 09:00 on `start`
+    fn dates(rule: &str, start: &str, through: &str) -> Vec<String> {
+        let start = date(start).and_hms_opt(9, 0, 0).unwrap();
+        Recurrence::parse(rule)
+            .unwrap()
+            .occurrences(start, date(through))
+            .into_iter()
+            .inspect(|occurrence| assert_eq!(occurrence.time(), start.time()))
+            .map(|occurrence| occurrence.date().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parses_numbered_weekdays() {
+        let rule = Recurrence::parse("FREQ=MONTHLY;BYDAY=1MO,+2TU,-1FR").unwrap();
+        assert_eq!(
+            rule.by_day,
+            vec![(Some(1), Weekday::Mon), (Some(2), Weekday::Tue), (Some(-1), Weekday::Fri)]
+        );
+        assert_eq!(rule.to_string(), "FREQ=MONTHLY;BYDAY=1MO,2TU,-1FR");
+    }
+
+    #[test]
+    fn rejects_bad_weekdays() {
+        for rule in [
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYDAY=1XX",
+            "FREQ=MONTHLY;BYDAY=1MÖ",
+            "FREQ=WEEKLY;BYDAY=Ö",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=YEARLY;BYDAY=MO",
+        ] {
+            assert!(Recurrence::parse(rule).is_err(), "{} should be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn parses_count_and_until() {
+        let rule = Recurrence::parse("rrule:freq=weekly;interval=2;count=12").unwrap();
+        assert_eq!((rule.frequency, rule.interval, rule.count, rule.until), (Frequency::Weekly, 2, Some(12), None));
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;COUNT=12");
+
+        let rule = Recurrence::parse("FREQ=DAILY;UNTIL=20261231T235959Z").unwrap();
+        assert_eq!(rule.until, Some(date("2026-12-31")));
+        assert_eq!(rule.to_string(), "FREQ=DAILY;UNTIL=20261231");
+
+        for rule in ["FREQ=DAILY;COUNT=3;UNTIL=20261231", "FREQ=DAILY;COUNT=0", "FREQ=DAILY;UNTIL=tomorrow", "COUNT=3"] {
+            assert!(Recurrence::parse(rule).is_err(), "{} should be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn first_and_last_weekday_of_the_month() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYDAY=1MO;COUNT=3", "2026-01-05", "2027-12-31"),
+            ["2026-01-05", "2026-02-02", "2026-03-02"]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", "2026-01-30", "2027-12-31"),
+            ["2026-01-30", "2026-02-27", "2026-03-27"]
+        );
+    }
+
+    #[test]
+    fn count_includes_the_first_occurrence() {
+        // the start is a Monday but the rule picks Wednesdays
+        assert_eq!(
+            dates("FREQ=WEEKLY;BYDAY=WE;COUNT=3", "2026-01-05", "2027-12-31"),
+            ["2026-01-05", "2026-01-07", "2026-01-14"]
+        );
+    }
+
+    #[test]
+    fn until_is_inclusive() {
+        assert_eq!(
+            dates("FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20260114", "2026-01-05", "2027-12-31"),
+            ["2026-01-05", "2026-01-07", "2026-01-12", "2026-01-14"]
+        );
+    }
+
+    #[test]
+    fn stops_at_the_requested_date() {
+        assert_eq!(
+            dates("FREQ=DAILY;COUNT=10", "2026-01-05", "2026-01-07"),
+            ["2026-01-05", "2026-01-06", "2026-01-07"]
+        );
+    }
+
+    #[test]
+    fn months_without_the_day_are_skipped() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;COUNT=3", "2026-01-31", "2027-12-31"),
+            ["2026-01-31", "2026-03-31", "2026-05-31"]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=-1;COUNT=3", "2026-01-31", "2027-12-31"),
+            ["2026-01-31", "2026-02-28", "2026-03-31"]
+        );
+    }
+}
@@ -12,4 +12,19 @@ pub struct Settings {
     pub email: String,
     pub logo: Option<String>,
     pub logo_municipality: Option<String>,
+    #[serde(default)]
+    pub clearance_blotter_policy: Option<String>, // "warn" or "block"
+    #[serde(default)]
+    pub budget_overspend_policy: Option<String>, // "warn" or "block"
+    // office schedule the logbook measures attendance against, as "HH:MM"
+    #[serde(default)]
+    pub office_am_in: Option<String>,
+    #[serde(default)]
+    pub office_am_out: Option<String>,
+    #[serde(default)]
+    pub office_pm_in: Option<String>,
+    #[serde(default)]
+    pub office_pm_out: Option<String>,
+    #[serde(default)]
+    pub grace_minutes: Option<i32>, // minutes after the start still counted on time
 }
\ No newline at end of file
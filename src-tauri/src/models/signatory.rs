@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+// A signature block on a document, filled by whoever holds `role`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signatory {
+    pub id: Option<i32>,
+    pub document_type: String,
+    #[serde(default)]
+    pub slot: String, // assigned on insert
+    #[serde(default)]
+    pub label: String,
+    pub role: String,
+    pub title: String,
+    #[serde(default)]
+    pub sort_order: i32,
+    #[serde(default)]
+    pub built_in: bool,
+    #[serde(default)]
+    pub oic_official_id: Option<i32>, // officer-in-charge signing in the holder's absence
+    #[serde(default)]
+    pub oic_official_name: Option<String>,
+    #[serde(default)]
+    pub oic_from: Option<String>,
+    #[serde(default)]
+    pub oic_to: Option<String>,
+}
+
+// A signature block as printed on a particular date
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Signer {
+    pub slot: String,
+    pub label: String,
+    pub name: String,
+    pub position: String,
+    pub official_id: Option<i32>,
+    pub signature: Option<String>, // data URL of the official's captured signature
+    pub oic: bool,
+}
+
+impl Signer {
+    // A block that is not tied to an office, such as the payee on a voucher
+    pub fn new(label: &str, name: &str, position: &str) -> Self {
+        Signer {
+            label: label.to_string(),
+            name: name.to_string(),
+            position: position.to_string(),
+            ..Default::default()
+        }
+    }
+}
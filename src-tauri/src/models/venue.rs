@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+// A place events can be booked into, such as the barangay hall or covered court
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Venue {
+    pub id: Option<i32>,
+    pub name: String,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub capacity: Option<i32>,
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::money::Money;
+
+// Disbursement Voucher behind an expense. It is prepared by the treasurer,
+// certified and approved by the Punong Barangay, then marked paid.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisbursementVoucher {
+    pub id: Option<i32>,
+    pub expense_id: i32,
+    #[serde(default)]
+    pub dv_number: Option<String>, // assigned when the voucher is prepared
+    #[serde(default)]
+    pub status: Option<String>, // Prepared, Certified, Approved, Paid or Rejected
+    #[serde(default)]
+    pub particulars: Option<String>,
+    pub payment_mode: String, // "Cash" or "Check"
+    #[serde(default)]
+    pub check_number: Option<String>,
+    #[serde(default)]
+    pub check_date: Option<String>,
+    #[serde(default)]
+    pub bank: Option<String>,
+    #[serde(default)]
+    pub prepared_by: Option<i32>, // officials.id; defaults to the current treasurer
+    #[serde(default)]
+    pub prepared_by_name: Option<String>,
+    #[serde(default)]
+    pub prepared_at: Option<String>,
+    #[serde(default)]
+    pub certified_by: Option<i32>,
+    #[serde(default)]
+    pub certified_by_name: Option<String>,
+    #[serde(default)]
+    pub certified_at: Option<String>,
+    #[serde(default)]
+    pub approved_by: Option<i32>,
+    #[serde(default)]
+    pub approved_by_name: Option<String>,
+    #[serde(default)]
+    pub approved_at: Option<String>,
+    #[serde(default)]
+    pub paid_at: Option<String>,
+    #[serde(default)]
+    pub rejected_by: Option<i32>,
+    #[serde(default)]
+    pub rejected_by_name: Option<String>,
+    #[serde(default)]
+    pub rejected_at: Option<String>,
+    #[serde(default)]
+    pub rejection_reason: Option<String>,
+    // from the expense, for listing
+    #[serde(default)]
+    pub payee: Option<String>,
+    #[serde(default)]
+    pub amount: Option<Money>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub expense_date: Option<String>,
+}
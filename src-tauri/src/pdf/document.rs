@@ -0,0 +1,680 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use image::{DynamicImage, Rgb, RgbImage};
+use printpdf::path::PaintMode;
+use printpdf::{
+    Color, Greyscale, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerIndex, PdfLayerReference, PdfPageIndex, Point, Rect, Rgb as PdfRgb,
+};
+use ttf_parser::Face;
+
+use crate::models::money::Money;
+use crate::models::settings::Settings;
+use crate::models::signatory::Signer;
+
+const REGULAR_TTF: &[u8] = include_bytes!("../../../src/assets/fonts/RedHatDisplay-Regular.ttf");
+const BOLD_TTF: &[u8] = include_bytes!("../../../src/assets/fonts/RedHatDisplay-Bold.ttf");
+
+const PT_TO_MM: f32 = 0.352_778;
+const A4_SHORT: f32 = 210.0;
+const A4_LONG: f32 = 297.0;
+
+pub const MARGIN: f32 = 15.0;
+const FOOTER_HEIGHT: f32 = 12.0;
+const CELL_PADDING: f32 = 1.8;
+const TABLE_SIZE: f32 = 9.0;
+const LOGO_WIDTH: f32 = 22.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RowStyle {
+    Header,
+    Body,
+    Striped,
+    Section,
+    Subtotal,
+    Total,
+}
+
+impl RowStyle {
+    fn shade(self) -> Option<f32> {
+        match self {
+            RowStyle::Header => Some(0.88),
+            RowStyle::Body => None,
+            RowStyle::Striped => Some(0.976),
+            RowStyle::Section => Some(0.95),
+            RowStyle::Subtotal => Some(0.965),
+            RowStyle::Total => Some(0.93),
+        }
+    }
+
+    fn bold(self) -> bool {
+        !matches!(self, RowStyle::Body | RowStyle::Striped)
+    }
+}
+
+// Row of a grouped table. Section headings span every column; subtotals and
+// totals print in bold.
+pub enum TableRow {
+    Section(String),
+    Body(Vec<String>),
+    Subtotal(Vec<String>),
+    Total(Vec<String>),
+}
+
+pub struct Column {
+    pub title: &'static str,
+    pub width: f32, // relative to the other columns
+    pub align: Align,
+}
+
+impl Column {
+    pub const fn new(title: &'static str, width: f32) -> Self {
+        Column { title, width, align: Align::Left }
+    }
+
+    pub const fn right(title: &'static str, width: f32) -> Self {
+        Column { title, width, align: Align::Right }
+    }
+}
+
+// Header block printed on top of every report, taken from Settings
+pub struct Letterhead {
+    pub barangay: String,
+    pub municipality: String,
+    pub province: String,
+    pub contact: String,
+    pub logo: Option<DynamicImage>,
+    pub logo_municipality: Option<DynamicImage>,
+}
+
+impl Letterhead {
+    pub fn from_settings(settings: &Settings) -> Self {
+        let contact = [settings.phone_number.trim(), settings.email.trim()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("  |  ");
+
+        Letterhead {
+            barangay: settings.barangay.trim().to_string(),
+            municipality: settings.municipality.trim().to_string(),
+            province: settings.province.trim().to_string(),
+            contact,
+            logo: settings.logo.as_deref().and_then(decode_image),
+            logo_municipality: settings.logo_municipality.as_deref().and_then(decode_image),
+        }
+    }
+}
+
+// Logos are stored as data URLs by the Settings page. Anything the image crate
+// can't read is skipped rather than failing the whole report.
+pub fn decode_image(data: &str) -> Option<DynamicImage> {
+    let data = data.trim();
+    if data.is_empty() {
+        return None;
+    }
+    let encoded = match data.find("base64,") {
+        Some(index) => &data[index + "base64,".len()..],
+        None => data,
+    };
+    let bytes = STANDARD.decode(encoded).ok()?;
+    image::load_from_memory(&bytes).ok().map(flatten)
+}
+
+pub fn decode_png(bytes: &[u8]) -> Result<DynamicImage, String> {
+    image::load_from_memory(bytes).map(flatten).map_err(|e| e.to_string())
+}
+
+// Composites transparent pixels onto white so logos print the same in every viewer
+fn flatten(image: DynamicImage) -> DynamicImage {
+    let rgba = image.to_rgba8();
+    let flat = RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    });
+    DynamicImage::ImageRgb8(flat)
+}
+
+// 1234.5 -> "1,234.50"
+pub fn money(amount: Money) -> String {
+    let formatted = amount.abs().to_string();
+    let (whole, cents) = formatted.split_once('.').unwrap_or((&formatted, "00"));
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount.is_negative() { "-" } else { "" };
+    format!("{}{}.{}", sign, grouped, cents)
+}
+
+// Dates arrive either as plain YYYY-MM-DD or as the UTC timestamps the webview sends
+pub fn local_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Local).date_naive())
+        .ok()
+        .or_else(|| value.get(0..10).and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()))
+}
+
+// Date and time as entered: a UTC timestamp from the webview, or local
+// "YYYY-MM-DDTHH:MM" with or without seconds
+pub fn local_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Local).naive_local())
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        })
+}
+
+// "2025-07-21" -> "July 21, 2025"; unparseable values are printed as stored
+pub fn long_date(value: &str) -> String {
+    local_date(value)
+        .map(|date| date.format("%B %-d, %Y").to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn grey(level: f32) -> Color {
+    Color::Greyscale(Greyscale::new(level, None))
+}
+
+pub fn red() -> Color {
+    Color::Rgb(PdfRgb::new(0.75, 0.1, 0.1, None))
+}
+
+pub struct PdfReport {
+    doc: PdfDocumentReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    regular_face: Face<'static>,
+    bold_face: Face<'static>,
+    width: f32,
+    height: f32,
+    pages: Vec<(PdfPageIndex, PdfLayerIndex)>,
+    layer: PdfLayerReference,
+    y: f32, // top of the next block, measured from the bottom edge like PDF itself
+    letterhead: Letterhead,
+    footer: Option<String>,
+    table_size: f32,
+    cell_padding: f32,
+}
+
+impl PdfReport {
+    pub fn new(title: &str, orientation: Orientation, letterhead: Letterhead) -> Result<Self, String> {
+        let (width, height) = match orientation {
+            Orientation::Portrait => (A4_SHORT, A4_LONG),
+            Orientation::Landscape => (A4_LONG, A4_SHORT),
+        };
+
+        let (doc, page, layer) = PdfDocument::new(title, Mm(width), Mm(height), "Layer 1");
+        let regular = doc.add_external_font(REGULAR_TTF).map_err(|e| e.to_string())?;
+        let bold = doc.add_external_font(BOLD_TTF).map_err(|e| e.to_string())?;
+        let regular_face = Face::parse(REGULAR_TTF, 0).map_err(|e| e.to_string())?;
+        let bold_face = Face::parse(BOLD_TTF, 0).map_err(|e| e.to_string())?;
+        let current = doc.get_page(page).get_layer(layer);
+
+        Ok(PdfReport {
+            doc,
+            regular,
+            bold,
+            regular_face,
+            bold_face,
+            width,
+            height,
+            pages: vec![(page, layer)],
+            layer: current,
+            y: height - MARGIN,
+            letterhead,
+            footer: Some(format!("Generated {}", Local::now().format("%B %-d, %Y %-I:%M %p"))),
+            table_size: TABLE_SIZE,
+            cell_padding: CELL_PADDING,
+        })
+    }
+
+    // Certificates carry their own issue details, so they go without the generated/page footer
+    pub fn without_footer(mut self) -> Self {
+        self.footer = None;
+        self
+    }
+
+    // Smaller table text and padding, for forms that have to fit a month on one page
+    pub fn compact_tables(mut self) -> Self {
+        self.table_size = 7.5;
+        self.cell_padding = 0.9;
+        self
+    }
+
+    pub fn content_width(&self) -> f32 {
+        self.width - MARGIN * 2.0
+    }
+
+    // Places the next block at an absolute height, for content pinned to the page foot
+    pub fn move_to(&mut self, y: f32) {
+        self.y = y;
+    }
+
+    fn bottom(&self) -> f32 {
+        if self.footer.is_some() {
+            MARGIN + FOOTER_HEIGHT
+        } else {
+            MARGIN
+        }
+    }
+
+    pub fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(self.width), Mm(self.height), "Layer 1");
+        self.pages.push((page, layer));
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = self.height - MARGIN;
+    }
+
+    // Starts a new page when fewer than `needed` millimetres are left; returns true if it did
+    pub fn ensure_space(&mut self, needed: f32) -> bool {
+        if self.y - needed < self.bottom() {
+            self.new_page();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn space(&mut self, mm: f32) {
+        self.y -= mm;
+    }
+
+    fn font(&self, bold: bool) -> &IndirectFontRef {
+        if bold {
+            &self.bold
+        } else {
+            &self.regular
+        }
+    }
+
+    pub fn text_width(&self, text: &str, size: f32, bold: bool) -> f32 {
+        let face = if bold { &self.bold_face } else { &self.regular_face };
+        let units_per_em = face.units_per_em() as f32;
+        let units: f32 = text
+            .chars()
+            .map(|c| {
+                face.glyph_index(c)
+                    .and_then(|glyph| face.glyph_hor_advance(glyph))
+                    .unwrap_or(face.units_per_em() / 2) as f32
+            })
+            .sum();
+        units / units_per_em * size * PT_TO_MM
+    }
+
+    fn line_height(size: f32) -> f32 {
+        size * PT_TO_MM * 1.35
+    }
+
+    // Greedy word wrap; words longer than the line are broken by character
+    pub fn wrap(&self, text: &str, size: f32, bold: bool, max_width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for source_line in text.lines() {
+            let mut current = String::new();
+            for word in source_line.split_whitespace() {
+                let candidate = if current.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", current, word)
+                };
+                if self.text_width(&candidate, size, bold) <= max_width {
+                    current = candidate;
+                    continue;
+                }
+                if !current.is_empty() {
+                    lines.push(std::mem::take(&mut current));
+                }
+                for c in word.chars() {
+                    current.push(c);
+                    if self.text_width(&current, size, bold) > max_width && current.chars().count() > 1 {
+                        current.pop();
+                        lines.push(std::mem::take(&mut current));
+                        current.push(c);
+                    }
+                }
+            }
+            lines.push(current);
+        }
+
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines
+    }
+
+    fn draw_text(&self, text: &str, size: f32, bold: bool, x: f32, baseline: f32) {
+        if !text.is_empty() {
+            self.layer.use_text(text, size, Mm(x), Mm(baseline), self.font(bold));
+        }
+    }
+
+    fn aligned_x(&self, text: &str, size: f32, bold: bool, left: f32, width: f32, align: Align) -> f32 {
+        match align {
+            Align::Left | Align::Justify => left,
+            Align::Center => left + (width - self.text_width(text, size, bold)) / 2.0,
+            Align::Right => left + width - self.text_width(text, size, bold),
+        }
+    }
+
+    // Draws one line spread across `width` by widening the gaps between words
+    fn draw_justified(&self, text: &str, size: f32, bold: bool, left: f32, width: f32, baseline: f32) {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.len() < 2 {
+            self.draw_text(text, size, bold, left, baseline);
+            return;
+        }
+        let used: f32 = words.iter().map(|word| self.text_width(word, size, bold)).sum();
+        let gap = (width - used) / (words.len() - 1) as f32;
+        let mut x = left;
+        for word in words {
+            self.draw_text(word, size, bold, x, baseline);
+            x += self.text_width(word, size, bold) + gap;
+        }
+    }
+
+    pub fn text(&mut self, text: &str, size: f32, bold: bool, align: Align) {
+        self.paragraph_at(text, size, bold, align, MARGIN, self.content_width());
+    }
+
+    pub fn paragraph(&mut self, text: &str, size: f32, align: Align) {
+        self.paragraph_at(text, size, false, align, MARGIN, self.content_width());
+    }
+
+    pub fn paragraph_at(&mut self, text: &str, size: f32, bold: bool, align: Align, left: f32, width: f32) {
+        let lines = self.wrap(text, size, bold, width);
+        let line_height = Self::line_height(size);
+        let count = lines.len();
+
+        for (i, line) in lines.iter().enumerate() {
+            self.ensure_space(line_height);
+            let baseline = self.y - size * PT_TO_MM;
+            if align == Align::Justify && i + 1 < count {
+                self.draw_justified(line, size, bold, left, width, baseline);
+            } else {
+                let x = self.aligned_x(line, size, bold, left, width, align);
+                self.draw_text(line, size, bold, x, baseline);
+            }
+            self.y -= line_height;
+        }
+    }
+
+    pub fn set_color(&self, color: Color) {
+        self.layer.set_fill_color(color);
+    }
+
+    pub fn reset_color(&self) {
+        self.layer.set_fill_color(grey(0.0));
+    }
+
+    pub fn rule(&mut self) {
+        self.layer.set_outline_color(grey(0.0));
+        self.layer.set_outline_thickness(0.8);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(self.y)), false),
+                (Point::new(Mm(self.width - MARGIN), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+        self.y -= 2.0;
+    }
+
+    // Draws the image with its top-left corner at (x, top) and returns its height
+    pub fn image(&self, image: &DynamicImage, x: f32, top: f32, width: f32) -> f32 {
+        let dpi = image.width() as f32 * 25.4 / width;
+        let height = image.height() as f32 * 25.4 / dpi;
+        Image::from_dynamic_image(image).add_to_layer(
+            self.layer.clone(),
+            ImageTransform {
+                translate_x: Some(Mm(x)),
+                translate_y: Some(Mm(top - height)),
+                dpi: Some(dpi),
+                ..Default::default()
+            },
+        );
+        height
+    }
+
+    pub fn letterhead(&mut self) {
+        let top = self.y;
+        let mut logo_bottom = top;
+
+        if let Some(logo) = &self.letterhead.logo {
+            logo_bottom = logo_bottom.min(top - self.image(logo, MARGIN + 5.0, top, LOGO_WIDTH));
+        }
+        if let Some(logo) = &self.letterhead.logo_municipality {
+            let x = self.width - MARGIN - 5.0 - LOGO_WIDTH;
+            logo_bottom = logo_bottom.min(top - self.image(logo, x, top, LOGO_WIDTH));
+        }
+
+        self.text("Republic of the Philippines", 11.0, false, Align::Center);
+        if !self.letterhead.province.is_empty() {
+            let line = format!("Province of {}", self.letterhead.province);
+            self.text(&line, 11.0, false, Align::Center);
+        }
+        if !self.letterhead.municipality.is_empty() {
+            let line = format!("Municipality of {}", self.letterhead.municipality);
+            self.text(&line, 11.0, false, Align::Center);
+        }
+        if !self.letterhead.barangay.is_empty() {
+            let line = format!("BARANGAY {}", self.letterhead.barangay.to_uppercase());
+            self.text(&line, 13.0, true, Align::Center);
+        }
+        if !self.letterhead.contact.is_empty() {
+            let line = self.letterhead.contact.clone();
+            self.text(&line, 9.0, false, Align::Center);
+        }
+
+        self.y = self.y.min(logo_bottom) - 2.0;
+        self.rule();
+        self.space(4.0);
+    }
+
+    fn fill_rect(&self, left: f32, bottom: f32, right: f32, top: f32, shade: f32) {
+        self.layer.set_fill_color(grey(shade));
+        self.layer
+            .add_rect(Rect::new(Mm(left), Mm(bottom), Mm(right), Mm(top)).with_mode(PaintMode::Fill));
+        self.reset_color();
+    }
+
+    fn stroke_rect(&self, left: f32, bottom: f32, right: f32, top: f32) {
+        self.layer.set_outline_color(grey(0.0));
+        self.layer.set_outline_thickness(0.5);
+        self.layer
+            .add_rect(Rect::new(Mm(left), Mm(bottom), Mm(right), Mm(top)).with_mode(PaintMode::Stroke));
+    }
+
+    fn table_row(&mut self, columns: &[Column], cells: &[String], widths: &[f32], size: f32, style: RowStyle) {
+        let bold = style.bold();
+        let wrapped: Vec<Vec<String>> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| self.wrap(cell, size, bold, width - self.cell_padding * 2.0))
+            .collect();
+        let line_height = Self::line_height(size);
+        let lines = wrapped.iter().map(Vec::len).max().unwrap_or(1);
+        let height = self.cell_height(lines, size);
+
+        let top = self.y;
+        let bottom = top - height;
+        if let Some(shade) = style.shade() {
+            self.fill_rect(MARGIN, bottom, MARGIN + widths.iter().sum::<f32>(), top, shade);
+        }
+
+        let mut left = MARGIN;
+        for ((column, lines), width) in columns.iter().zip(&wrapped).zip(widths) {
+            self.stroke_rect(left, bottom, left + width, top);
+            let align = if style == RowStyle::Header { Align::Center } else { column.align };
+            for (i, line) in lines.iter().enumerate() {
+                let baseline = top - self.cell_padding - size * PT_TO_MM - i as f32 * line_height;
+                let x = self.aligned_x(line, size, bold, left + self.cell_padding, width - self.cell_padding * 2.0, align);
+                self.draw_text(line, size, bold, x, baseline);
+            }
+            left += width;
+        }
+
+        self.y = bottom;
+    }
+
+    fn cell_height(&self, lines: usize, size: f32) -> f32 {
+        (lines - 1) as f32 * Self::line_height(size) + size * PT_TO_MM * 1.25 + self.cell_padding * 2.0
+    }
+
+    fn row_height(&self, cells: &[String], widths: &[f32], size: f32, bold: bool) -> f32 {
+        let lines = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| self.wrap(cell, size, bold, width - self.cell_padding * 2.0).len())
+            .max()
+            .unwrap_or(1);
+        self.cell_height(lines, size)
+    }
+
+    // Bordered table with a shaded header that repeats on every page it spans.
+    // Rows in `totals` are printed in bold after the body.
+    pub fn table(&mut self, columns: &[Column], rows: &[Vec<String>], totals: &[Vec<String>]) {
+        let rows: Vec<TableRow> = rows
+            .iter()
+            .cloned()
+            .map(TableRow::Body)
+            .chain(totals.iter().cloned().map(TableRow::Total))
+            .collect();
+        self.grouped_table(columns, &rows);
+    }
+
+    // Like `table`, with section headings and subtotals between the rows.
+    // Zebra striping restarts under every section.
+    pub fn grouped_table(&mut self, columns: &[Column], rows: &[TableRow]) {
+        let size = self.table_size;
+        const SPAN: [Column; 1] = [Column::new("", 1.0)];
+
+        let total_weight: f32 = columns.iter().map(|column| column.width).sum();
+        let widths: Vec<f32> = columns
+            .iter()
+            .map(|column| column.width / total_weight * self.content_width())
+            .collect();
+        let full_width = [self.content_width()];
+        let titles: Vec<String> = columns.iter().map(|column| column.title.to_string()).collect();
+        let header_height = self.row_height(&titles, &widths, size, true);
+
+        let mut striped = true;
+        let mut header_drawn = false;
+        for row in rows {
+            let (style, cells, row_columns, row_widths): (RowStyle, &[String], &[Column], &[f32]) = match row {
+                TableRow::Section(title) => {
+                    striped = true;
+                    (RowStyle::Section, std::slice::from_ref(title), &SPAN, &full_width)
+                }
+                TableRow::Body(cells) => {
+                    let style = if striped { RowStyle::Striped } else { RowStyle::Body };
+                    striped = !striped;
+                    (style, cells, columns, &widths)
+                }
+                TableRow::Subtotal(cells) => (RowStyle::Subtotal, cells, columns, &widths),
+                TableRow::Total(cells) => (RowStyle::Total, cells, columns, &widths),
+            };
+
+            let height = self.row_height(cells, row_widths, size, style.bold());
+            // keep a section heading on the same page as its first row
+            let needed = if style == RowStyle::Section { height * 2.0 } else { height };
+            if !header_drawn {
+                self.ensure_space(header_height + needed);
+                self.table_row(columns, &titles, &widths, size, RowStyle::Header);
+                header_drawn = true;
+            } else if self.ensure_space(needed) {
+                self.table_row(columns, &titles, &widths, size, RowStyle::Header);
+            }
+            self.table_row(row_columns, cells, row_widths, size, style);
+        }
+
+        if !header_drawn {
+            self.ensure_space(header_height);
+            self.table_row(columns, &titles, &widths, size, RowStyle::Header);
+        }
+    }
+
+    // Name over a line, with the position underneath, centred on `center_x`. A
+    // captured signature is drawn over the name.
+    pub fn signature(&mut self, signer: &Signer, center_x: f32) {
+        const WIDTH: f32 = 70.0;
+        const IMAGE_HEIGHT: f32 = 16.0;
+        self.ensure_space(24.0);
+        if let Some(image) = signer.signature.as_deref().and_then(decode_image) {
+            let width = (IMAGE_HEIGHT * image.width() as f32 / image.height().max(1) as f32).min(WIDTH * 0.7);
+            self.image(&image, center_x - width / 2.0, self.y, width);
+        }
+        self.space(14.0);
+        let left = center_x - WIDTH / 2.0;
+        self.paragraph_at(&signer.name.to_uppercase(), 11.0, true, Align::Center, left, WIDTH);
+        self.layer.set_outline_color(grey(0.0));
+        self.layer.set_outline_thickness(0.5);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(left), Mm(self.y + 0.8)), false),
+                (Point::new(Mm(left + WIDTH), Mm(self.y + 0.8)), false),
+            ],
+            is_closed: false,
+        });
+        self.paragraph_at(&signer.position, 10.0, false, Align::Center, left, WIDTH);
+    }
+
+    // Signature blocks side by side, each under its label, e.g. "Prepared by:"
+    pub fn signatures(&mut self, blocks: &[Signer]) {
+        const WIDTH: f32 = 70.0;
+        self.ensure_space(34.0);
+        let top = self.y;
+        let mut lowest = top;
+        for (index, signer) in blocks.iter().enumerate() {
+            let center = MARGIN + self.content_width() * (2 * index + 1) as f32 / (2 * blocks.len()) as f32;
+            self.y = top;
+            self.paragraph_at(&signer.label, 9.0, false, Align::Left, center - WIDTH / 2.0, WIDTH);
+            self.signature(signer, center);
+            lowest = lowest.min(self.y);
+        }
+        self.y = lowest;
+    }
+
+    pub fn right_column_center(&self) -> f32 {
+        self.width - MARGIN - 40.0
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        if let Some(footer) = &self.footer {
+            let total = self.pages.len();
+            for (number, (page, layer)) in self.pages.iter().enumerate() {
+                let layer = self.doc.get_page(*page).get_layer(*layer);
+                layer.set_fill_color(grey(0.3));
+                layer.use_text(footer.as_str(), 8.0, Mm(MARGIN), Mm(MARGIN - 4.0), &self.regular);
+                let label = format!("Page {} of {}", number + 1, total);
+                let x = self.width - MARGIN - self.text_width(&label, 8.0, false);
+                layer.use_text(label, 8.0, Mm(x), Mm(MARGIN - 4.0), &self.regular);
+            }
+        }
+        self.doc.save_to_bytes().map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,80 @@
+pub mod document;
+pub mod reports;
+pub mod spreadsheet;
+pub mod statements;
+
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::commands::settings::fetch_settings_command;
+use crate::models::certificate::Certificate;
+use crate::models::settings::Settings;
+use reports::{certificates_pdf, render_report, ReportParams};
+use spreadsheet::statement_xlsx;
+use statements::build_statement;
+
+fn write_file(output: &Path, bytes: Vec<u8>) -> Result<(), String> {
+    if let Some(parent) = output.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(output, bytes).map_err(|e| e.to_string())
+}
+
+pub fn write_report(report: &str, params: Option<serde_json::Value>, output: &Path) -> Result<(), String> {
+    let params: ReportParams = match params {
+        Some(value) if !value.is_null() => serde_json::from_value(value).map_err(|e| e.to_string())?,
+        _ => ReportParams::default(),
+    };
+    // the financial statements can also be written as a workbook
+    if output.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx")) {
+        let settings = fetch_settings_command()?;
+        return write_file(output, statement_xlsx(&build_statement(report, &params)?, &settings)?);
+    }
+    write_file(output, render_report(report, &params)?)
+}
+
+// "pdf" merges every certificate into one printable file; "zip" packs one PDF
+// per certificate, named after its control number
+pub fn write_certificate_batch(conn: &Connection, settings: &Settings, certs: &[Certificate], format: &str, output: &Path) -> Result<(), String> {
+    if format != "zip" {
+        return write_file(output, certificates_pdf(conn, settings, certs)?);
+    }
+
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for cert in certs {
+        let name = cert.control_number.clone().unwrap_or_else(|| format!("certificate-{}", cert.id));
+        archive.start_file(format!("{}.pdf", name), options).map_err(|e| e.to_string())?;
+        archive
+            .write_all(&certificates_pdf(conn, settings, std::slice::from_ref(cert))?)
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = archive.finish().map_err(|e| e.to_string())?.into_inner();
+
+    write_file(output, bytes)
+}
+
+// bms --render <report> --out <file.pdf> [--params '<json>']
+// Renders straight from the database without opening a window, for scripts and scheduled jobs.
+pub fn run_headless(args: &[String]) -> Option<Result<PathBuf, String>> {
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .cloned()
+    };
+
+    let report = value_of("--render")?;
+    Some((|| {
+        let output = PathBuf::from(value_of("--out").ok_or("--out <file.pdf> is required with --render")?);
+        let params = value_of("--params")
+            .map(|json| serde_json::from_str(&json).map_err(|e| format!("Invalid --params: {}", e)))
+            .transpose()?;
+        write_report(&report, params, &output)?;
+        Ok(output)
+    })())
+}
@@ -0,0 +1,893 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveTime};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+
+use super::document::{decode_png, local_date, local_datetime, long_date, money, red, Align, Column, Letterhead, Orientation, PdfReport, TableRow, MARGIN};
+use super::statements::render_statement;
+use crate::commands::attendance::{attendance_summary, daily_time_record, month_range};
+use crate::commands::blotters::fetch_all_blotters_command;
+use crate::commands::cash_advances::fetch_cash_advance_aging_command;
+use crate::commands::certificate_types::{render_body, template_body};
+use crate::commands::certificates::{certificate_from_row, CERTIFICATE_COLUMNS};
+use crate::commands::event_attendance::fetch_attendance;
+use crate::commands::events::{fetch_all_events_command, fetch_event, schedule_label};
+use crate::commands::expense::fetch_all_expenses_command;
+use crate::commands::income::fetch_all_incomes_command;
+use crate::commands::logbook::fetch_all_logbook_entries_command;
+use crate::commands::officials::fetch_all_officials_command;
+use crate::commands::residents::fetch_all_residents_command;
+use crate::commands::settings::{fetch_settings_command, DEFAULT_SCHEDULE};
+use crate::commands::signatories::signers;
+use crate::commands::verification::{certificate_qr_payload, qr_png};
+use crate::commands::vouchers::fetch_voucher;
+use crate::database::connection::establish_connection;
+use crate::models::cash_advance::CashAdvanceAging;
+use crate::models::certificate::Certificate;
+use crate::models::money::Money;
+use crate::models::settings::Settings;
+use crate::models::signatory::Signer;
+
+pub const REPORTS: &[&str] = &[
+    "residents",
+    "households",
+    "blotters",
+    "incomes",
+    "expenses",
+    "events",
+    "officials",
+    "logbook",
+    "certificate",
+    "cashbook",
+    "receipts_expenditures",
+    "collections_deposits",
+    "disbursement_voucher",
+    "cash_advance_aging",
+    "daily_time_record",
+    "attendance_summary",
+    "event_attendance",
+];
+
+// Every report reads the fields it needs and ignores the rest
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ReportParams {
+    pub title: Option<String>,   // heading printed above the table, e.g. the active filter
+    pub id: Option<i32>,         // single record, e.g. the certificate to print
+    pub ids: Option<Vec<i32>>,   // restrict a list to these records
+    pub from: Option<String>,    // inclusive date range for dated records
+    pub to: Option<String>,
+    pub month: Option<String>,   // "YYYY-MM" for monthly forms such as the DTR
+}
+
+impl ReportParams {
+    fn title_or(&self, default: &str) -> String {
+        self.title
+            .clone()
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    fn includes(&self, id: Option<i32>) -> bool {
+        match (&self.ids, id) {
+            (Some(ids), Some(id)) => ids.contains(&id),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
+    fn in_range(&self, date: &str) -> bool {
+        let bound = |value: &Option<String>| value.as_deref().and_then(local_date);
+        let Some(date) = local_date(date) else {
+            return self.from.is_none() && self.to.is_none();
+        };
+        bound(&self.from).is_none_or(|from| date >= from) && bound(&self.to).is_none_or(|to| date <= to)
+    }
+}
+
+fn list_report(title: &str, orientation: Orientation, columns: &[Column], rows: &[Vec<String>], totals: &[Vec<String>]) -> Result<Vec<u8>, String> {
+    let settings = fetch_settings_command()?;
+    let mut report = PdfReport::new(title, orientation, Letterhead::from_settings(&settings))?;
+    report.letterhead();
+    report.text(title, 14.0, true, Align::Left);
+    report.space(2.0);
+    report.table(columns, rows, totals);
+    report.finish()
+}
+
+fn residents(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Full Name", 2.2),
+        Column::new("Civil Status", 1.0),
+        Column::new("Gender", 0.8),
+        Column::new("Birthday", 1.2),
+        Column::new("Zone", 0.7),
+        Column::new("Status", 0.9),
+    ];
+
+    let rows: Vec<Vec<String>> = fetch_all_residents_command()?
+        .into_iter()
+        .filter(|resident| params.includes(resident.id))
+        .map(|resident| {
+            let name = [
+                Some(resident.first_name.as_str()),
+                resident.middle_name.as_deref(),
+                Some(resident.last_name.as_str()),
+                resident.suffix.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+            vec![
+                resident.id.unwrap_or_default().to_string(),
+                name,
+                resident.civil_status,
+                resident.gender,
+                long_date(&resident.date_of_birth),
+                resident.zone,
+                resident.status,
+            ]
+        })
+        .collect();
+
+    list_report(&params.title_or("Residents"), Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+// Monthly income, in pesos, below which a household counts as low income
+const LOW_INCOME_THRESHOLD: i64 = 20_000;
+
+// One row per household head with what the whole household adds up to
+fn households(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("House #", 0.9),
+        Column::new("Head", 2.2),
+        Column::new("Zone", 0.7),
+        Column::right("Members", 0.8),
+        Column::new("With PWD", 0.8),
+        Column::new("With Senior", 0.9),
+        Column::new("Low Income", 0.9),
+    ];
+
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT h.id, h.household_number, h.first_name, h.middle_name, h.last_name, h.suffix, h.zone,
+                 COUNT(m.id), MAX(COALESCE(m.is_pwd, 0)), MAX(COALESCE(m.is_senior, 0)), COALESCE(SUM(m.average_monthly_income), 0)
+             FROM residents h
+             LEFT JOIN residents m ON m.household_number = h.household_number
+             WHERE h.role_in_household = 'Head'
+             GROUP BY h.id
+             ORDER BY h.household_number, h.id",
+        )
+        .map_err(|e| e.to_string())?;
+    let yes_no = |flag: bool| if flag { "Yes" } else { "No" }.to_string();
+    let rows = stmt
+        .query_map([], |row| {
+            let id: i32 = row.get(0)?;
+            let name = [row.get::<_, Option<String>>(4)?, row.get(2)?, row.get(3)?, row.get(5)?]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Ok((
+                id,
+                vec![
+                    id.to_string(),
+                    row.get::<_, String>(1)?,
+                    name,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, i64>(7)?.to_string(),
+                    yes_no(row.get(8)?),
+                    yes_no(row.get(9)?),
+                    yes_no(row.get::<_, i64>(10)? < LOW_INCOME_THRESHOLD),
+                ],
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|(id, _)| params.includes(Some(*id)))
+        .map(|(_, row)| row)
+        .collect::<Vec<_>>();
+
+    list_report(&params.title_or("All Households"), Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+fn blotters(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Type", 1.2),
+        Column::new("Reported By", 1.4),
+        Column::new("Involved", 1.6),
+        Column::new("Date", 1.2),
+        Column::new("Location", 1.2),
+        Column::new("Zone", 0.6),
+        Column::new("Status", 1.0),
+    ];
+
+    let rows: Vec<Vec<String>> = fetch_all_blotters_command()?
+        .into_iter()
+        .filter(|blotter| params.includes(blotter.id) && params.in_range(&blotter.incident_date))
+        .map(|blotter| {
+            vec![
+                blotter.id.unwrap_or_default().to_string(),
+                blotter.type_,
+                blotter.reported_by,
+                blotter.involved,
+                long_date(&blotter.incident_date),
+                format!("Brgy. {}", blotter.location),
+                blotter.zone,
+                blotter.status,
+            ]
+        })
+        .collect();
+
+    list_report(&params.title_or("Blotter Records"), Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+fn incomes(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Type", 1.6),
+        Column::new("Category", 1.2),
+        Column::right("OR Number", 0.9),
+        Column::right("Amount", 1.0),
+        Column::new("Received From", 1.4),
+        Column::new("Received By", 1.4),
+        Column::new("Date Issued", 1.2),
+    ];
+
+    let incomes: Vec<_> = fetch_all_incomes_command()?
+        .into_iter()
+        .filter(|income| params.includes(income.id) && params.in_range(&income.date))
+        .collect();
+    let total: Money = incomes.iter().map(|income| income.amount).sum();
+
+    let rows: Vec<Vec<String>> = incomes
+        .into_iter()
+        .map(|income| {
+            vec![
+                income.id.unwrap_or_default().to_string(),
+                income.type_,
+                income.category,
+                income.or_number.to_string(),
+                money(income.amount),
+                income.received_from,
+                income.received_by,
+                long_date(&income.date),
+            ]
+        })
+        .collect();
+    let totals = vec![vec![String::new(), "Total".into(), String::new(), String::new(), money(total), String::new(), String::new(), String::new()]];
+
+    list_report(&params.title_or("Income Records"), Orientation::Landscape, COLUMNS, &rows, &totals)
+}
+
+fn expenses(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Type", 1.6),
+        Column::new("Category", 1.2),
+        Column::right("OR Number", 0.9),
+        Column::right("Amount", 1.0),
+        Column::new("Paid To", 1.4),
+        Column::new("Paid By", 1.4),
+        Column::new("Date", 1.2),
+    ];
+
+    let expenses: Vec<_> = fetch_all_expenses_command()?
+        .into_iter()
+        .filter(|expense| params.includes(expense.id) && params.in_range(&expense.date))
+        .collect();
+    let total: Money = expenses.iter().map(|expense| expense.amount).sum();
+
+    let rows: Vec<Vec<String>> = expenses
+        .into_iter()
+        .map(|expense| {
+            vec![
+                expense.id.unwrap_or_default().to_string(),
+                expense.type_,
+                expense.category,
+                expense.or_number.to_string(),
+                money(expense.amount),
+                expense.paid_to,
+                expense.paid_by,
+                long_date(&expense.date),
+            ]
+        })
+        .collect();
+    let totals = vec![vec![String::new(), "Total".into(), String::new(), String::new(), money(total), String::new(), String::new(), String::new()]];
+
+    list_report(&params.title_or("Expense Records"), Orientation::Landscape, COLUMNS, &rows, &totals)
+}
+
+fn events(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Name", 1.6),
+        Column::new("Type", 1.0),
+        Column::new("Status", 0.9),
+        Column::new("Schedule", 1.6),
+        Column::new("Venue", 1.3),
+        Column::new("Attendee", 1.2),
+        Column::new("Notes", 1.8),
+    ];
+
+    let rows: Vec<Vec<String>> = fetch_all_events_command()?
+        .into_iter()
+        .filter(|event| params.includes(event.id) && params.in_range(&event.date))
+        .map(|event| {
+            let schedule = schedule_label(&event);
+            vec![
+                event.id.unwrap_or_default().to_string(),
+                event.name,
+                event.type_,
+                event.status,
+                schedule,
+                event.venue,
+                event.attendee,
+                event.notes,
+            ]
+        })
+        .collect();
+
+    list_report(&params.title_or("Events"), Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+fn officials(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::new("Name", 1.8),
+        Column::new("Position", 1.4),
+        Column::new("Section", 1.3),
+        Column::new("Contact", 1.1),
+        Column::new("Zone", 0.6),
+        Column::new("Term", 1.6),
+    ];
+
+    let rows: Vec<Vec<String>> = fetch_all_officials_command()?
+        .into_iter()
+        .filter(|official| params.includes(official.id))
+        .map(|official| {
+            vec![
+                official.name,
+                official.role,
+                official.section,
+                official.contact,
+                official.zone,
+                format!("{} - {}", official.term_start, official.term_end),
+            ]
+        })
+        .collect();
+
+    list_report(&params.title_or("Barangay Officials"), Orientation::Portrait, COLUMNS, &rows, &[])
+}
+
+fn logbook(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("ID", 0.5),
+        Column::new("Official", 1.6),
+        Column::new("Role", 1.1),
+        Column::new("Date", 1.2),
+        Column::new("Time In AM", 0.9),
+        Column::new("Time Out AM", 0.9),
+        Column::new("Time In PM", 0.9),
+        Column::new("Time Out PM", 0.9),
+        Column::new("Remarks", 1.4),
+        Column::new("Status", 0.9),
+        Column::right("Total Hours", 0.8),
+    ];
+
+    let rows: Vec<Vec<String>> = fetch_all_logbook_entries_command(None)?
+        .into_iter()
+        .filter(|entry| params.includes(entry.id) && params.in_range(&entry.date))
+        .map(|entry| {
+            vec![
+                entry.id.unwrap_or_default().to_string(),
+                entry.official_name,
+                entry.official_role.unwrap_or_default(),
+                long_date(&entry.date),
+                entry.time_in_am.unwrap_or_default(),
+                entry.time_out_am.unwrap_or_default(),
+                entry.time_in_pm.unwrap_or_default(),
+                entry.time_out_pm.unwrap_or_default(),
+                entry.remarks.unwrap_or_default(),
+                entry.status.unwrap_or_default(),
+                entry.total_hours.map(|hours| format!("{:.2}", hours)).unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    list_report(&params.title_or("Logbook"), Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+// "21st day of July, 2025", as used in "Given this ... at ..."
+fn day_of_month(date: NaiveDate) -> String {
+    let day = date.day();
+    let suffix = match (day % 10, day % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{} day of {}", day, suffix, date.format("%B, %Y"))
+}
+
+fn certificate_values<'a>(conn: &Connection, settings: &Settings, cert: &Certificate) -> HashMap<&'a str, String> {
+    let zone: Option<String> = cert.resident_id.and_then(|resident_id| {
+        conn.query_row("SELECT zone FROM residents WHERE id = ?1", params![resident_id], |row| row.get(0))
+            .optional()
+            .ok()
+            .flatten()
+    });
+    let issued = cert.issued_date.as_deref().and_then(local_date);
+
+    HashMap::from([
+        ("resident_name", cert.resident_name.clone()),
+        ("age", cert.age.map(|age| age.to_string()).unwrap_or_default()),
+        ("civil_status", cert.civil_status.clone().unwrap_or_default()),
+        ("zone", zone.unwrap_or_default()),
+        ("barangay", settings.barangay.clone()),
+        ("municipality", settings.municipality.clone()),
+        ("province", settings.province.clone()),
+        ("purpose", cert.purpose.clone().unwrap_or_default()),
+        ("ownership_text", cert.ownership_text.clone().unwrap_or_default()),
+        ("issued_date", issued.map(day_of_month).unwrap_or_default()),
+    ])
+}
+
+// Draws one certificate on the current page
+fn certificate_page(report: &mut PdfReport, conn: &Connection, settings: &Settings, cert: &Certificate) -> Result<(), String> {
+    const QR_SIZE: f32 = 32.0;
+
+    report.letterhead();
+    report.text("OFFICE OF THE PUNONG BARANGAY", 12.0, true, Align::Center);
+    report.space(6.0);
+    report.text(&cert.type_.to_uppercase(), 18.0, true, Align::Center);
+    report.space(2.0);
+    if let Some(control_number) = &cert.control_number {
+        report.text(&format!("Control No. {}", control_number), 10.0, false, Align::Right);
+    }
+    if cert.voided {
+        report.set_color(red());
+        let reason = cert.void_reason.clone().unwrap_or_default();
+        report.text(&format!("VOID: {}", reason), 12.0, true, Align::Center);
+        report.reset_color();
+    }
+    report.space(6.0);
+
+    let values = certificate_values(conn, settings, cert);
+    let body = template_body(conn, cert.template_version_id, &cert.type_)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(|| {
+            "TO WHOM IT MAY CONCERN:\n\n\
+             This is to certify that {{resident_name}} is a resident of Barangay {{barangay}}, {{municipality}}, \
+             {{province}}.\n\n\
+             Given this {{issued_date}}, at {{barangay}}, {{municipality}}, {{province}}."
+                .to_string()
+        });
+    for paragraph in render_body(&body, &values).split("\n\n") {
+        report.paragraph(paragraph.trim(), 12.0, Align::Justify);
+        report.space(4.0);
+    }
+
+    let issued_on = cert.issued_date.as_deref().and_then(local_date).unwrap_or_else(|| Local::now().date_naive());
+    match signers(conn, "certificate", issued_on, &[])?.as_slice() {
+        [signer] => {
+            let center = report.right_column_center();
+            report.signature(signer, center);
+        }
+        signers => report.signatures(signers),
+    }
+
+    // Issue details and QR code sit at the foot of the page
+    let qr_top = MARGIN + QR_SIZE + 6.0;
+    if let (Some(control_number), Some(content_hash)) = (&cert.control_number, &cert.content_hash) {
+        let payload = certificate_qr_payload(conn, control_number, content_hash)?;
+        let qr = decode_png(&qr_png(&payload)?)?;
+        report.image(&qr, MARGIN, qr_top, QR_SIZE);
+        report.move_to(MARGIN + 6.0);
+        report.paragraph_at("Scan to verify", 8.0, false, Align::Center, MARGIN, QR_SIZE);
+    }
+
+    let details = [
+        ("O.R. No.", cert.or_number.map(|or| or.to_string())),
+        ("Amount Paid", cert.amount.clone().filter(|amount| !amount.trim().is_empty())),
+        ("Date Issued", cert.issued_date.as_deref().map(long_date)),
+        ("Valid Until", cert.valid_until.as_deref().map(long_date)),
+    ];
+    report.move_to(qr_top - 4.0);
+    for (label, value) in details {
+        let line = format!("{}: {}", label, value.unwrap_or_else(|| "-".into()));
+        report.paragraph_at(&line, 9.0, false, Align::Left, MARGIN + QR_SIZE + 4.0, 80.0);
+    }
+
+    Ok(())
+}
+
+// One page per certificate, in the order given
+pub fn certificates_pdf(conn: &Connection, settings: &Settings, certs: &[Certificate]) -> Result<Vec<u8>, String> {
+    let title = match certs {
+        [cert] => cert.control_number.clone().unwrap_or_else(|| cert.type_.clone()),
+        _ => format!("{} certificates", certs.len()),
+    };
+    let mut report = PdfReport::new(&title, Orientation::Portrait, Letterhead::from_settings(settings))?.without_footer();
+    for (index, cert) in certs.iter().enumerate() {
+        if index > 0 {
+            report.new_page();
+        }
+        certificate_page(&mut report, conn, settings, cert)?;
+    }
+    report.finish()
+}
+
+fn certificate(params: &ReportParams) -> Result<Vec<u8>, String> {
+    let id = params.id.ok_or("The certificate report needs the certificate id.")?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let settings = fetch_settings_command()?;
+
+    let cert = conn
+        .query_row(
+            &format!("SELECT {} FROM certificates WHERE id = ?1", CERTIFICATE_COLUMNS),
+            params![id],
+            certificate_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Certificate {} does not exist.", id))?;
+
+    certificates_pdf(&conn, &settings, &[cert])
+}
+
+fn disbursement_voucher(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[Column::new("Item", 1.0), Column::new("Details", 3.0)];
+
+    let id = params.id.ok_or("Choose the voucher to print.")?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let settings = fetch_settings_command()?;
+    let voucher = fetch_voucher(&conn, id)?;
+
+    let mut report = PdfReport::new("Disbursement Voucher", Orientation::Portrait, Letterhead::from_settings(&settings))?;
+    report.letterhead();
+    report.text("DISBURSEMENT VOUCHER", 14.0, true, Align::Center);
+    report.text(
+        &format!("DV No. {}  |  Status: {}", voucher.dv_number.clone().unwrap_or_default(), voucher.status.clone().unwrap_or_default()),
+        10.0,
+        false,
+        Align::Center,
+    );
+    report.space(4.0);
+
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    let mut rows = vec![
+        vec!["Payee".to_string(), optional(&voucher.payee)],
+        vec!["Date".to_string(), voucher.expense_date.as_deref().map(long_date).unwrap_or_default()],
+        vec!["Category".to_string(), optional(&voucher.category)],
+        vec!["Particulars".to_string(), optional(&voucher.particulars)],
+        vec!["Amount".to_string(), voucher.amount.map(money).unwrap_or_default()],
+        vec!["Mode of Payment".to_string(), voucher.payment_mode.clone()],
+    ];
+    if voucher.payment_mode == "Check" {
+        rows.push(vec!["Check No.".to_string(), optional(&voucher.check_number)]);
+        rows.push(vec!["Check Date".to_string(), voucher.check_date.as_deref().map(long_date).unwrap_or_default()]);
+        rows.push(vec!["Bank".to_string(), optional(&voucher.bank)]);
+    }
+    if let Some(reason) = &voucher.rejection_reason {
+        rows.push(vec!["Rejected".to_string(), reason.clone()]);
+    }
+    report.table(COLUMNS, &rows, &[]);
+    report.space(6.0);
+
+    // signers on record, otherwise whoever held the position when the voucher was prepared
+    let prepared_on = voucher
+        .prepared_at
+        .as_deref()
+        .or(voucher.expense_date.as_deref())
+        .and_then(local_date)
+        .unwrap_or_else(|| Local::now().date_naive());
+    let recorded: Vec<(&str, i32, &str)> = [
+        ("certified", voucher.certified_by, &voucher.certified_by_name),
+        ("funds_available", voucher.prepared_by, &voucher.prepared_by_name),
+        ("approved", voucher.approved_by, &voucher.approved_by_name),
+    ]
+    .into_iter()
+    .filter_map(|(slot, id, name)| Some((slot, id?, name.as_deref()?)))
+    .collect();
+    let signers = signers(&conn, "disbursement_voucher", prepared_on, &recorded)?;
+    let block = |slot: &str| signers.iter().find(|signer| signer.slot == slot).cloned().unwrap_or_default();
+    report.signatures(&[block("certified"), block("funds_available")]);
+    report.space(6.0);
+    report.signatures(&[block("approved"), Signer::new("D. Received payment:", &optional(&voucher.payee), "Payee")]);
+
+    report.finish()
+}
+
+// Unliquidated cash advances per official as of `to` (today by default)
+fn cash_advance_aging(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::new("Official", 2.0),
+        Column::right("Advances", 0.8),
+        Column::right("Not Yet Due", 1.1),
+        Column::right("1-30 Days", 1.0),
+        Column::right("31-60 Days", 1.0),
+        Column::right("61-90 Days", 1.0),
+        Column::right("Over 90 Days", 1.0),
+        Column::right("Total", 1.1),
+    ];
+
+    let aging = fetch_cash_advance_aging_command(params.to.clone())?;
+    let amounts = |row: &CashAdvanceAging| {
+        [row.current, row.days_1_30, row.days_31_60, row.days_61_90, row.over_90, row.total].map(money)
+    };
+    let rows: Vec<Vec<String>> = aging
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.official_name.clone(), row.advances.to_string()];
+            cells.extend(amounts(row));
+            cells
+        })
+        .collect();
+
+    let mut total = CashAdvanceAging::default();
+    for row in &aging {
+        total.advances += row.advances;
+        total.current += row.current;
+        total.days_1_30 += row.days_1_30;
+        total.days_31_60 += row.days_31_60;
+        total.days_61_90 += row.days_61_90;
+        total.over_90 += row.over_90;
+        total.total += row.total;
+    }
+    let mut totals = vec!["Total".to_string(), total.advances.to_string()];
+    totals.extend(amounts(&total));
+
+    let as_of = params.to.as_deref().map(long_date).unwrap_or_else(|| Local::now().format("%B %-d, %Y").to_string());
+    let title = params.title_or(&format!("Aging of Cash Advances as of {}", as_of));
+    list_report(&title, Orientation::Landscape, COLUMNS, &rows, &[totals])
+}
+
+// "8:00 AM"
+fn clock_time(value: &str) -> String {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map(|time| time.format("%-I:%M %p").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+// Daily Time Record in the layout of Civil Service Form No. 48, for the official in `id`
+fn daily_time_record_form(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("Day", 0.5),
+        Column::new("A.M. Arrival", 1.0),
+        Column::new("A.M. Departure", 1.0),
+        Column::new("P.M. Arrival", 1.0),
+        Column::new("P.M. Departure", 1.0),
+        Column::right("Undertime Hours", 0.8),
+        Column::right("Undertime Minutes", 0.8),
+    ];
+
+    let official_id = params.id.ok_or("Choose the official whose DTR to print.")?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let settings = fetch_settings_command()?;
+    let record = daily_time_record(&conn, official_id, params.month.as_deref())?;
+    let (first, last) = month_range(params.month.as_deref())?;
+
+    let time = |value: &Option<String>| value.clone().unwrap_or_default();
+    let undertime = |minutes: i32| {
+        if minutes > 0 { ((minutes / 60).to_string(), (minutes % 60).to_string()) } else { (String::new(), String::new()) }
+    };
+    let rows: Vec<Vec<String>> = record
+        .days
+        .iter()
+        .map(|day| {
+            let (hours, minutes) = undertime(day.undertime_minutes);
+            // days off and absences are written across the morning arrival column
+            let note = match day.status.as_deref() {
+                Some("Rest Day") => Some(day.weekday.to_uppercase()),
+                Some("Holiday") => Some("HOLIDAY".to_string()),
+                Some("On Leave") => Some("ON LEAVE".to_string()),
+                Some("Absent") => Some("ABSENT".to_string()),
+                _ => None,
+            };
+            match note {
+                Some(note) => vec![day.day.to_string(), note, String::new(), String::new(), String::new(), hours, minutes],
+                None => vec![
+                    day.day.to_string(),
+                    time(&day.time_in_am),
+                    time(&day.time_out_am),
+                    time(&day.time_in_pm),
+                    time(&day.time_out_pm),
+                    hours,
+                    minutes,
+                ],
+            }
+        })
+        .collect();
+    let (hours, minutes) = undertime(record.undertime_minutes);
+    let totals = vec![vec!["Total".to_string(), String::new(), String::new(), String::new(), String::new(), hours, minutes]];
+
+    let office_time = |value: &Option<String>, index: usize| clock_time(value.as_deref().unwrap_or(DEFAULT_SCHEDULE[index]));
+    let office_hours = format!(
+        "{} - {}, {} - {}",
+        office_time(&settings.office_am_in, 0),
+        office_time(&settings.office_am_out, 1),
+        office_time(&settings.office_pm_in, 2),
+        office_time(&settings.office_pm_out, 3)
+    );
+
+    let title = format!("Daily Time Record - {}", record.official_name);
+    let mut report = PdfReport::new(&title, Orientation::Portrait, Letterhead::from_settings(&settings))?
+        .without_footer()
+        .compact_tables();
+    report.text("Civil Service Form No. 48", 8.0, false, Align::Left);
+    report.text("DAILY TIME RECORD", 14.0, true, Align::Center);
+    report.text("-----o0o-----", 9.0, false, Align::Center);
+    report.space(2.0);
+    report.text(&record.official_name.to_uppercase(), 12.0, true, Align::Center);
+    report.text(&record.official_role, 9.0, false, Align::Center);
+    report.space(2.0);
+    report.text(&format!("For the month of {}", first.format("%B %Y")), 10.0, false, Align::Left);
+    report.text(&format!("Official hours for arrival and departure: Regular days {}", office_hours), 9.0, false, Align::Left);
+    report.space(2.0);
+    report.table(COLUMNS, &rows, &totals);
+    report.space(3.0);
+    report.paragraph(
+        "I certify on my honor that the above is a true and correct report of the hours of work performed, record of which was made daily at the time of arrival and departure from office.",
+        9.0,
+        Align::Justify,
+    );
+    let mut blocks = vec![Signer::new("", &record.official_name, "Signature")];
+    blocks.extend(signers(&conn, "daily_time_record", last.min(Local::now().date_naive()), &[])?);
+    report.signatures(&blocks);
+
+    report.finish()
+}
+
+// Days present, absences and hours of every official for the month
+fn attendance_summary_report(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::new("Official", 2.0),
+        Column::new("Position", 1.3),
+        Column::new("Section", 1.2),
+        Column::right("Days Present", 0.8),
+        Column::right("Late", 0.6),
+        Column::right("Undertime", 0.8),
+        Column::right("Absences", 0.8),
+        Column::right("On Leave", 0.8),
+        Column::right("Hours", 0.8),
+        Column::right("Tardiness / Undertime", 1.1),
+    ];
+
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let (first, _) = month_range(params.month.as_deref())?;
+    let summary = attendance_summary(&conn, params.month.as_deref())?;
+
+    let duration = |minutes: i32| format!("{}h {:02}m", minutes / 60, minutes % 60);
+    let rows: Vec<Vec<String>> = summary
+        .iter()
+        .filter(|row| params.includes(Some(row.official_id)))
+        .map(|row| {
+            vec![
+                row.official_name.clone(),
+                row.official_role.clone(),
+                row.official_section.clone(),
+                row.days_present.to_string(),
+                row.days_late.to_string(),
+                row.days_undertime.to_string(),
+                row.absences.to_string(),
+                row.days_on_leave.to_string(),
+                format!("{:.2}", row.hours),
+                duration(row.undertime_minutes),
+            ]
+        })
+        .collect();
+
+    let title = params.title_or(&format!("Attendance Summary for {}", first.format("%B %Y")));
+    list_report(&title, Orientation::Landscape, COLUMNS, &rows, &[])
+}
+
+// Who attended an event, sitting by sitting, with the goods each received.
+// `from` and `to` narrow a recurring event to some of its sittings.
+fn event_attendance_sheet(params: &ReportParams) -> Result<Vec<u8>, String> {
+    const COLUMNS: &[Column] = &[
+        Column::right("No.", 0.4),
+        Column::new("Name", 2.0),
+        Column::new("Zone", 0.6),
+        Column::new("Household No.", 0.9),
+        Column::new("Time In", 0.8),
+        Column::new("Items Received", 1.6),
+        Column::new("Signature", 1.3),
+    ];
+
+    let id = params.id.ok_or("The attendance sheet needs the event id.")?;
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let settings = fetch_settings_command()?;
+    let event = fetch_event(&conn, id)?;
+    let attendance: Vec<_> = fetch_attendance(&conn, id, None)?
+        .into_iter()
+        .filter(|attendee| attendee.sitting_date.as_deref().is_some_and(|date| params.in_range(date)))
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut sitting: Option<String> = None;
+    let mut number = 0;
+    for attendee in &attendance {
+        if attendee.sitting_date != sitting {
+            sitting = attendee.sitting_date.clone();
+            let present = attendance.iter().filter(|other| other.sitting_date == sitting).count();
+            rows.push(TableRow::Section(format!("{} ({} present)", long_date(sitting.as_deref().unwrap_or_default()), present)));
+            number = 0;
+        }
+        number += 1;
+        let time_in = attendee.checked_in_at.as_deref().and_then(local_datetime).map(|time| time.format("%-I:%M %p").to_string());
+        rows.push(TableRow::Body(vec![
+            number.to_string(),
+            attendee.resident_name.clone().unwrap_or_default(),
+            attendee.zone.clone().unwrap_or_default(),
+            attendee.household_number.clone().unwrap_or_default(),
+            time_in.unwrap_or_default(),
+            attendee.items_received.clone().unwrap_or_default(),
+            String::new(),
+        ]));
+    }
+    let claims = attendance.iter().filter(|attendee| attendee.items_received.is_some()).count();
+    rows.push(TableRow::Total(vec![
+        attendance.len().to_string(),
+        "Total attendance".into(),
+        String::new(),
+        String::new(),
+        String::new(),
+        if claims > 0 { format!("{} received goods", claims) } else { String::new() },
+        String::new(),
+    ]));
+
+    let mut report = PdfReport::new(&format!("Attendance - {}", event.name), Orientation::Portrait, Letterhead::from_settings(&settings))?;
+    report.letterhead();
+    report.text(&params.title_or("ATTENDANCE SHEET"), 14.0, true, Align::Center);
+    report.text(&event.name, 12.0, true, Align::Center);
+    report.text(&schedule_label(&event), 10.0, false, Align::Center);
+    if !event.venue.trim().is_empty() {
+        report.text(&event.venue, 10.0, false, Align::Center);
+    }
+    report.space(3.0);
+    report.grouped_table(COLUMNS, &rows);
+    report.space(6.0);
+
+    let today = Local::now().date_naive();
+    let signed_on = attendance
+        .iter()
+        .filter_map(|attendee| attendee.sitting_date.as_deref().and_then(local_date))
+        .max()
+        .or_else(|| local_date(&event.date))
+        .map_or(today, |date| date.min(today));
+    report.signatures(&signers(&conn, "event_attendance", signed_on, &[])?);
+
+    report.finish()
+}
+
+pub fn render_report(report: &str, params: &ReportParams) -> Result<Vec<u8>, String> {
+    match report {
+        "residents" => residents(params),
+        "households" => households(params),
+        "blotters" => blotters(params),
+        "incomes" => incomes(params),
+        "expenses" => expenses(params),
+        "events" => events(params),
+        "officials" => officials(params),
+        "logbook" => logbook(params),
+        "certificate" => certificate(params),
+        "cashbook" | "receipts_expenditures" | "collections_deposits" => render_statement(report, params),
+        "disbursement_voucher" => disbursement_voucher(params),
+        "cash_advance_aging" => cash_advance_aging(params),
+        "daily_time_record" => daily_time_record_form(params),
+        "attendance_summary" => attendance_summary_report(params),
+        "event_attendance" => event_attendance_sheet(params),
+        _ => Err(format!("Unknown report '{}'. Available reports: {}", report, REPORTS.join(", "))),
+    }
+}
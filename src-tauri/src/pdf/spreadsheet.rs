@@ -0,0 +1,131 @@
+use rust_xlsxwriter::{Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+
+use super::document::{Align, Orientation};
+use super::statements::{Cell, Statement, StatementRow};
+use crate::models::settings::Settings;
+
+const AMOUNT_FORMAT: &str = "#,##0.00";
+const PAPER_A4: u8 = 9;
+
+struct Formats {
+    heading: Format,
+    header: Format,
+    text: Format,
+    amount: Format,
+    bold_text: Format,
+    bold_amount: Format,
+    section: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let bold = Format::new().set_bold();
+        Formats {
+            heading: bold.clone().set_font_size(12),
+            header: bold.clone().set_border_bottom(FormatBorder::Thin).set_background_color("#E6E6E6"),
+            text: Format::new(),
+            amount: Format::new().set_num_format(AMOUNT_FORMAT),
+            bold_text: bold.clone().set_border_top(FormatBorder::Thin),
+            bold_amount: bold.clone().set_num_format(AMOUNT_FORMAT).set_border_top(FormatBorder::Thin),
+            section: bold.set_background_color("#F2F2F2"),
+        }
+    }
+}
+
+fn write_cells(sheet: &mut Worksheet, row: u32, cells: &[Cell], text: &Format, amount: &Format) -> Result<(), XlsxError> {
+    for (col, cell) in cells.iter().enumerate() {
+        match cell {
+            Cell::Text(value) => sheet.write_string_with_format(row, col as u16, value, text)?,
+            Cell::Amount(value) => sheet.write_number_with_format(row, col as u16, value.pesos(), amount)?,
+        };
+    }
+    Ok(())
+}
+
+fn write_statement(sheet: &mut Worksheet, statement: &Statement, settings: &Settings) -> Result<(), XlsxError> {
+    let formats = Formats::new();
+    let widest = statement.tables.iter().map(|table| table.columns.len()).max().unwrap_or(1) as u16;
+
+    sheet.set_name(statement.title.chars().take(31).collect::<String>())?;
+    sheet.set_paper_size(PAPER_A4);
+    if matches!(statement.orientation, Orientation::Landscape) {
+        sheet.set_landscape();
+    }
+
+    // Letterhead lines, same as the PDF header
+    let mut row = 0;
+    let place = [settings.municipality.trim(), settings.province.trim()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    for (line, format) in [
+        (format!("Barangay {}", settings.barangay.trim()), &formats.heading),
+        (place, &formats.text),
+        (statement.title.to_uppercase(), &formats.heading),
+        (statement.period.clone(), &formats.text),
+    ] {
+        sheet.write_string_with_format(row, 0, line, format)?;
+        row += 1;
+    }
+    row += 1;
+
+    // Column widths follow the first table, which is the widest in every statement
+    if let Some(table) = statement.tables.first() {
+        for (col, column) in table.columns.iter().enumerate() {
+            sheet.set_column_width(col as u16, (column.width * 12.0).max(10.0) as f64)?;
+        }
+    }
+
+    for table in &statement.tables {
+        if !table.heading.is_empty() {
+            sheet.write_string_with_format(row, 0, table.heading, &formats.heading)?;
+            row += 1;
+        }
+        for (col, column) in table.columns.iter().enumerate() {
+            let header = match column.align {
+                Align::Right => formats.header.clone().set_align(FormatAlign::Right),
+                _ => formats.header.clone(),
+            };
+            sheet.write_string_with_format(row, col as u16, column.title, &header)?;
+        }
+        row += 1;
+
+        for line in &table.rows {
+            match line {
+                StatementRow::Section(title) => {
+                    let last = (table.columns.len().max(1) - 1) as u16;
+                    if last == 0 {
+                        sheet.write_string_with_format(row, 0, title, &formats.section)?;
+                    } else {
+                        sheet.merge_range(row, 0, row, last, title, &formats.section)?;
+                    }
+                }
+                StatementRow::Body(cells) => write_cells(sheet, row, cells, &formats.text, &formats.amount)?,
+                StatementRow::Subtotal(cells) | StatementRow::Total(cells) => {
+                    write_cells(sheet, row, cells, &formats.bold_text, &formats.bold_amount)?
+                }
+            }
+            row += 1;
+        }
+        row += 1;
+    }
+
+    // Signatories side by side: label, then name and position below it
+    row += 1;
+    let step = (widest / statement.signatories.len().max(1) as u16).max(1);
+    for (index, signer) in statement.signatories.iter().enumerate() {
+        let col = index as u16 * step;
+        sheet.write_string(row, col, &signer.label)?;
+        sheet.write_string_with_format(row + 3, col, signer.name.to_uppercase(), &formats.bold_text)?;
+        sheet.write_string(row + 4, col, &signer.position)?;
+    }
+
+    Ok(())
+}
+
+pub fn statement_xlsx(statement: &Statement, settings: &Settings) -> Result<Vec<u8>, String> {
+    let mut workbook = Workbook::new();
+    write_statement(workbook.add_worksheet(), statement, settings).map_err(|e| e.to_string())?;
+    workbook.save_to_buffer().map_err(|e| e.to_string())
+}
@@ -0,0 +1,540 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, NaiveDate};
+use rusqlite::{params, Connection};
+
+use super::document::{local_date, money, Align, Column, Letterhead, Orientation, PdfReport, TableRow};
+use super::reports::ReportParams;
+use crate::commands::cash_advances::fetch_advances;
+use crate::commands::expense::fetch_all_expenses_command;
+use crate::commands::income::fetch_all_incomes_command;
+use crate::commands::ledger::{category_account_code, CASH_ACCOUNT, CASH_IN_BANK_ACCOUNT};
+use crate::commands::settings::fetch_settings_command;
+use crate::commands::signatories::signers;
+use crate::database::connection::establish_connection;
+use crate::models::money::Money;
+use crate::models::settings::Settings;
+use crate::models::signatory::Signer;
+
+pub const STATEMENTS: &[&str] = &["cashbook", "receipts_expenditures", "collections_deposits"];
+
+pub enum Cell {
+    Text(String),
+    Amount(Money),
+}
+
+pub enum StatementRow {
+    Section(String),
+    Body(Vec<Cell>),
+    Subtotal(Vec<Cell>),
+    Total(Vec<Cell>),
+}
+
+pub struct StatementTable {
+    pub heading: &'static str,
+    pub columns: &'static [Column],
+    pub rows: Vec<StatementRow>,
+}
+
+// A statutory report laid out once and written to either PDF or XLSX
+pub struct Statement {
+    pub title: &'static str,
+    pub period: String,
+    pub orientation: Orientation,
+    pub tables: Vec<StatementTable>,
+    pub signatories: Vec<Signer>,
+}
+
+fn text(value: impl Into<String>) -> Cell {
+    Cell::Text(value.into())
+}
+
+fn blank() -> Cell {
+    Cell::Text(String::new())
+}
+
+// Inclusive reporting period; an open start means "since the first record"
+struct Period {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+impl Period {
+    fn from_params(params: &ReportParams) -> Self {
+        Period {
+            from: params.from.as_deref().and_then(local_date),
+            to: params.to.as_deref().and_then(local_date),
+        }
+    }
+
+    fn before(&self, date: NaiveDate) -> bool {
+        self.from.is_some_and(|from| date < from)
+    }
+
+    fn contains(&self, date: NaiveDate) -> bool {
+        !self.before(date) && self.to.is_none_or(|to| date <= to)
+    }
+
+    fn label(&self) -> String {
+        let long = |date: NaiveDate| date.format("%B %-d, %Y").to_string();
+        match (self.from, self.to) {
+            (Some(from), Some(to)) => format!("For the period {} to {}", long(from), long(to)),
+            (Some(from), None) => format!("For the period beginning {}", long(from)),
+            (None, Some(to)) => format!("As of {}", long(to)),
+            (None, None) => "All recorded transactions".to_string(),
+        }
+    }
+}
+
+// One line of the cash book: a collection (income) or a disbursement (expense)
+struct CashEntry {
+    date: NaiveDate,
+    reference: String,
+    particulars: String,
+    category: String,
+    collector: String,
+    receipt: Money,
+    disbursement: Money,
+    by_check: bool, // drawn on the bank account, so not cash on hand
+}
+
+fn cash_entries() -> Result<Vec<CashEntry>, String> {
+    let mut entries = Vec::new();
+
+    for income in fetch_all_incomes_command()? {
+        let Some(date) = local_date(&income.date) else {
+            continue;
+        };
+        entries.push(CashEntry {
+            date,
+            reference: if income.or_number > 0 { format!("OR {}", income.or_number) } else { String::new() },
+            particulars: format!("{} - {}", income.type_, income.received_from),
+            category: income.category,
+            collector: income.received_by,
+            receipt: income.amount,
+            disbursement: Money::ZERO,
+            by_check: false,
+        });
+    }
+    for expense in fetch_all_expenses_command()? {
+        // unapproved vouchers have not been paid out yet, and liquidations were
+        // paid out of a cash advance already counted below
+        if !expense.is_final() || expense.cash_advance_id.is_some() {
+            continue;
+        }
+        let Some(date) = local_date(&expense.date) else {
+            continue;
+        };
+        entries.push(CashEntry {
+            date,
+            reference: if expense.or_number > 0 { format!("Ref. {}", expense.or_number) } else { String::new() },
+            particulars: format!("{} - {}", expense.type_, expense.paid_to),
+            category: expense.category,
+            collector: expense.paid_by,
+            receipt: Money::ZERO,
+            disbursement: expense.amount,
+            by_check: expense.payment_mode.as_deref() == Some("Check"),
+        });
+    }
+
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    for advance in fetch_advances(&conn, None, Local::now().date_naive())? {
+        let official = advance.official_name.unwrap_or_default();
+        if let Some(date) = local_date(&advance.date_granted) {
+            entries.push(CashEntry {
+                date,
+                reference: format!("CA {}", advance.id.unwrap_or_default()),
+                particulars: format!("Cash advance to {} - {}", official, advance.purpose),
+                category: "Cash Advances".to_string(),
+                collector: official.clone(),
+                receipt: Money::ZERO,
+                disbursement: advance.amount,
+                by_check: false,
+            });
+        }
+        let refund_date = advance.refund_date.as_deref().unwrap_or(&advance.date_granted);
+        if let Some(date) = local_date(refund_date).filter(|_| !advance.refunded.is_zero()) {
+            entries.push(CashEntry {
+                date,
+                reference: format!("CA {}", advance.id.unwrap_or_default()),
+                particulars: format!("Refund of cash advance - {}", official),
+                category: "Refund of Cash Advances".to_string(),
+                collector: official,
+                receipt: advance.refunded,
+                disbursement: Money::ZERO,
+                by_check: false,
+            });
+        }
+    }
+
+    // stable sort keeps collections ahead of disbursements made the same day
+    entries.sort_by_key(|entry| entry.date);
+    Ok(entries)
+}
+
+fn short_date(date: NaiveDate) -> String {
+    date.format("%m/%d/%Y").to_string()
+}
+
+// Whoever held the positions at the end of the period, or today for an open period
+fn signatories(conn: &Connection, period: &Period, report: &str) -> Result<Vec<Signer>, String> {
+    let today = Local::now().date_naive();
+    let on = period.to.map_or(today, |to| to.min(today));
+    signers(conn, report, on, &[])
+}
+
+// Sums per category, keeping the category names as first written
+fn by_category<'a>(amounts: impl Iterator<Item = (&'a str, Money)>) -> BTreeMap<String, Money> {
+    let mut totals: BTreeMap<String, Money> = BTreeMap::new();
+    for (category, amount) in amounts {
+        let key = totals
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(category.trim()))
+            .cloned()
+            .unwrap_or_else(|| category.trim().to_string());
+        *totals.entry(key).or_default() += amount;
+    }
+    totals
+}
+
+fn cashbook(conn: &Connection, period: &Period) -> Result<Statement, String> {
+    const ENTRIES: &[Column] = &[
+        Column::new("Date", 0.9),
+        Column::new("Reference", 0.9),
+        Column::new("Particulars", 2.6),
+        Column::new("Category", 1.3),
+        Column::right("Receipts", 1.0),
+        Column::right("Disbursements", 1.1),
+        Column::right("Balance", 1.0),
+    ];
+    const SUMMARY: &[Column] = &[Column::new("Category", 3.0), Column::right("Amount", 1.0)];
+
+    // the cash book follows cash on hand; checks are drawn on the bank account
+    let entries: Vec<CashEntry> = cash_entries()?.into_iter().filter(|entry| !entry.by_check).collect();
+    let opening: Money = entries
+        .iter()
+        .filter(|entry| period.before(entry.date))
+        .map(|entry| entry.receipt - entry.disbursement)
+        .sum();
+    let entries: Vec<&CashEntry> = entries.iter().filter(|entry| period.contains(entry.date)).collect();
+
+    let mut balance = opening;
+    let mut rows = vec![StatementRow::Body(vec![
+        blank(),
+        blank(),
+        text("Balance forwarded"),
+        blank(),
+        blank(),
+        blank(),
+        Cell::Amount(opening),
+    ])];
+    for entry in &entries {
+        balance += entry.receipt - entry.disbursement;
+        rows.push(StatementRow::Body(vec![
+            text(short_date(entry.date)),
+            text(entry.reference.clone()),
+            text(entry.particulars.clone()),
+            text(entry.category.clone()),
+            if entry.receipt.is_zero() { blank() } else { Cell::Amount(entry.receipt) },
+            if entry.disbursement.is_zero() { blank() } else { Cell::Amount(entry.disbursement) },
+            Cell::Amount(balance),
+        ]));
+    }
+    let receipts: Money = entries.iter().map(|entry| entry.receipt).sum();
+    let disbursements: Money = entries.iter().map(|entry| entry.disbursement).sum();
+    rows.push(StatementRow::Total(vec![
+        blank(),
+        blank(),
+        text("Totals for the period"),
+        blank(),
+        Cell::Amount(receipts),
+        Cell::Amount(disbursements),
+        Cell::Amount(balance),
+    ]));
+
+    let mut summary = vec![StatementRow::Section("Receipts".into())];
+    let collected = entries.iter().filter(|entry| !entry.receipt.is_zero());
+    for (category, amount) in by_category(collected.map(|entry| (entry.category.as_str(), entry.receipt))) {
+        summary.push(StatementRow::Body(vec![text(category), Cell::Amount(amount)]));
+    }
+    summary.push(StatementRow::Subtotal(vec![text("Total receipts"), Cell::Amount(receipts)]));
+    summary.push(StatementRow::Section("Disbursements".into()));
+    let disbursed = entries.iter().filter(|entry| !entry.disbursement.is_zero());
+    for (category, amount) in by_category(disbursed.map(|entry| (entry.category.as_str(), entry.disbursement))) {
+        summary.push(StatementRow::Body(vec![text(category), Cell::Amount(amount)]));
+    }
+    summary.push(StatementRow::Subtotal(vec![text("Total disbursements"), Cell::Amount(disbursements)]));
+    summary.push(StatementRow::Total(vec![text("Net increase (decrease) in cash"), Cell::Amount(receipts - disbursements)]));
+
+    Ok(Statement {
+        title: "Cashbook",
+        period: period.label(),
+        orientation: Orientation::Landscape,
+        tables: vec![
+            StatementTable { heading: "Cash Receipts and Disbursements", columns: ENTRIES, rows },
+            StatementTable { heading: "Summary by Category", columns: SUMMARY, rows: summary },
+        ],
+        signatories: signatories(conn, period, "cashbook")?,
+    })
+}
+
+fn budget_class_name(class: Option<&str>) -> &'static str {
+    match class {
+        Some("PS") => "Personal Services",
+        Some("MOOE") => "Maintenance and Other Operating Expenses",
+        Some("CO") => "Capital Outlay",
+        Some("DF") => "20% Development Fund",
+        _ => "Other Expenditures",
+    }
+}
+
+fn receipts_and_expenditures(conn: &Connection, period: &Period) -> Result<Statement, String> {
+    const COLUMNS: &[Column] = &[
+        Column::new("Particulars", 3.2),
+        Column::new("Account Code", 1.1),
+        Column::right("Amount", 1.2),
+    ];
+
+    let entries = cash_entries()?;
+    let opening: Money = entries
+        .iter()
+        .filter(|entry| period.before(entry.date))
+        .map(|entry| entry.receipt - entry.disbursement)
+        .sum();
+    let entries: Vec<&CashEntry> = entries.iter().filter(|entry| period.contains(entry.date)).collect();
+
+    let mut rows = vec![StatementRow::Body(vec![text("Cash balance, beginning"), blank(), Cell::Amount(opening)])];
+
+    // (account code, category) keeps each section in chart-of-accounts order
+    rows.push(StatementRow::Section("RECEIPTS".into()));
+    let mut receipts: BTreeMap<(String, String), Money> = BTreeMap::new();
+    let collected = entries.iter().filter(|entry| !entry.receipt.is_zero());
+    for (category, amount) in by_category(collected.map(|entry| (entry.category.as_str(), entry.receipt))) {
+        let (code, _) = category_account_code(conn, "Income", &category).map_err(|e| e.to_string())?;
+        receipts.insert((code, category), amount);
+    }
+    for ((code, category), amount) in &receipts {
+        rows.push(StatementRow::Body(vec![text(category.clone()), text(code.clone()), Cell::Amount(*amount)]));
+    }
+    let total_receipts: Money = receipts.values().sum();
+    rows.push(StatementRow::Subtotal(vec![text("Total Receipts"), blank(), Cell::Amount(total_receipts)]));
+
+    let mut expenditures: BTreeMap<&'static str, BTreeMap<(String, String), Money>> = BTreeMap::new();
+    let disbursed = entries.iter().filter(|entry| !entry.disbursement.is_zero());
+    for (category, amount) in by_category(disbursed.map(|entry| (entry.category.as_str(), entry.disbursement))) {
+        let (code, class) = category_account_code(conn, "Expense", &category).map_err(|e| e.to_string())?;
+        expenditures
+            .entry(budget_class_name(class.as_deref()))
+            .or_default()
+            .insert((code, category), amount);
+    }
+    let mut total_expenditures = Money::ZERO;
+    for class in ["PS", "MOOE", "CO", "DF", ""] {
+        let name = budget_class_name(Some(class));
+        let Some(lines) = expenditures.get(name) else {
+            continue;
+        };
+        rows.push(StatementRow::Section(format!("EXPENDITURES - {}", name)));
+        for ((code, category), amount) in lines {
+            rows.push(StatementRow::Body(vec![text(category.clone()), text(code.clone()), Cell::Amount(*amount)]));
+        }
+        let subtotal: Money = lines.values().sum();
+        total_expenditures += subtotal;
+        rows.push(StatementRow::Subtotal(vec![text(format!("Total {}", name)), blank(), Cell::Amount(subtotal)]));
+    }
+    rows.push(StatementRow::Subtotal(vec![text("Total Expenditures"), blank(), Cell::Amount(total_expenditures)]));
+
+    let net = total_receipts - total_expenditures;
+    rows.push(StatementRow::Total(vec![text("Excess (deficit) of receipts over expenditures"), blank(), Cell::Amount(net)]));
+    rows.push(StatementRow::Total(vec![text("Cash balance, ending"), blank(), Cell::Amount(opening + net)]));
+
+    Ok(Statement {
+        title: "Statement of Receipts and Expenditures",
+        period: period.label(),
+        orientation: Orientation::Portrait,
+        tables: vec![StatementTable { heading: "", columns: COLUMNS, rows }],
+        signatories: signatories(conn, period, "receipts_expenditures")?,
+    })
+}
+
+// Manual journal entries moving cash from the treasury into the bank
+fn deposits(conn: &Connection) -> Result<Vec<(NaiveDate, String, Money)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.date, e.description, l.credit
+             FROM journal_entries e
+             JOIN journal_lines l ON l.entry_id = e.id
+             JOIN accounts a ON a.id = l.account_id
+             WHERE e.source = 'manual' AND a.code = ?1 AND l.credit > 0
+               AND EXISTS (
+                   SELECT 1 FROM journal_lines b JOIN accounts ba ON ba.id = b.account_id
+                   WHERE b.entry_id = e.id AND ba.code = ?2 AND b.debit > 0
+               )
+             ORDER BY e.date, e.id",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![CASH_ACCOUNT, CASH_IN_BANK_ACCOUNT], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Money>(2)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut deposits = Vec::new();
+    for row in rows {
+        let (date, description, amount) = row.map_err(|e| e.to_string())?;
+        if let Some(date) = local_date(&date) {
+            deposits.push((date, description, amount));
+        }
+    }
+    Ok(deposits)
+}
+
+fn collections_and_deposits(conn: &Connection, period: &Period) -> Result<Statement, String> {
+    const COLLECTIONS: &[Column] = &[
+        Column::new("Date", 0.9),
+        Column::new("OR No.", 0.8),
+        Column::new("Payor / Particulars", 2.4),
+        Column::new("Nature of Collection", 1.4),
+        Column::new("Collecting Officer", 1.3),
+        Column::right("Amount", 1.0),
+        Column::right("Running Total", 1.1),
+    ];
+    const BY_NATURE: &[Column] = &[Column::new("Nature of Collection", 3.0), Column::right("Amount", 1.0)];
+    const DEPOSITS: &[Column] = &[
+        Column::new("Date", 0.9),
+        Column::new("Particulars", 3.6),
+        Column::right("Amount", 1.0),
+    ];
+    const SUMMARY: &[Column] = &[Column::new("Particulars", 3.0), Column::right("Amount", 1.0)];
+
+    let collections: Vec<CashEntry> = cash_entries()?
+        .into_iter()
+        .filter(|entry| !entry.receipt.is_zero())
+        .collect();
+    let deposits = deposits(conn)?;
+
+    let undeposited: Money = collections
+        .iter()
+        .filter(|entry| period.before(entry.date))
+        .map(|entry| entry.receipt)
+        .sum::<Money>()
+        - deposits.iter().filter(|(date, _, _)| period.before(*date)).map(|(_, _, amount)| *amount).sum();
+
+    let collections: Vec<&CashEntry> = collections.iter().filter(|entry| period.contains(entry.date)).collect();
+    let mut running = Money::ZERO;
+    let mut collection_rows = Vec::new();
+    for entry in &collections {
+        running += entry.receipt;
+        collection_rows.push(StatementRow::Body(vec![
+            text(short_date(entry.date)),
+            text(entry.reference.trim_start_matches("OR ").to_string()),
+            text(entry.particulars.clone()),
+            text(entry.category.clone()),
+            text(entry.collector.clone()),
+            Cell::Amount(entry.receipt),
+            Cell::Amount(running),
+        ]));
+    }
+    collection_rows.push(StatementRow::Total(vec![
+        blank(),
+        blank(),
+        text("Total collections"),
+        blank(),
+        blank(),
+        Cell::Amount(running),
+        blank(),
+    ]));
+
+    let mut nature_rows = Vec::new();
+    for (category, amount) in by_category(collections.iter().map(|entry| (entry.category.as_str(), entry.receipt))) {
+        nature_rows.push(StatementRow::Body(vec![text(category), Cell::Amount(amount)]));
+    }
+    nature_rows.push(StatementRow::Total(vec![text("Total collections"), Cell::Amount(running)]));
+
+    let mut deposit_rows = Vec::new();
+    let mut deposited = Money::ZERO;
+    for (date, description, amount) in deposits.iter().filter(|(date, _, _)| period.contains(*date)) {
+        deposited += *amount;
+        deposit_rows.push(StatementRow::Body(vec![text(short_date(*date)), text(description.clone()), Cell::Amount(*amount)]));
+    }
+    deposit_rows.push(StatementRow::Total(vec![blank(), text("Total deposits"), Cell::Amount(deposited)]));
+
+    let summary = vec![
+        StatementRow::Body(vec![text("Undeposited collections, beginning"), Cell::Amount(undeposited)]),
+        StatementRow::Body(vec![text("Add: Collections for the period"), Cell::Amount(running)]),
+        StatementRow::Body(vec![text("Less: Deposits for the period"), Cell::Amount(deposited)]),
+        StatementRow::Total(vec![text("Undeposited collections, ending"), Cell::Amount(undeposited + running - deposited)]),
+    ];
+
+    Ok(Statement {
+        title: "Report of Collections and Deposits",
+        period: period.label(),
+        orientation: Orientation::Landscape,
+        tables: vec![
+            StatementTable { heading: "A. Collections", columns: COLLECTIONS, rows: collection_rows },
+            StatementTable { heading: "B. Collections by Nature", columns: BY_NATURE, rows: nature_rows },
+            StatementTable { heading: "C. Deposits", columns: DEPOSITS, rows: deposit_rows },
+            StatementTable { heading: "D. Summary", columns: SUMMARY, rows: summary },
+        ],
+        signatories: signatories(conn, period, "collections_deposits")?,
+    })
+}
+
+pub fn build_statement(report: &str, params: &ReportParams) -> Result<Statement, String> {
+    let conn = establish_connection().map_err(|e| e.to_string())?;
+    let period = Period::from_params(params);
+    match report {
+        "cashbook" => cashbook(&conn, &period),
+        "receipts_expenditures" => receipts_and_expenditures(&conn, &period),
+        "collections_deposits" => collections_and_deposits(&conn, &period),
+        _ => Err(format!(
+            "'{}' cannot be exported to XLSX. Available statements: {}",
+            report,
+            STATEMENTS.join(", ")
+        )),
+    }
+}
+
+fn cell_text(cell: &Cell) -> String {
+    match cell {
+        Cell::Text(value) => value.clone(),
+        Cell::Amount(amount) => money(*amount),
+    }
+}
+
+pub fn statement_pdf(statement: &Statement, settings: &Settings) -> Result<Vec<u8>, String> {
+    let mut report = PdfReport::new(statement.title, statement.orientation, Letterhead::from_settings(settings))?;
+    report.letterhead();
+    report.text(&statement.title.to_uppercase(), 14.0, true, Align::Center);
+    report.text(&statement.period, 10.0, false, Align::Center);
+    report.space(3.0);
+
+    for table in &statement.tables {
+        if !table.heading.is_empty() {
+            report.ensure_space(20.0);
+            report.text(table.heading, 11.0, true, Align::Left);
+            report.space(1.0);
+        }
+        let cells = |cells: &Vec<Cell>| cells.iter().map(cell_text).collect::<Vec<String>>();
+        let rows: Vec<TableRow> = table
+            .rows
+            .iter()
+            .map(|row| match row {
+                StatementRow::Section(title) => TableRow::Section(title.clone()),
+                StatementRow::Body(row) => TableRow::Body(cells(row)),
+                StatementRow::Subtotal(row) => TableRow::Subtotal(cells(row)),
+                StatementRow::Total(row) => TableRow::Total(cells(row)),
+            })
+            .collect();
+        report.grouped_table(table.columns, &rows);
+        report.space(5.0);
+    }
+
+    report.signatures(&statement.signatories);
+
+    report.finish()
+}
+
+pub fn render_statement(report: &str, params: &ReportParams) -> Result<Vec<u8>, String> {
+    statement_pdf(&build_statement(report, params)?, &fetch_settings_command()?)
+}